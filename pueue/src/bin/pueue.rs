@@ -1,3 +1,5 @@
+use std::fs::OpenOptions;
+use std::io::Write;
 use std::path::PathBuf;
 
 use anyhow::{bail, Context, Result};
@@ -10,6 +12,7 @@ use pueue_lib::settings::Settings;
 
 use pueue::client::cli::{CliArguments, Shell, SubCommand};
 use pueue::client::client::Client;
+use pueue::client::doctor;
 
 /// This is the main entry point of the client.
 ///
@@ -33,6 +36,12 @@ async fn main() -> Result<()> {
         return create_shell_completion_file(shell, output_directory);
     }
 
+    // The schema doesn't depend on the configuration, so print it right away.
+    if let Some(SubCommand::GenerateConfigSchema) = &opt.cmd {
+        println!("{}", Settings::json_schema());
+        return Ok(());
+    }
+
     // Init the logger and set the verbosity level depending on the `-v` flags.
     let level = match opt.verbose {
         0 => LevelFilter::Error,
@@ -53,13 +62,30 @@ async fn main() -> Result<()> {
 
     SimpleLogger::init(level, logger_config).unwrap();
 
+    // The diagnostics read the configuration themselves, as it might be broken.
+    if let Some(SubCommand::Doctor) = &opt.cmd {
+        if !doctor(&opt).await {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     // Try to read settings from the configuration file.
     let (mut settings, config_found) =
         Settings::read(&opt.config).context("Failed to read configuration.")?;
 
-    // Load any requested profile.
-    if let Some(profile) = &opt.profile {
-        settings.load_profile(profile)?;
+    // Load any requested profile or the default profile.
+    settings.load_requested_profile(&opt.profile)?;
+
+    // Connect to an explicitly specified socket, regardless of the configured connection.
+    #[cfg(not(target_os = "windows"))]
+    let socket_override = opt.socket.is_some();
+    #[cfg(target_os = "windows")]
+    let socket_override = false;
+    #[cfg(not(target_os = "windows"))]
+    if let Some(socket) = &opt.socket {
+        settings.shared.use_unix_socket = true;
+        settings.shared.unix_socket_path = Some(socket.clone());
     }
 
     #[allow(deprecated)]
@@ -74,7 +100,8 @@ async fn main() -> Result<()> {
 
     // Error if no configuration file can be found, as this is an indicator, that the daemon hasn't
     // been started yet.
-    if !config_found {
+    // An explicitly specified socket doesn't need any configuration.
+    if !config_found && !socket_override {
         bail!("Couldn't find a configuration file. Did you start the daemon yet?");
     }
 
@@ -103,9 +130,42 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// All subcommands whose positional arguments are task ids.
+const TASK_ID_SUBCOMMANDS: &str =
+    "remove rm switch move stash enqueue start restart re pause kill send edit log follow fo wait";
+
+/// Bash snippet that wraps the generated completion function.
+/// Task ids and group names are completed by asking the daemon via `pueue complete`.
+const BASH_DYNAMIC_COMPLETION: &str = r#"
+_pueue_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    if [[ ${COMP_CWORD} -gt 1 && "${cur}" != -* ]]; then
+        if [[ " TASK_ID_SUBCOMMANDS " == *" ${COMP_WORDS[1]} "* || "${prev}" == "-g" || "${prev}" == "--group" ]]; then
+            local candidates
+            candidates=$(pueue complete bash "${cur}" 2>/dev/null)
+            if [[ -n "${candidates}" ]]; then
+                COMPREPLY=( ${candidates} )
+                return 0
+            fi
+        fi
+    fi
+    _pueue "$@"
+}
+complete -F _pueue_dynamic -o bashdefault -o default pueue
+"#;
+
+/// Fish snippet that adds task ids and group names, which are fetched via `pueue complete`.
+const FISH_DYNAMIC_COMPLETION: &str = r#"
+complete -c pueue -n "__fish_seen_subcommand_from TASK_ID_SUBCOMMANDS" -f -a "(pueue complete fish (commandline -ct) 2>/dev/null)"
+"#;
+
 /// [clap] is capable of creating auto-generated shell completion files.
 /// This function creates such a file for one of the supported shells and puts it into the
 /// specified output directory.
+///
+/// Static completions are extended by the dynamic completion of task ids and group names
+/// for Bash and Fish.
 fn create_shell_completion_file(shell: &Shell, output_directory: &PathBuf) -> Result<()> {
     let mut app = CliArguments::command();
     app.set_bin_name("pueue");
@@ -116,7 +176,24 @@ fn create_shell_completion_file(shell: &Shell, output_directory: &PathBuf) -> Re
         Shell::PowerShell => generate_to(shells::PowerShell, &mut app, "pueue", output_directory),
         Shell::Zsh => generate_to(shells::Zsh, &mut app, "pueue", output_directory),
     };
-    completion_result.context(format!("Failed to generate completions for {shell:?}"))?;
+    let path =
+        completion_result.context(format!("Failed to generate completions for {shell:?}"))?;
+
+    let dynamic_completion = match shell {
+        Shell::Bash => BASH_DYNAMIC_COMPLETION,
+        Shell::Fish => FISH_DYNAMIC_COMPLETION,
+        _ => return Ok(()),
+    };
+    let mut file = OpenOptions::new()
+        .append(true)
+        .open(&path)
+        .context(format!("Failed to open completion file {path:?}"))?;
+    file.write_all(
+        dynamic_completion
+            .replace("TASK_ID_SUBCOMMANDS", TASK_ID_SUBCOMMANDS)
+            .as_bytes(),
+    )
+    .context(format!("Failed to write completion file {path:?}"))?;
 
     Ok(())
 }
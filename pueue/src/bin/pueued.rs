@@ -5,7 +5,8 @@ use clap::Parser;
 use log::warn;
 use simplelog::{Config, ConfigBuilder, LevelFilter, SimpleLogger};
 
-use pueue::daemon::cli::CliArguments;
+use pueue::daemon::cli::{CliArguments, LogFormat};
+use pueue::daemon::json_logger::JsonLogger;
 use pueue::daemon::run;
 
 #[tokio::main(flavor = "multi_thread", worker_threads = 4)]
@@ -25,6 +26,11 @@ async fn main() -> Result<()> {
         _ => LevelFilter::Debug,
     };
 
+    if opt.log_format == LogFormat::Json {
+        JsonLogger::init(level).unwrap();
+        return run(opt.config, opt.profile, false).await;
+    }
+
     // Try to initialize the logger with the timezone set to the Local time of the machine.
     let mut builder = ConfigBuilder::new();
     let logger_config = match builder.set_time_offset_to_local() {
@@ -59,6 +65,11 @@ fn fork_daemon(opt: &CliArguments) -> Result<()> {
         arguments.push("-".to_string() + &"v".repeat(opt.verbose as usize));
     }
 
+    if opt.log_format == LogFormat::Json {
+        arguments.push("--log-format".to_string());
+        arguments.push("json".to_string());
+    }
+
     Command::new("pueued").args(&arguments).spawn()?;
 
     println!("Pueued is now running in the background");
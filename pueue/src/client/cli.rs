@@ -6,17 +6,29 @@ use chrono_english::*;
 use clap::ArgAction;
 use clap::{Parser, ValueEnum, ValueHint};
 
-use pueue_lib::network::message::Signal;
+use pueue_lib::network::message::{HistoryStatus, Signal};
 
 use super::commands::WaitTargetStatus;
 
+/// A shell program with its arguments, e.g. `["bash", "-c"]`.
+///
+/// This is an alias, as clap would otherwise expect multiple values for a `Vec`.
+pub type ShellCommand = Vec<String>;
+
 #[derive(Parser, Debug)]
+// The subcommand is parsed once per invocation, so the size of the `Add` variant doesn't matter.
+#[allow(clippy::large_enum_variant)]
 pub enum SubCommand {
     /// Enqueue a task for execution.
     #[command(trailing_var_arg = true)]
     Add {
         /// The command to be added.
-        #[arg(required = true, num_args(1..), value_hint = ValueHint::CommandWithArguments)]
+        /// This can be omitted, if a template is used or another task is cloned.
+        #[arg(
+            required_unless_present_any = ["template", "clone", "from_file"],
+            num_args(1..),
+            value_hint = ValueHint::CommandWithArguments
+        )]
         command: Vec<String>,
 
         /// Specify current working directory.
@@ -28,7 +40,8 @@ pub enum SubCommand {
         #[arg(short, long)]
         escape: bool,
 
-        /// Immediately start the task.
+        /// Immediately start the task, regardless of the group's parallel limit.
+        /// The task doesn't occupy one of the group's slots while it's running.
         #[arg(name = "immediate", short, long, conflicts_with = "stashed")]
         start_immediately: bool,
 
@@ -37,6 +50,12 @@ pub enum SubCommand {
         #[arg(name = "stashed", short, long, conflicts_with = "immediate")]
         stashed: bool,
 
+        /// Queue the task, but never start it automatically.
+        /// It's only started once you explicitly start it via `pueue start <id>`.
+        /// This can't be combined with `--immediate`, which starts the task right away.
+        #[arg(long, conflicts_with_all = ["immediate", "stashed", "delay"])]
+        paused: bool,
+
         /// Prevents the task from being enqueued until <delay> elapses. See "enqueue" for accepted formats.
         #[arg(name = "delay", short, long, conflicts_with = "immediate", value_parser = parse_delay_until)]
         delay_until: Option<DateTime<Local>>,
@@ -49,27 +68,166 @@ pub enum SubCommand {
 
         /// Start the task once all specified tasks have successfully finished.
         /// As soon as one of the dependencies fails, this task will fail as well.
+        /// Use `--ignore-dependency-failure` to start the task regardless of their result.
         #[arg(name = "after", short, long, num_args(1..))]
         dependencies: Vec<usize>,
 
+        /// Start the task once the most recently added task of the same group successfully finished.
+        /// If the group doesn't contain any tasks yet, no dependency is added.
+        #[arg(long)]
+        after_last: bool,
+
+        /// Only start the task, if all of its dependencies succeeded.
+        /// This is the default and only exists to make scripts more explicit.
+        #[arg(long)]
+        only_if_success: bool,
+
+        /// Start the task as soon as all of its dependencies finished, regardless of their result.
+        #[arg(long, conflicts_with = "only_if_success")]
+        ignore_dependency_failure: bool,
+
+        /// Only start the task, if the output of all its dependencies contains this text.
+        /// Otherwise, the task is marked as "Skipped" without being started.
+        /// This only has an effect in combination with `--after` or `--after-last`.
+        #[arg(long, value_name = "TEXT")]
+        when_output_contains: Option<String>,
+
+        /// Execute the command with this shell instead of the daemon's default shell,
+        /// e.g. `--shell /bin/bash`. The command is passed via `-c`, unless you specify
+        /// the shell's arguments yourself, e.g. `--shell "zsh -ic"`.
+        #[arg(long, value_parser = parse_shell)]
+        shell: Option<ShellCommand>,
+
         /// Add some information for yourself.
         /// This string will be shown in the "status" table.
         /// There's no additional logic connected to it.
         #[arg(short, long)]
         label: Option<String>,
 
+        /// Use the first word of the command as label, e.g. `cargo` for `cargo build`.
+        #[arg(long, conflicts_with = "label")]
+        auto_label: bool,
+
+        /// The color of the label in the "status" table, e.g. `red` or `dark_blue`.
+        #[arg(long, value_parser = parse_color)]
+        label_color: Option<String>,
+
+        /// Create an empty marker file once the task finished, e.g. for integration with other tools.
+        /// The result is appended to the path, i.e. `<PATH>.success` or `<PATH>.failed`.
+        /// Relative paths are resolved against the task's working directory.
+        #[arg(long, value_name = "PATH", value_hint = ValueHint::FilePath)]
+        touch_on_done: Option<PathBuf>,
+
+        /// Set an environment variable for this task, e.g. `--env KEY=VALUE`.
+        /// This can be used multiple times and overrides variables of the current environment.
+        #[arg(long = "env", value_name = "KEY=VALUE", value_parser = parse_env_var)]
+        envs: Vec<(String, String)>,
+
+        /// Run the task with a clean environment, which only contains the variables set via `--env`
+        /// or the template. Neither the current environment nor the group's variables are inherited.
+        #[arg(long)]
+        no_inherit_env: bool,
+
         /// Only return the task id instead of a text.
         /// This is useful when scripting and working with dependencies.
         #[arg(short, long)]
         print_task_id: bool,
+
+        /// Automatically kill the task, if it runs longer than this amount of seconds.
+        /// Such tasks will be marked as "Timed out".
+        #[arg(long)]
+        timeout: Option<u64>,
+
+        /// Automatically re-enqueue the task up to this many times, if it fails.
+        #[arg(long, default_value_t = 0)]
+        retries: usize,
+
+        /// The delay in seconds before a failed task is re-enqueued.
+        /// The delay doubles with each failed attempt.
+        #[arg(long, default_value_t = 0, requires = "retries")]
+        retry_delay: u64,
+
+        /// Run the task as a different user.
+        /// This requires the daemon to run as root, which drops its privileges before spawning the task.
+        #[arg(short, long)]
+        user: Option<String>,
+
+        /// Queued tasks with a higher priority are started before other tasks of the same group.
+        /// Tasks with the same priority are started by their position in the queue.
+        #[arg(long, default_value_t = 0, allow_negative_numbers = true)]
+        priority: i32,
+
+        /// Pin the task to these CPU cores, e.g. `--cpus 0,1`.
+        /// This is only supported on Linux and ignored on other platforms.
+        #[arg(long, value_delimiter = ',')]
+        cpus: Option<Vec<usize>>,
+
+        /// Run the task with this scheduling priority (niceness), e.g. `--nice 10`.
+        /// Values outside of `-20` to `19` are clamped. This is only supported on Linux.
+        #[arg(long, allow_negative_numbers = true)]
+        nice: Option<i32>,
+
+        /// The amount of parallel slots of its group this task occupies while running, e.g. for heavy tasks.
+        /// Tasks whose weight exceeds the group's limit are started once nothing else runs in the group.
+        #[arg(long, default_value_t = 1, value_parser = min_one)]
+        weight: usize,
+
+        /// Use a task template from the `client.templates` setting.
+        /// Any other given options, including the command, take precedence over the template.
+        #[arg(short = 'T', long, conflicts_with = "clone")]
+        template: Option<String>,
+
+        /// Use the command, environment variables, working directory and group of an existing task.
+        /// Any other given options, including the command, take precedence over the cloned task.
+        #[arg(long, value_name = "TASK_ID")]
+        clone: Option<usize>,
+
+        /// Add the task to a job with the given name.
+        /// All tasks of a job can be inspected and killed together via `pueue job`.
+        #[arg(long)]
+        job: Option<String>,
+
+        /// Don't add the task, if a task with the same key already exists.
+        /// The id of the existing task is returned instead.
+        /// This makes it safe to retry `pueue add` in scripts.
+        #[arg(long, value_name = "KEY", conflicts_with = "from_file")]
+        idempotency_key: Option<String>,
+
+        /// Add one task per line of the given file, e.g. `--from-file jobs.txt`.
+        /// Empty lines and lines starting with `#` are ignored.
+        /// All other given options apply to each of those tasks.
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["command", "clone"])]
+        from_file: Option<PathBuf>,
     },
     /// Remove tasks from the list.
     /// Running or paused tasks need to be killed first.
     #[command(alias("rm"))]
     Remove {
         /// The task ids to be removed.
-        #[arg(required = true)]
+        #[arg(required_unless_present_any = ["matching", "before", "older_than"])]
         task_ids: Vec<usize>,
+
+        /// Also remove all tasks whose command matches this regular expression.
+        #[arg(short, long)]
+        matching: Option<String>,
+
+        /// Also remove all finished tasks with an id lower than this one.
+        #[arg(long)]
+        before: Option<usize>,
+
+        /// Also remove all tasks that finished longer ago than this duration.
+        /// Either a number of seconds or a number with a unit, e.g. `30m`, `12h`, `7d` or `2w`.
+        #[arg(long, value_parser = parse_age)]
+        older_than: Option<Duration>,
+
+        /// Only remove tasks of this group via `--before` and `--older-than`.
+        #[arg(short, long)]
+        group: Option<String>,
+
+        /// Move the logs of the removed tasks into an archive instead of deleting them.
+        /// Archived logs can be shown via `pueue log --archived`.
+        #[arg(long)]
+        keep_logs: bool,
     },
     /// Switches the queue position of two commands.
     /// Only works on queued and stashed commands.
@@ -79,13 +237,53 @@ pub enum SubCommand {
         /// The second task id.
         task_id_2: usize,
     },
+    /// Move a task in front of another task in the queue.
+    /// Only works on queued and stashed commands.
+    Move {
+        /// The id of the task that should be moved.
+        task_id: usize,
+        /// The id of the task in front of which the task should be put.
+        #[arg(long)]
+        before: usize,
+    },
     /// Stashed tasks won't be automatically started.
     /// You have to enqueue them or start them by hand.
     Stash {
         /// Stash these specific tasks.
         #[arg(required = true)]
         task_ids: Vec<usize>,
+
+        /// Automatically enqueue the tasks again at this point in time.
+        /// See "enqueue" for accepted formats.
+        #[arg(short, long, value_parser = parse_delay_until)]
+        until: Option<DateTime<Local>>,
+    },
+    /// Hold queued tasks, so they won't be automatically started.
+    /// In contrast to stashing, held tasks keep their position in the queue.
+    /// Use "release" to let them be started again.
+    Hold {
+        /// Hold these specific tasks.
+        #[arg(required = true)]
+        task_ids: Vec<usize>,
+    },
+
+    /// Release held tasks, so they're started once it's their turn.
+    Release {
+        /// Release these specific tasks.
+        #[arg(required = true)]
+        task_ids: Vec<usize>,
     },
+
+    /// Attach a free-form note to a task, which is shown in the `note` column of `status`.
+    /// Omit the note to remove the current one.
+    Note {
+        /// The id of the task.
+        task_id: usize,
+
+        /// The new note of the task.
+        note: Option<String>,
+    },
+
     /// Enqueue stashed tasks. They'll be handled normally afterwards.
     #[command(after_help = "DELAY FORMAT:
 
@@ -108,6 +306,10 @@ pub enum SubCommand {
     1d 03:00              // The closest 3:00 after 1 day (24 hours)
     3h                    // 3 hours from now
     3600s                 // 3600 seconds from now
+    in 2 hours            // 2 hours from now
+    18:00                 // The next time it's 18:00, either today or tomorrow
+    tomorrow 9am          // Tomorrow at 09:00
+    today                 // Today at 00:00:00
 ")]
     Enqueue {
         /// Enqueue these specific tasks.
@@ -137,6 +339,15 @@ pub enum SubCommand {
         #[arg(short, long)]
         all: bool,
 
+        /// Also start all tasks whose command matches this regular expression.
+        #[arg(short, long, conflicts_with_all = ["group", "all"])]
+        matching: Option<String>,
+
+        /// Enqueue all stashed tasks of the group instead, including delayed tasks.
+        /// Use `--group` to select a group other than the default group.
+        #[arg(long, conflicts_with_all = ["task_ids", "all", "matching"])]
+        stashed: bool,
+
         /// Deprecated: this switch no longer has any effect.
         #[arg(short, long)]
         children: bool,
@@ -160,6 +371,10 @@ pub enum SubCommand {
         #[arg(short = 'g', long, conflicts_with = "all_failed")]
         failed_in_group: Option<String>,
 
+        /// Only restart the failed tasks of this group, e.g. `--all-failed --group build`.
+        #[arg(long, requires = "all_failed")]
+        group: Option<String>,
+
         /// Immediately start the tasks, no matter how many open slots there are.
         /// This will ignore any dependencies tasks may have.
         #[arg(short = 'k', long, conflicts_with = "stashed")]
@@ -191,6 +406,20 @@ pub enum SubCommand {
         /// Edit the tasks' labels before restarting.
         #[arg(short = 'l', long)]
         edit_label: bool,
+
+        /// Restart the tasks in a different working directory.
+        #[arg(
+            name = "working-directory",
+            short = 'w',
+            long,
+            conflicts_with = "edit_path",
+            value_hint = ValueHint::DirPath
+        )]
+        working_directory: Option<PathBuf>,
+
+        /// Also restart all tasks whose command matches this regular expression.
+        #[arg(short, long)]
+        matching: Option<String>,
     },
 
     /// Either pause running tasks or specific groups of tasks.
@@ -210,6 +439,10 @@ pub enum SubCommand {
         #[arg(short, long)]
         all: bool,
 
+        /// Also pause all tasks whose command matches this regular expression.
+        #[arg(short, long, conflicts_with_all = ["group", "all"])]
+        matching: Option<String>,
+
         /// Only pause the specified group and let already running tasks finish by themselves.
         #[arg(short, long)]
         wait: bool,
@@ -242,18 +475,41 @@ pub enum SubCommand {
         ///     You might enter weird invalid states, use at your own descretion.
         #[arg(short, long, ignore_case(true))]
         signal: Option<Signal>,
+
+        /// Re-queue the killed tasks instead of marking them as killed.
+        /// Groups won't be paused when using this flag.
+        /// A task can only be retried a limited amount of times, see the `max_kill_retries` setting.
+        #[arg(short, long)]
+        retry: bool,
+
+        /// Also kill all tasks whose command matches this regular expression.
+        #[arg(short, long, conflicts_with_all = ["group", "all"])]
+        matching: Option<String>,
+
+        /// Also kill all tasks that (transitively) depend on the given tasks.
+        /// Dependents that haven't been started yet are marked as "Skipped".
+        #[arg(long, conflicts_with_all = ["group", "all"])]
+        with_dependents: bool,
     },
 
     /// Send something to a task. Useful for sending confirmations such as 'y\n'.
     Send {
-        /// The id of the task.
-        task_id: usize,
+        /// The ids of the tasks.
+        /// Send the same input to multiple tasks by separating their ids with commas, e.g. `1,2,3`.
+        #[arg(value_delimiter = ',', num_args = 1, action = ArgAction::Set, required = true)]
+        task_ids: Vec<usize>,
 
         /// The input that should be sent to the process.
-        input: String,
+        /// Use `-` to send everything that's read from stdin.
+        #[arg(required_unless_present = "stdin_file", conflicts_with = "stdin_file")]
+        input: Option<String>,
+
+        /// Send the content of this file to the process.
+        #[arg(long, value_hint = ValueHint::FilePath)]
+        stdin_file: Option<PathBuf>,
     },
 
-    /// Edit the command, path or label of a stashed or queued task.
+    /// Edit the command, path, label or dependencies of a stashed or queued task.
     /// By default only the command is edited.
     /// Multiple properties can be added in one go.
     #[command(verbatim_doc_comment)]
@@ -272,6 +528,15 @@ pub enum SubCommand {
         /// Edit the task's label.
         #[arg(short, long)]
         label: bool,
+
+        /// Edit the task's dependencies as a space separated list of task ids.
+        #[arg(short, long)]
+        dependencies: bool,
+
+        /// The editor that should be used, e.g. "code --wait".
+        /// This overrides the `$EDITOR` environment variable.
+        #[arg(short, long)]
+        editor: Option<String>,
     },
 
     /// Use this to add or remove groups.
@@ -281,6 +546,13 @@ pub enum SubCommand {
         cmd: Option<GroupCommand>,
     },
 
+    /// Inspect or kill all tasks of a job.
+    /// Tasks are added to a job via `pueue add --job <name>`.
+    Job {
+        #[command(subcommand)]
+        cmd: JobCommand,
+    },
+
     /// Display the current status of all tasks.
     Status {
         /// Users can specify a custom query to filter for specific values, order by a column
@@ -296,6 +568,56 @@ pub enum SubCommand {
         #[arg(short, long)]
         /// Only show tasks of a specific group
         group: Option<String>,
+
+        /// Use a named column preset from the `client.column_presets` setting.
+        /// This is equivalent to prepending a `columns=...` clause to the query.
+        #[arg(short, long)]
+        preset: Option<String>,
+
+        /// Only print a compact overview with the amount of tasks per status for each group.
+        #[arg(long, conflicts_with = "json")]
+        group_summary: bool,
+
+        /// Show a table for each label instead of each group.
+        /// Tasks without a label are shown in a separate "unlabeled" table.
+        #[arg(long, conflicts_with_all = ["json", "group_summary"])]
+        group_by_label: bool,
+
+        /// Print the state in a stable, versioned format.
+        /// Unlike `--json`, this format doesn't change between Pueue versions.
+        #[arg(long, value_enum, conflicts_with_all = ["json", "group_summary", "group_by_label"])]
+        format: Option<StatusFormat>,
+
+        /// Refresh the status every <SECONDS> seconds, until you press `Ctrl+c`.
+        /// The screen is cleared between refreshes.
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            value_parser = clap::value_parser!(u64).range(1..),
+            conflicts_with_all = ["json", "format"]
+        )]
+        watch: Option<u64>,
+
+        /// Only request the task whose id is given as query, e.g. `pueue status 3 --single`.
+        /// This is faster than fetching the whole state, which is useful for scripts that
+        /// poll a specific task.
+        #[arg(
+            long,
+            conflicts_with_all = ["group", "preset", "group_summary", "group_by_label", "format", "watch"]
+        )]
+        single: bool,
+
+        /// Only show tasks that were running at some point after this point in time.
+        /// Either a duration ago, e.g. "1h" or "30m", a date expression, e.g. "yesterday",
+        /// or the amount of seconds ago.
+        #[arg(long, value_parser = parse_since, conflicts_with = "single")]
+        since: Option<DateTime<Local>>,
+
+        /// Only show tasks that were running at some point before this point in time,
+        /// e.g. "now" or "30m". Takes the same formats as `--since`.
+        /// Tasks that are still running are included, as long as they started before this point.
+        #[arg(long, value_parser = parse_since, conflicts_with = "single")]
+        until: Option<DateTime<Local>>,
     },
 
     /// Accept a list or map of JSON pueue tasks via stdin and display it just like "status".
@@ -310,6 +632,10 @@ pub enum SubCommand {
         group: Option<String>,
     },
 
+    /// Show a live dashboard of all tasks, which is refreshed every second.
+    /// Scroll with the arrow keys, press "k" to kill the selected task and "q" to quit.
+    Top,
+
     /// Display the log output of finished tasks.
     /// Only the last few lines will be shown by default.
     /// If you want to "follow" the output of a task, please use the "follow" subcommand.
@@ -332,6 +658,24 @@ pub enum SubCommand {
         /// Show the whole output.
         #[arg(short, long)]
         full: bool,
+
+        /// Start printing at this (zero-based) line of each task's output.
+        /// Combine with `--lines` to only print a specific range of lines.
+        #[arg(short, long, conflicts_with = "full")]
+        offset: Option<usize>,
+
+        /// Only show the stderr output of each task.
+        /// This requires the daemon's `separate_stderr` option to be enabled.
+        #[arg(long)]
+        stderr: bool,
+
+        /// Print the whole output of a single task and continue to follow it, like `follow`.
+        #[arg(long, conflicts_with_all = ["json", "lines", "full", "offset", "stderr"])]
+        follow_from_start: bool,
+
+        /// Show the logs of tasks that have been removed via `pueue remove --keep-logs`.
+        #[arg(long, conflicts_with = "follow_from_start")]
+        archived: bool,
     },
 
     /// Follow the output of a currently running task.
@@ -346,6 +690,17 @@ pub enum SubCommand {
         /// Only print the last X lines of the output before following
         #[arg(short, long)]
         lines: Option<usize>,
+
+        /// Print the output as JSON Lines.
+        /// Each line is an object with the `task_id`, the `chunk` of new output,
+        /// its byte `offset` in the followed output and a `timestamp`.
+        #[arg(short, long)]
+        json: bool,
+
+        /// Follow the output of all running tasks at once.
+        /// Each line is prefixed with the id of the task it belongs to.
+        #[arg(short, long, conflicts_with_all = ["task_id", "json"])]
+        all: bool,
     },
 
     /// Wait until tasks are finished. This can be quite useful for scripting.
@@ -369,8 +724,14 @@ pub enum SubCommand {
         quiet: bool,
 
         /// Wait for tasks to reach a specific task status.
+        /// With "success" or "failed", the client exits with code 1 as soon as a task fails.
         #[arg(short, long)]
         status: Option<WaitTargetStatus>,
+
+        /// Stop waiting after this many seconds.
+        /// The client exits with code 2, if the tasks didn't reach the target status in time.
+        #[arg(short, long)]
+        timeout: Option<u64>,
     },
 
     /// Remove all finished tasks from the list.
@@ -382,6 +743,32 @@ pub enum SubCommand {
         /// Only clean tasks of a specific group
         #[arg(short, long)]
         group: Option<String>,
+
+        /// Only print the ids of the tasks that would be removed, without removing them.
+        #[arg(short, long)]
+        dry_run: bool,
+    },
+
+    /// Show tasks that have been removed via "clean".
+    /// Cleaned tasks are archived by the daemon.
+    History {
+        /// Only show tasks that finished after this point in time.
+        /// Either a duration ago, e.g. "1h", a date expression, e.g. "yesterday",
+        /// or the amount of seconds ago.
+        #[arg(long, value_parser = parse_since)]
+        since: Option<DateTime<Local>>,
+
+        /// Only show tasks of a specific group.
+        #[arg(short, long)]
+        group: Option<String>,
+
+        /// Only show tasks with a specific result: "success" or "failed".
+        #[arg(short, long)]
+        status: Option<HistoryStatus>,
+
+        /// Print the archived tasks as json.
+        #[arg(short, long)]
+        json: bool,
     },
 
     /// Kill all tasks, clean up afterwards and reset EVERYTHING!
@@ -393,13 +780,32 @@ pub enum SubCommand {
         /// Don't ask for any confirmation.
         #[arg(short, long)]
         force: bool,
+
+        /// Only reset this group.
+        /// Its running tasks are killed and all of its tasks are removed.
+        /// Other groups aren't touched.
+        #[arg(short, long)]
+        group: Option<String>,
     },
 
     /// Remotely shut down the daemon. Should only be used if the daemon isn't started by a service manager.
-    Shutdown,
+    Shutdown {
+        /// Don't kill running tasks. Stop scheduling new tasks and wait for running tasks to finish
+        /// instead. Queued tasks are preserved for the next start of the daemon.
+        /// Paused tasks keep the daemon waiting until they're resumed or the timeout is hit.
+        #[arg(long)]
+        graceful: bool,
+
+        /// Kill all remaining tasks after this amount of seconds, when shutting down gracefully.
+        #[arg(long, requires = "graceful")]
+        timeout: Option<u64>,
+    },
 
     /// Set the amount of allowed parallel tasks.
     /// By default, adjusts the amount of the default group.
+    ///
+    /// If no amount is given, the current limit of the group is printed instead.
+    /// Without a group, an overview of all groups is shown.
     Parallel {
         /// The amount of allowed parallel tasks.
         #[arg(value_parser = min_one)]
@@ -410,6 +816,34 @@ pub enum SubCommand {
         group: Option<String>,
     },
 
+    /// Print metrics about all tasks and groups in the Prometheus text format.
+    /// This includes the amount of tasks by status and the usage of each group.
+    Metrics,
+
+    /// Print the full state of the daemon as json, e.g. `pueue export > backup.json`.
+    /// The output can be restored via `pueue import`.
+    Export,
+
+    /// Add all tasks and groups of a file that has been created via `pueue export`.
+    /// Imported tasks get new ids. Tasks that were running during the export are queued again.
+    Import {
+        /// The path to the exported state.
+        #[arg(value_hint = ValueHint::FilePath)]
+        file: PathBuf,
+    },
+
+    /// Check the configuration, the connection to the daemon and the pueue directory and
+    /// print a report.
+    /// Exits with a non-zero exit code, if any critical check fails.
+    Doctor,
+
+    /// Show information about the daemon, such as its version, uptime and pid.
+    Info {
+        /// Print the information as json.
+        #[arg(short, long)]
+        json: bool,
+    },
+
     /// Generates shell completion files.
     /// This can be ignored during normal operations.
     Completions {
@@ -420,6 +854,22 @@ pub enum SubCommand {
         #[arg(value_hint = ValueHint::DirPath)]
         output_directory: PathBuf,
     },
+
+    /// Print a JSON Schema of the configuration file.
+    /// Editors can use it to validate `pueue.yml` and to offer completion.
+    GenerateConfigSchema,
+
+    /// Print the task ids and group names that start with the given word.
+    /// This is used by the generated shell completion files for dynamic completion.
+    #[command(hide = true)]
+    Complete {
+        /// The shell that requests the completion.
+        #[arg(value_enum)]
+        shell: Shell,
+        /// The word that's currently being completed.
+        #[arg(default_value = "")]
+        current_word: String,
+    },
 }
 
 #[derive(Parser, Debug)]
@@ -429,6 +879,7 @@ pub enum GroupCommand {
         name: String,
 
         /// Set the amount of parallel tasks this group can have.
+        /// If the group already exists, its limit is updated.
         #[arg(short, long, value_parser = min_one)]
         parallel: Option<usize>,
     },
@@ -436,6 +887,21 @@ pub enum GroupCommand {
     /// Remove a group by name.
     /// This will move all tasks in this group to the default group!
     Remove { name: String },
+
+    /// Rename a group.
+    /// All tasks and the parallel setting of the group are moved to the new name.
+    /// Groups that are configured via the `group_settings` cannot be renamed.
+    Rename { name: String, new_name: String },
+}
+
+#[derive(Parser, Debug)]
+pub enum JobCommand {
+    /// Show the status of all tasks of a job.
+    Status { name: String },
+
+    /// Kill all running tasks of a job.
+    /// Tasks of the job that haven't been started yet are marked as skipped.
+    Kill { name: String },
 }
 
 #[derive(Parser, ValueEnum, Debug, Clone, PartialEq, Eq)]
@@ -445,6 +911,15 @@ pub enum ColorChoice {
     Always,
 }
 
+/// Machine-readable formats of the `status` output.
+#[derive(Parser, ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatusFormat {
+    /// Version 1 of the stable json schema.
+    JsonV1,
+    /// One line per task with its id, status, group and command, separated by tabs.
+    Line,
+}
+
 #[derive(Parser, ValueEnum, Debug, Clone, PartialEq, Eq)]
 pub enum Shell {
     Bash,
@@ -467,7 +942,8 @@ pub struct CliArguments {
     pub verbose: u8,
 
     /// Colorize the output; auto enables color output when connected to a tty.
-    #[arg(long, value_enum, default_value = "auto")]
+    /// `never` disables all styling, `always` enforces it even if the output is piped.
+    #[arg(long, value_enum, default_value = "auto", global = true)]
     pub color: ColorChoice,
 
     /// Path to a specific pueue config file to use.
@@ -476,28 +952,198 @@ pub struct CliArguments {
     pub config: Option<PathBuf>,
 
     /// The name of the profile that should be loaded from your config file.
+    /// If this isn't given, the `default_profile` from your config file is used, if it's set.
     #[arg(short, long)]
     pub profile: Option<String>,
 
+    /// Connect to the daemon via this unix socket.
+    /// This overrides the socket settings of your config file, which is useful to talk to
+    /// a different daemon, e.g. a test instance.
+    #[cfg(not(target_os = "windows"))]
+    #[arg(long, value_hint = ValueHint::FilePath)]
+    pub socket: Option<PathBuf>,
+
     #[command(subcommand)]
     pub cmd: Option<SubCommand>,
 }
 
-fn parse_delay_until(src: &str) -> Result<DateTime<Local>, String> {
+/// Parse the point in time at which a task should be enqueued.
+///
+/// On top of the date expressions that are supported by `chrono_english`, this accepts
+/// expressions like `in 2 hours`, plain times of the day like `18:00` or `9am` and `today` or
+/// `tomorrow` followed by an optional time of the day.
+pub fn parse_delay_until(src: &str) -> Result<DateTime<Local>, String> {
+    let src = src.trim();
     if let Ok(seconds) = src.parse::<i64>() {
         let delay_until = Local::now() + Duration::seconds(seconds);
         return Ok(delay_until);
     }
 
+    let now = Local::now();
+    let today = now.naive_local().date();
+    let lowercase = src.to_lowercase();
+    // `in 2 hours` is the same as `2 hours`.
+    let expression = lowercase.strip_prefix("in ").unwrap_or(&lowercase).trim();
+
+    // A plain time of the day refers to its next occurrence.
+    if let Some(time) = parse_time_of_day(expression) {
+        let date_time = local_date_time(today, time, src)?;
+        if date_time > now {
+            return Ok(date_time);
+        }
+        return local_date_time(today + Duration::days(1), time, src);
+    }
+
+    for (day, offset) in [("today", 0), ("tomorrow", 1)] {
+        let Some(rest) = expression.strip_prefix(day) else {
+            continue;
+        };
+        let time = match rest.trim() {
+            "" => NaiveTime::from_hms_opt(0, 0, 0).unwrap(),
+            time if rest.starts_with(' ') => {
+                parse_time_of_day(time).ok_or_else(|| delay_format_error(src))?
+            }
+            _ => continue,
+        };
+        return local_date_time(today + Duration::days(offset), time, src);
+    }
+
+    if let Ok(date_time) = parse_date_string(expression, now, Dialect::Us) {
+        return Ok(date_time);
+    }
+
+    Err(delay_format_error(src))
+}
+
+/// Parse a time of the day such as `18:00`, `18:00:30`, `9am` or `9:30pm`.
+fn parse_time_of_day(src: &str) -> Option<NaiveTime> {
+    let (time, pm) = if let Some(time) = src.strip_suffix("am") {
+        (time.trim_end(), Some(false))
+    } else if let Some(time) = src.strip_suffix("pm") {
+        (time.trim_end(), Some(true))
+    } else {
+        (src, None)
+    };
+
+    // A plain number without am/pm isn't a time of the day.
+    if pm.is_none() && !time.contains(':') {
+        return None;
+    }
+
+    let mut parts = time.split(':');
+    let hour: u32 = parts.next()?.parse().ok()?;
+    let minute: u32 = parts.next().map_or(Some(0), |minute| minute.parse().ok())?;
+    let second: u32 = parts.next().map_or(Some(0), |second| second.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let hour = match pm {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+        None => hour,
+    };
+
+    NaiveTime::from_hms_opt(hour, minute, second)
+}
+
+/// Combine a date and a time in the local timezone.
+fn local_date_time(date: NaiveDate, time: NaiveTime, src: &str) -> Result<DateTime<Local>, String> {
+    Local
+        .from_local_datetime(&date.and_time(time))
+        .earliest()
+        .ok_or_else(|| delay_format_error(src))
+}
+
+fn delay_format_error(src: &str) -> String {
+    format!(
+        "could not parse \"{src}\" as a point in time. Accepted formats are a number of seconds \
+        (3600), relative expressions (in 2 hours, 1 week, 3h), times of the day (18:00, 9am), \
+        days with an optional time (today, tomorrow 9am) and dates (2020-04-01T18:30:00, \
+        April 1 2020 18:30, wednesday 10:30pm). See `pueue enqueue --help` for more examples."
+    )
+}
+
+fn parse_since(src: &str) -> Result<DateTime<Local>, String> {
+    if src.trim() == "now" {
+        return Ok(Local::now());
+    }
+
+    // Plain numbers are handled as seconds ago.
+    if let Ok(age) = parse_age(src) {
+        return Ok(Local::now() - age);
+    }
+
     if let Ok(date_time) = parse_date_string(src, Local::now(), Dialect::Us) {
         return Ok(date_time);
     }
 
     Err(String::from(
-        "could not parse as seconds or date expression",
+        "could not parse as duration, seconds or date expression",
     ))
 }
 
+/// Parse a duration such as `3600`, `30m`, `12h`, `7d` or `2w`.
+/// Plain numbers are interpreted as seconds.
+fn parse_age(src: &str) -> Result<Duration, String> {
+    let src = src.trim();
+    let split = src
+        .find(|char: char| !char.is_ascii_digit())
+        .unwrap_or(src.len());
+    let (amount, unit) = src.split_at(split);
+    let error = || {
+        format!(
+            "Invalid duration \"{src}\". \
+            Expected a number with an optional unit, e.g. 30m, 12h or 7d"
+        )
+    };
+
+    let amount: i64 = amount.parse().map_err(|_| error())?;
+    match unit.trim() {
+        "" | "s" => Ok(Duration::seconds(amount)),
+        "m" => Ok(Duration::minutes(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        "d" => Ok(Duration::days(amount)),
+        "w" => Ok(Duration::weeks(amount)),
+        _ => Err(error()),
+    }
+}
+
+/// Parse an environment variable in the `KEY=VALUE` format.
+fn parse_env_var(src: &str) -> Result<(String, String), String> {
+    match src.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!(
+            "Invalid environment variable \"{src}\". Expected the format KEY=VALUE"
+        )),
+    }
+}
+
+/// Make sure that the given color name is known.
+fn parse_color(src: &str) -> Result<String, String> {
+    match crossterm::style::Color::try_from(src) {
+        Ok(_) => Ok(src.to_lowercase()),
+        Err(_) => Err(format!(
+            "Unknown color \"{src}\". Available colors are black, red, green, yellow, blue, \
+            magenta, cyan, grey, white and their dark_ variants, e.g. dark_red"
+        )),
+    }
+}
+
+/// Split a shell into its program and arguments, e.g. `"bash -c"`.
+/// If only a program is given, `-c` is added, which is understood by all common Unix shells.
+fn parse_shell(src: &str) -> Result<ShellCommand, String> {
+    let mut shell: ShellCommand = src.split_whitespace().map(ToString::to_string).collect();
+    match shell.len() {
+        0 => Err("The shell must not be empty".into()),
+        1 => {
+            shell.push("-c".into());
+            Ok(shell)
+        }
+        _ => Ok(shell),
+    }
+}
+
 /// Validator function. The input string has to be parsable as int and bigger than 0
 fn min_one(value: &str) -> Result<usize, String> {
     match value.parse::<usize>() {
@@ -1,17 +1,23 @@
 use std::env::{current_dir, vars};
+use std::fs::read_to_string;
 use std::io::{self, stdout, Write};
-use std::{borrow::Cow, collections::HashMap};
+use std::{
+    borrow::Cow,
+    collections::{BTreeSet, HashMap},
+};
 
 use anyhow::{bail, Context, Result};
+use chrono::Local;
 use clap::crate_version;
 use crossterm::tty::IsTty;
 use log::error;
+use regex::Regex;
 
 use pueue_lib::network::message::*;
 use pueue_lib::network::protocol::*;
-use pueue_lib::network::secret::read_shared_secret;
+use pueue_lib::network::secret::{read_shared_secret, SECRET_ENV_VAR};
 use pueue_lib::settings::Settings;
-use pueue_lib::state::PUEUE_DEFAULT_GROUP;
+use pueue_lib::state::{State, PUEUE_DEFAULT_GROUP};
 
 use crate::client::cli::{CliArguments, ColorChoice, GroupCommand, SubCommand};
 use crate::client::commands::*;
@@ -100,8 +106,14 @@ impl Client {
             // Only show warnings if we aren't supposed to output json.
             let show_warning = if let Some(subcommand) = &opt.cmd {
                 match subcommand {
-                    SubCommand::Status { json, .. } => !json,
+                    SubCommand::Status { json, format, .. } => !json && format.is_none(),
                     SubCommand::Log { json, .. } => !json,
+                    SubCommand::History { json, .. } => !json,
+                    SubCommand::Follow { json, .. } => !json,
+                    SubCommand::Info { json } => !json,
+                    SubCommand::Export => false,
+                    SubCommand::Metrics => false,
+                    SubCommand::Complete { .. } => false,
                     _ => true,
                 }
             } else {
@@ -130,6 +142,14 @@ impl Client {
             json: false,
             group: None,
             query: Vec::new(),
+            preset: None,
+            group_summary: false,
+            group_by_label: false,
+            format: None,
+            watch: None,
+            single: false,
+            since: None,
+            until: None,
         });
 
         Ok(Client {
@@ -146,6 +166,15 @@ impl Client {
     ///
     /// The command handling is splitted into "simple" and "complex" commands.
     pub async fn start(&mut self) -> Result<()> {
+        // Use the daemon's id prefix for rendering task ids.
+        self.resolve_id_prefix().await?;
+        // Resolve the tasks that are selected via a `--matching` pattern.
+        self.resolve_matching_tasks().await?;
+        // Add the dependents of the selected tasks, if they should be killed as well.
+        self.resolve_dependent_tasks().await?;
+        // Resolve the finished tasks that are selected via `remove --before/--older-than`.
+        self.resolve_finished_tasks().await?;
+
         // Return early, if the command has already been handled.
         if self.handle_complex_command().await? {
             return Ok(());
@@ -157,6 +186,154 @@ impl Client {
         Ok(())
     }
 
+    /// Task ids are rendered with the `id_prefix` of the daemon we're connected to.
+    /// Ask the daemon for its prefix, instead of relying on the client's local configuration.
+    async fn resolve_id_prefix(&mut self) -> Result<()> {
+        if !matches!(
+            self.subcommand,
+            SubCommand::Status { .. } | SubCommand::Log { .. } | SubCommand::History { .. }
+        ) {
+            return Ok(());
+        }
+
+        send_message(Message::Info, &mut self.stream).await?;
+        self.settings.shared.id_prefix = match receive_message(&mut self.stream).await? {
+            Message::InfoResponse(info) => info.id_prefix,
+            _ => None,
+        };
+
+        Ok(())
+    }
+
+    /// Some commands allow to select tasks by a regular expression on their command.
+    /// Resolve the ids of those tasks and add them to the explicitly specified task ids.
+    /// That way, the daemon only receives a list of task ids as usual.
+    async fn resolve_matching_tasks(&mut self) -> Result<()> {
+        let (task_ids, pattern) = match &mut self.subcommand {
+            SubCommand::Kill {
+                task_ids, matching, ..
+            }
+            | SubCommand::Remove {
+                task_ids, matching, ..
+            }
+            | SubCommand::Restart {
+                task_ids, matching, ..
+            }
+            | SubCommand::Start {
+                task_ids, matching, ..
+            }
+            | SubCommand::Pause {
+                task_ids, matching, ..
+            } => match matching {
+                Some(pattern) => (task_ids, pattern),
+                None => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+
+        let regex = Regex::new(pattern)
+            .with_context(|| format!("Invalid regular expression '{pattern}'"))?;
+
+        let state = get_state(&mut self.stream).await?;
+        let matching_ids: Vec<usize> = state
+            .tasks
+            .values()
+            .filter(|task| regex.is_match(&task.command))
+            .map(|task| task.id)
+            .collect();
+
+        // Don't continue with an empty selection.
+        // Otherwise, some commands would fall back to the default group.
+        if matching_ids.is_empty() && task_ids.is_empty() {
+            bail!("No tasks match the regular expression '{pattern}'");
+        }
+
+        task_ids.extend(matching_ids);
+        task_ids.sort_unstable();
+        task_ids.dedup();
+
+        Ok(())
+    }
+
+    /// `remove --before` and `remove --older-than` select finished tasks by their id or end time.
+    /// Resolve their ids and add them to the selected task ids.
+    /// Tasks that haven't finished yet are never selected.
+    async fn resolve_finished_tasks(&mut self) -> Result<()> {
+        let SubCommand::Remove {
+            task_ids,
+            before,
+            older_than,
+            group,
+            ..
+        } = &mut self.subcommand
+        else {
+            return Ok(());
+        };
+        if before.is_none() && older_than.is_none() {
+            return Ok(());
+        }
+
+        let cutoff = older_than.map(|older_than| Local::now() - older_than);
+        let state = get_state(&mut self.stream).await?;
+        let finished_ids: Vec<usize> = state
+            .tasks
+            .values()
+            .filter(|task| task.is_done())
+            .filter(|task| group.as_ref().map_or(true, |group| &task.group == group))
+            .filter(|task| before.map_or(true, |before| task.id < before))
+            .filter(|task| match (cutoff, task.end) {
+                (Some(cutoff), Some(end)) => end < cutoff,
+                (Some(_), None) => false,
+                (None, _) => true,
+            })
+            .map(|task| task.id)
+            .collect();
+
+        if finished_ids.is_empty() && task_ids.is_empty() {
+            bail!("No finished tasks match the given filters");
+        }
+
+        task_ids.extend(finished_ids);
+        task_ids.sort_unstable();
+        task_ids.dedup();
+
+        Ok(())
+    }
+
+    /// `kill --with-dependents` also targets all tasks that (transitively) depend on the
+    /// selected tasks. Resolve their ids and add them to the selected task ids.
+    async fn resolve_dependent_tasks(&mut self) -> Result<()> {
+        let SubCommand::Kill {
+            task_ids,
+            with_dependents: true,
+            ..
+        } = &mut self.subcommand
+        else {
+            return Ok(());
+        };
+
+        let state = get_state(&mut self.stream).await?;
+        let mut selected: BTreeSet<usize> = task_ids.iter().copied().collect();
+        // Keep adding dependents, until no new ones are found.
+        loop {
+            let dependents: Vec<usize> = state
+                .tasks
+                .values()
+                .filter(|task| !selected.contains(&task.id))
+                .filter(|task| task.dependencies.iter().any(|id| selected.contains(id)))
+                .map(|task| task.id)
+                .collect();
+            if dependents.is_empty() {
+                break;
+            }
+            selected.extend(dependents);
+        }
+
+        *task_ids = selected.into_iter().collect();
+
+        Ok(())
+    }
+
     /// Handle all complex client-side functionalities.
     /// Complex functionalities need some special handling and are contained
     /// in their own functions with their own communication code.
@@ -169,13 +346,15 @@ impl Client {
     async fn handle_complex_command(&mut self) -> Result<bool> {
         // This match handles all "complex" commands.
         match &self.subcommand {
-            SubCommand::Reset { force, .. } => {
+            SubCommand::Reset { force, group, .. } => {
                 // Get the current state and check if there're any running tasks.
                 // If there are, ask the user if they really want to reset the state.
+                // If only a single group is reset, only its tasks are relevant.
                 let state = get_state(&mut self.stream).await?;
                 let running_tasks = state
                     .tasks
                     .iter()
+                    .filter(|(_, task)| group.as_ref().map_or(true, |group| &task.group == group))
                     .filter_map(|(id, task)| if task.is_running() { Some(*id) } else { None })
                     .collect::<Vec<_>>();
 
@@ -193,8 +372,45 @@ impl Client {
                 command,
                 path,
                 label,
+                dependencies,
+                editor,
             } => {
-                let message = edit(&mut self.stream, *task_id, *command, *path, *label).await?;
+                let message = edit(
+                    &mut self.stream,
+                    *task_id,
+                    *command,
+                    *path,
+                    *label,
+                    *dependencies,
+                    editor.as_deref(),
+                )
+                .await?;
+                self.handle_response(message)?;
+                Ok(true)
+            }
+            SubCommand::Send {
+                task_ids,
+                input,
+                stdin_file,
+            } if stdin_file.is_some() || input.as_deref() == Some("-") => {
+                let message =
+                    send_input(&mut self.stream, task_ids.clone(), stdin_file.as_deref()).await?;
+                self.handle_response(message)?;
+                Ok(true)
+            }
+            SubCommand::Job { cmd } => {
+                if let Some(message) =
+                    job(&mut self.stream, cmd, &self.style, &self.settings).await?
+                {
+                    self.handle_response(message)?;
+                }
+                Ok(true)
+            }
+            SubCommand::Stash {
+                task_ids,
+                until: Some(until),
+            } => {
+                let message = stash_until(&mut self.stream, task_ids.clone(), *until).await?;
                 self.handle_response(message)?;
                 Ok(true)
             }
@@ -204,15 +420,25 @@ impl Client {
                 all,
                 quiet,
                 status,
+                timeout,
             } => {
                 let selection = selection_from_params(*all, group, task_ids);
-                wait(&mut self.stream, &self.style, selection, *quiet, status).await?;
+                wait(
+                    &mut self.stream,
+                    &self.style,
+                    selection,
+                    *quiet,
+                    status,
+                    *timeout,
+                )
+                .await?;
                 Ok(true)
             }
             SubCommand::Restart {
                 task_ids,
                 all_failed,
                 failed_in_group,
+                group,
                 start_immediately,
                 stashed,
                 in_place,
@@ -220,6 +446,8 @@ impl Client {
                 edit,
                 edit_path,
                 edit_label,
+                working_directory,
+                ..
             } => {
                 // `not_in_place` superseeds both other configs
                 let in_place =
@@ -228,18 +456,49 @@ impl Client {
                     &mut self.stream,
                     task_ids.clone(),
                     *all_failed,
-                    failed_in_group.clone(),
+                    // `--group` narrows `--all-failed` down to a single group.
+                    failed_in_group.clone().or_else(|| group.clone()),
                     *start_immediately,
                     *stashed,
                     in_place,
                     *edit,
                     *edit_path,
                     *edit_label,
+                    working_directory.clone(),
                 )
                 .await?;
                 Ok(true)
             }
-            SubCommand::Follow { task_id, lines } => {
+            SubCommand::Log {
+                task_ids,
+                follow_from_start: true,
+                ..
+            } => {
+                let [task_id] = task_ids[..] else {
+                    bail!("--follow-from-start requires exactly one task id");
+                };
+                // Local logs are followed from the start of the file, if no lines are given.
+                if self.settings.client.read_local_logs {
+                    local_follow(
+                        &mut self.stream,
+                        &self.settings.shared.pueue_directory(),
+                        &Some(task_id),
+                        None,
+                        false,
+                        false,
+                    )
+                    .await?;
+                } else {
+                    remote_follow_from_start(&mut self.stream, task_id).await?;
+                }
+                Ok(true)
+            }
+            SubCommand::Follow {
+                task_id,
+                lines,
+                json,
+                all,
+            } => {
                 // Simple log output follows for local logs don't need any communication with the daemon.
                 // Thereby we handle this separately over here.
                 if self.settings.client.read_local_logs {
@@ -248,12 +507,87 @@ impl Client {
                         &self.settings.shared.pueue_directory(),
                         task_id,
                         *lines,
+                        *json,
+                        *all,
                     )
                     .await?;
                     return Ok(true);
                 }
+                if *all {
+                    remote_follow_all(&mut self.stream, *lines).await?;
+                    return Ok(true);
+                }
+                if *json {
+                    remote_follow_json(&mut self.stream, task_id, *lines).await?;
+                    return Ok(true);
+                }
                 Ok(false)
             }
+            SubCommand::Top => {
+                top(&mut self.stream, &self.settings).await?;
+                Ok(true)
+            }
+            SubCommand::Metrics => {
+                let state = get_state(&mut self.stream).await?;
+                print!("{}", format_metrics(&state));
+                Ok(true)
+            }
+            SubCommand::Status {
+                watch: Some(interval),
+                ..
+            } => {
+                watch_status(
+                    &mut self.stream,
+                    *interval,
+                    &self.subcommand,
+                    &self.style,
+                    &self.settings,
+                )
+                .await?;
+                Ok(true)
+            }
+            SubCommand::Complete {
+                shell,
+                current_word,
+            } => {
+                complete(&mut self.stream, shell, current_word).await?;
+                Ok(true)
+            }
+            SubCommand::Clean {
+                successful_only,
+                group,
+                dry_run: true,
+            } => {
+                // Use the same selection as the daemon, but don't remove anything.
+                let state = get_state(&mut self.stream).await?;
+                let task_ids = state.cleanable_task_ids(*successful_only, group.as_deref());
+                if task_ids.is_empty() {
+                    println!("No tasks would be removed");
+                } else {
+                    let task_ids = task_ids
+                        .iter()
+                        .map(|id| id.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ");
+                    println!("The following tasks would be removed: {task_ids}");
+                }
+                Ok(true)
+            }
+            SubCommand::Parallel {
+                parallel_tasks: None,
+                group: Some(group),
+            } => {
+                // Only query the current limit of the given group.
+                let state = get_state(&mut self.stream).await?;
+                let Some(group_info) = state.groups.get(group) else {
+                    bail!("Group \"{group}\" doesn't exist");
+                };
+                println!(
+                    "Group \"{group}\" allows {} parallel tasks",
+                    group_info.parallel_tasks
+                );
+                Ok(true)
+            }
             SubCommand::FormatStatus { .. } => {
                 format_state(
                     &mut self.stream,
@@ -277,7 +611,7 @@ impl Client {
     async fn handle_simple_command(&mut self) -> Result<()> {
         // Create the message that should be sent to the daemon
         // depending on the given commandline options.
-        let message = self.get_message_from_opt()?;
+        let message = self.get_message_from_opt().await?;
 
         // Create the message payload and send it to the daemon.
         send_message(message, &mut self.stream).await?;
@@ -311,9 +645,24 @@ impl Client {
                     print_state(*state, tasks, &self.subcommand, &self.style, &self.settings)?;
                 println!("{output}");
             }
+            Message::TaskResponse(task) => {
+                let output = print_task(*task, &self.subcommand, &self.style, &self.settings);
+                println!("{output}");
+            }
             Message::LogResponse(task_logs) => {
                 print_logs(task_logs, &self.subcommand, &self.style, &self.settings)
             }
+            Message::HistoryResponse(tasks) => {
+                let output = print_history(tasks, &self.subcommand, &self.style, &self.settings);
+                println!("{output}");
+            }
+            Message::InfoResponse(info) => {
+                let output = print_info(info, &self.subcommand, &self.style);
+                println!("{output}");
+            }
+            Message::ExportResponse(state) => {
+                println!("{}", serde_json::to_string_pretty(&state)?);
+            }
             Message::GroupResponse(groups) => {
                 let group_text = format_groups(groups, &self.style);
                 println!("{group_text}");
@@ -371,7 +720,7 @@ impl Client {
     ///
     /// This function is pretty large, but it consists mostly of simple conversions
     /// of [SubCommand] variant to a [Message] variant.
-    fn get_message_from_opt(&self) -> Result<Message> {
+    async fn get_message_from_opt(&mut self) -> Result<Message> {
         Ok(match &self.subcommand {
             SubCommand::Add {
                 command,
@@ -379,17 +728,64 @@ impl Client {
                 escape,
                 start_immediately,
                 stashed,
+                paused,
                 group,
                 delay_until,
                 dependencies,
+                after_last,
+                only_if_success: _,
+                ignore_dependency_failure,
+                when_output_contains,
+                shell,
                 label,
+                auto_label,
+                label_color,
+                touch_on_done,
+                envs: envs_override,
+                no_inherit_env,
                 print_task_id,
+                timeout,
+                retries,
+                retry_delay,
+                user,
+                priority,
+                cpus,
+                nice,
+                weight,
+                template,
+                clone,
+                job,
+                idempotency_key,
+                from_file,
             } => {
-                // Either take the user-specified path or default to the current working directory.
-                let path = working_directory
-                    .as_ref()
-                    .map(|path| Ok(path.clone()))
-                    .unwrap_or_else(current_dir)?;
+                let template = match template {
+                    Some(name) => match self.settings.client.templates.get(name) {
+                        Some(template) => Some(template.clone()),
+                        None => bail!(
+                            "Unknown template \"{name}\". Check the `client.templates` setting."
+                        ),
+                    },
+                    None => None,
+                };
+
+                let cloned_task = match clone {
+                    Some(task_id) => {
+                        let mut state = get_state(&mut self.stream).await?;
+                        match state.tasks.remove(task_id) {
+                            Some(task) => Some(task),
+                            None => bail!("There's no task with id {task_id} to clone."),
+                        }
+                    }
+                    None => None,
+                };
+
+                // Either take the user-specified path, the path of the cloned task or default to
+                // the current working directory.
+                let path = match (working_directory, &cloned_task) {
+                    (Some(path), _) => path.clone(),
+                    (None, Some(task)) => task.path.clone(),
+                    (None, None) => current_dir()?,
+                };
 
                 let mut command = command.clone();
                 // The user can request to escape any special shell characters in all parameter strings before
@@ -401,28 +797,147 @@ impl Client {
                         .collect();
                 }
 
-                AddMessage {
+                // Catch the current environment for later injection into the task's process.
+                // Tasks with a clean environment only get explicitly specified variables.
+                let mut envs = if *no_inherit_env {
+                    HashMap::new()
+                } else {
+                    HashMap::from_iter(vars())
+                };
+                // Never leak the daemon's secret into the state and the task's environment.
+                envs.remove(SECRET_ENV_VAR);
+                let mut group = group.clone();
+                let mut label = label.clone();
+                // Fill everything that hasn't been explicitly specified from the cloned task.
+                if let Some(task) = cloned_task {
+                    if command.is_empty() {
+                        command = vec![task.original_command];
+                    }
+                    group = group.or(Some(task.group));
+                    if !*no_inherit_env {
+                        envs = task.envs;
+                    }
+                }
+                // Fill everything that hasn't been explicitly specified from the template.
+                if let Some(template) = template {
+                    if command.is_empty() {
+                        command = vec![template.command];
+                    }
+                    group = group.or(template.group);
+                    label = label.or(template.label);
+                    envs.extend(template.envs);
+                }
+                // Explicitly specified variables take precedence over everything else.
+                envs.extend(envs_override.iter().cloned());
+
+                // Label the task with the first word of its command, if no label has been given.
+                if *auto_label && label.is_none() {
+                    label = command
+                        .join(" ")
+                        .split_whitespace()
+                        .next()
+                        .map(ToString::to_string);
+                }
+
+                let group = group_or_default(&group);
+                let mut dependencies = dependencies.to_vec();
+                // Depend on the task with the highest id in the same group, if there's any.
+                if *after_last {
+                    let state = get_state(&mut self.stream).await?;
+                    let last_task = state
+                        .tasks
+                        .values()
+                        .filter(|task| task.group == group)
+                        .map(|task| task.id)
+                        .max();
+                    dependencies.extend(last_task);
+                }
+
+                // Relative marker paths are resolved against the task's working directory.
+                let touch_on_done = touch_on_done.as_ref().map(|marker| path.join(marker));
+
+                let message = AddMessage {
                     command: command.join(" "),
                     path,
-                    // Catch the current environment for later injection into the task's process.
-                    envs: HashMap::from_iter(vars()),
+                    envs,
                     start_immediately: *start_immediately,
                     stashed: *stashed,
-                    group: group_or_default(group),
+                    group,
                     enqueue_at: *delay_until,
-                    dependencies: dependencies.to_vec(),
-                    label: label.clone(),
+                    dependencies,
+                    label,
                     print_task_id: *print_task_id,
+                    timeout: *timeout,
+                    retries: *retries,
+                    retry_delay: *retry_delay,
+                    user: user.clone(),
+                    path_is_default: working_directory.is_none() && clone.is_none(),
+                    priority: *priority,
+                    cpu_affinity: cpus.clone(),
+                    nice: *nice,
+                    when_output_contains: when_output_contains.clone(),
+                    shell: shell.clone(),
+                    manual_start: *paused,
+                    only_if_success: !*ignore_dependency_failure,
+                    label_color: label_color.clone(),
+                    touch_on_done,
+                    weight: *weight,
+                    job_id: job.clone(),
+                    idempotency_key: idempotency_key.clone(),
+                    no_inherit_env: *no_inherit_env,
+                };
+
+                match from_file {
+                    Some(file) => {
+                        let content = read_to_string(file)
+                            .with_context(|| format!("Failed to read commands from {file:?}"))?;
+                        let messages: Vec<AddMessage> = content
+                            .lines()
+                            .map(str::trim)
+                            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                            .map(|line| {
+                                let mut message = message.clone();
+                                message.command = line.to_string();
+                                if *auto_label && message.label.is_none() {
+                                    message.label =
+                                        line.split_whitespace().next().map(ToString::to_string);
+                                }
+                                message
+                            })
+                            .collect();
+                        if messages.is_empty() {
+                            bail!("The file {file:?} doesn't contain any commands.");
+                        }
+                        Message::AddBatch(messages)
+                    }
+                    None => message.into(),
                 }
-                .into()
             }
-            SubCommand::Remove { task_ids } => {
+            SubCommand::Remove {
+                task_ids,
+                keep_logs,
+                ..
+            } => {
                 if self.settings.client.show_confirmation_questions {
                     self.handle_user_confirmation("remove", task_ids)?;
                 }
-                Message::Remove(task_ids.clone())
+                if *keep_logs {
+                    Message::RemoveKeepLogs(task_ids.clone())
+                } else {
+                    Message::Remove(task_ids.clone())
+                }
             }
-            SubCommand::Stash { task_ids } => Message::Stash(task_ids.clone()),
+            SubCommand::Stash {
+                task_ids,
+                until: None,
+            } => Message::Stash(task_ids.clone()),
+            SubCommand::Hold { task_ids } => Message::Hold(task_ids.clone()),
+            SubCommand::Release { task_ids } => Message::Release(task_ids.clone()),
+            SubCommand::Note { task_id, note } => NoteMessage {
+                task_id: *task_id,
+                note: note.clone(),
+            }
+            .into(),
             SubCommand::Switch {
                 task_id_1,
                 task_id_2,
@@ -431,12 +946,28 @@ impl Client {
                 task_id_2: *task_id_2,
             }
             .into(),
+            SubCommand::Move { task_id, before } => ReorderMessage {
+                task_id: *task_id,
+                before: *before,
+            }
+            .into(),
             SubCommand::Enqueue {
                 task_ids,
                 delay_until,
             } => EnqueueMessage {
                 task_ids: task_ids.clone(),
                 enqueue_at: *delay_until,
+                group: None,
+            }
+            .into(),
+            SubCommand::Start {
+                group,
+                stashed: true,
+                ..
+            } => EnqueueMessage {
+                task_ids: Vec::new(),
+                enqueue_at: None,
+                group: Some(group_or_default(group)),
             }
             .into(),
             SubCommand::Start {
@@ -464,6 +995,8 @@ impl Client {
                 group,
                 all,
                 signal,
+                retry,
+                with_dependents,
                 ..
             } => {
                 if self.settings.client.show_confirmation_questions {
@@ -472,11 +1005,17 @@ impl Client {
                 KillMessage {
                     tasks: selection_from_params(*all, group, task_ids),
                     signal: signal.clone(),
+                    retry: *retry,
+                    skip_unstarted: *with_dependents,
                 }
                 .into()
             }
-            SubCommand::Send { task_id, input } => SendMessage {
-                task_id: *task_id,
+            SubCommand::Send {
+                task_ids,
+                input: Some(input),
+                ..
+            } => SendMessage {
+                task_ids: task_ids.clone(),
                 input: input.clone(),
             }
             .into(),
@@ -486,46 +1025,97 @@ impl Client {
                     parallel_tasks: parallel.to_owned(),
                 },
                 Some(GroupCommand::Remove { name }) => GroupMessage::Remove(name.to_owned()),
+                Some(GroupCommand::Rename { name, new_name }) => GroupMessage::Rename {
+                    name: name.to_owned(),
+                    new_name: new_name.to_owned(),
+                },
                 None => GroupMessage::List,
             }
             .into(),
+            SubCommand::Status {
+                query,
+                single: true,
+                ..
+            } => {
+                let id = match query.as_slice() {
+                    [id] => id
+                        .parse()
+                        .with_context(|| format!("\"{id}\" isn't a valid task id"))?,
+                    _ => bail!("`--single` expects exactly one task id."),
+                };
+                TaskRequestMessage { id }.into()
+            }
             SubCommand::Status { .. } => Message::Status,
             SubCommand::Log {
                 task_ids,
                 lines,
                 full,
+                offset,
+                stderr,
+                archived,
                 ..
             } => {
-                let lines = determine_log_line_amount(*full, lines);
+                let lines = determine_log_line_amount(*full, lines, offset);
 
                 let message = LogRequestMessage {
                     task_ids: task_ids.clone(),
-                    send_logs: !self.settings.client.read_local_logs,
+                    // Archived logs can't be read locally.
+                    send_logs: *archived || !self.settings.client.read_local_logs,
                     lines,
+                    offset: *offset,
+                    stderr: *stderr,
+                    archived: *archived,
                 };
                 Message::Log(message)
             }
-            SubCommand::Follow { task_id, lines } => StreamRequestMessage {
+            SubCommand::Follow { task_id, lines, .. } => StreamRequestMessage {
                 task_id: *task_id,
                 lines: *lines,
+                all: false,
+                offset: None,
             }
             .into(),
             SubCommand::Clean {
                 successful_only,
                 group,
+                ..
             } => CleanMessage {
                 successful_only: *successful_only,
                 group: group.clone(),
             }
             .into(),
-            SubCommand::Reset { force, .. } => {
+            SubCommand::History {
+                since,
+                group,
+                status,
+                ..
+            } => HistoryMessage {
+                since: *since,
+                group: group.clone(),
+                status: status.clone(),
+            }
+            .into(),
+            SubCommand::Reset { force, group, .. } => {
                 if self.settings.client.show_confirmation_questions && !force {
-                    self.handle_user_confirmation("reset", &Vec::new())?;
+                    let action = match group {
+                        Some(group) => format!("reset group \"{group}\""),
+                        None => "reset".to_string(),
+                    };
+                    self.handle_user_confirmation(&action, &Vec::new())?;
                 }
 
-                ResetMessage {}.into()
+                ResetMessage {
+                    group: group.clone(),
+                }
+                .into()
+            }
+            SubCommand::Shutdown { graceful, timeout } => {
+                if *graceful {
+                    Shutdown::Drain { timeout: *timeout }.into()
+                } else {
+                    Shutdown::Graceful.into()
+                }
             }
-            SubCommand::Shutdown => Shutdown::Graceful.into(),
             SubCommand::Parallel {
                 parallel_tasks,
                 group,
@@ -542,9 +1132,28 @@ impl Client {
             },
             SubCommand::FormatStatus { .. } => bail!("FormatStatus has to be handled earlier"),
             SubCommand::Completions { .. } => bail!("Completions have to be handled earlier"),
+            SubCommand::Doctor => bail!("Doctor has to be handled earlier"),
+            SubCommand::GenerateConfigSchema => {
+                bail!("Config schema generation has to be handled earlier")
+            }
             SubCommand::Restart { .. } => bail!("Restarts have to be handled earlier"),
             SubCommand::Edit { .. } => bail!("Edits have to be handled earlier"),
             SubCommand::Wait { .. } => bail!("Wait has to be handled earlier"),
+            SubCommand::Info { .. } => Message::Info,
+            SubCommand::Export => Message::Export,
+            SubCommand::Import { file } => {
+                let content = read_to_string(file)
+                    .with_context(|| format!("Failed to read export file {file:?}"))?;
+                let state: State = serde_json::from_str(&content)
+                    .with_context(|| format!("Failed to deserialize export file {file:?}"))?;
+                Message::Import(Box::new(state))
+            }
+            SubCommand::Top => bail!("Top has to be handled earlier"),
+            SubCommand::Metrics => bail!("Metrics have to be handled earlier"),
+            SubCommand::Complete { .. } => bail!("Complete has to be handled earlier"),
+            SubCommand::Send { .. } => bail!("Sending files has to be handled earlier"),
+            SubCommand::Stash { .. } => bail!("Delayed stashes have to be handled earlier"),
+            SubCommand::Job { .. } => bail!("Jobs have to be handled earlier"),
         })
     }
 }
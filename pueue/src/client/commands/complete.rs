@@ -0,0 +1,40 @@
+use anyhow::Result;
+
+use pueue_lib::network::protocol::*;
+
+use super::get_state;
+use crate::client::cli::Shell;
+
+/// Print all task ids and group names that start with the given word, one per line.
+///
+/// This is called by the generated shell completion files, which allows them to complete
+/// the ids and groups that currently exist on the daemon.
+/// Fish additionally receives a short description for each candidate.
+pub async fn complete(stream: &mut GenericStream, shell: &Shell, current_word: &str) -> Result<()> {
+    let state = get_state(stream).await?;
+
+    for (id, task) in state.tasks.iter() {
+        let id = id.to_string();
+        if !id.starts_with(current_word) {
+            continue;
+        }
+
+        match shell {
+            Shell::Fish => println!("{id}\t{}", task.command),
+            _ => println!("{id}"),
+        }
+    }
+
+    for name in state.groups.keys() {
+        if !name.starts_with(current_word) {
+            continue;
+        }
+
+        match shell {
+            Shell::Fish => println!("{name}\tGroup"),
+            _ => println!("{name}"),
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+use std::fs::{remove_file, write};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+
+use pueue_lib::network::protocol::*;
+use pueue_lib::network::secret::read_shared_secret;
+use pueue_lib::settings::{Settings, Shared};
+
+use crate::client::cli::CliArguments;
+
+/// The outcome of a single check of `pueue doctor`.
+enum CheckResult {
+    Pass(String),
+    /// Something looks wrong, but pueue still works.
+    Warn(String),
+    Fail(String),
+}
+
+/// Run some diagnostic checks and print a report.
+///
+/// This doesn't use the normal client, as it needs to work even if the configuration is broken
+/// or the daemon cannot be reached.
+/// Returns whether all critical checks passed.
+pub async fn doctor(opt: &CliArguments) -> bool {
+    let mut healthy = true;
+    let mut report = |name: &str, result: CheckResult| match result {
+        CheckResult::Pass(details) => println!("[pass] {name}: {details}"),
+        CheckResult::Warn(details) => println!("[warn] {name}: {details}"),
+        CheckResult::Fail(details) => {
+            healthy = false;
+            println!("[fail] {name}: {details}");
+        }
+    };
+
+    let settings = match load_settings(opt) {
+        Ok((settings, result)) => {
+            report("Configuration", result);
+            settings
+        }
+        Err(err) => {
+            report("Configuration", CheckResult::Fail(format!("{err:#}")));
+            // All other checks depend on a valid configuration.
+            return false;
+        }
+    };
+
+    report(
+        "Pueue directory",
+        check_directory(&settings.shared.pueue_directory()),
+    );
+    report("Secret file", check_secret(&settings.shared));
+    report("Connection setup", check_connection_setup(&settings.shared));
+    report("Daemon", check_daemon(&settings.shared).await);
+
+    healthy
+}
+
+/// Read the configuration file and apply the requested profile and socket, just like the client.
+fn load_settings(opt: &CliArguments) -> Result<(Settings, CheckResult)> {
+    let (mut settings, config_found) = Settings::read(&opt.config)?;
+    settings.load_requested_profile(&opt.profile)?;
+
+    #[cfg(not(target_os = "windows"))]
+    if let Some(socket) = &opt.socket {
+        settings.shared.use_unix_socket = true;
+        settings.shared.unix_socket_path = Some(socket.clone());
+        return Ok((settings, CheckResult::Pass("Using the given socket".into())));
+    }
+
+    let result = if config_found {
+        CheckResult::Pass("Configuration file is valid".into())
+    } else {
+        CheckResult::Fail(
+            "Couldn't find a configuration file. Did you start the daemon yet?".into(),
+        )
+    };
+
+    Ok((settings, result))
+}
+
+/// Ensure that the daemon can write to the given directory.
+fn check_directory(directory: &Path) -> CheckResult {
+    let test_file = directory.join(".pueue_doctor");
+    let result = write(&test_file, b"").and_then(|_| remove_file(&test_file));
+    match result {
+        Ok(_) => CheckResult::Pass(format!("{directory:?} is writable")),
+        Err(err) => CheckResult::Fail(format!("{directory:?} isn't writable: {err}")),
+    }
+}
+
+/// Ensure that the secret can be read and that it isn't accessible by other users.
+fn check_secret(settings: &Shared) -> CheckResult {
+    let path = settings.shared_secret_path();
+    if let Err(err) = read_shared_secret(&path) {
+        return CheckResult::Fail(err.to_string());
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        // The secret might be passed via environment variable, in which case there's no file.
+        if let Ok(metadata) = path.metadata() {
+            let mode = metadata.permissions().mode();
+            if mode & 0o077 != 0 {
+                return CheckResult::Warn(format!(
+                    "{path:?} is accessible by other users (mode {:o}). Consider `chmod 600`.",
+                    mode & 0o777
+                ));
+            }
+        }
+    }
+
+    CheckResult::Pass(format!("{path:?} is readable"))
+}
+
+/// Ensure that all files that are needed to establish a connection exist.
+fn check_connection_setup(settings: &Shared) -> CheckResult {
+    if settings.use_unix_socket {
+        let path = settings.unix_socket_path();
+        if !path.exists() {
+            return CheckResult::Fail(format!("Unix socket {path:?} doesn't exist"));
+        }
+        return CheckResult::Pass(format!("Using unix socket {path:?}"));
+    }
+
+    let mut files = vec![settings.daemon_cert()];
+    if settings.require_client_cert {
+        files.extend(settings.client_cert());
+        files.extend(settings.client_key());
+    }
+    if let Some(missing) = files.iter().find(|path| !path.exists()) {
+        return CheckResult::Fail(format!("TLS file {missing:?} doesn't exist"));
+    }
+
+    CheckResult::Pass(format!("Using TLS on {}:{}", settings.host, settings.port))
+}
+
+/// Connect to the daemon and do the usual handshake.
+async fn check_daemon(settings: &Shared) -> CheckResult {
+    match handshake(settings).await {
+        Ok(version) => CheckResult::Pass(format!("Daemon is reachable (version {version})")),
+        Err(err) => CheckResult::Fail(format!("{err:#}")),
+    }
+}
+
+/// Authenticate with the daemon and return its version.
+async fn handshake(settings: &Shared) -> Result<String> {
+    let mut stream = get_client_stream(settings)
+        .await
+        .context("Failed to connect to daemon")?;
+
+    let secret = read_shared_secret(&settings.shared_secret_path())?;
+    send_bytes(&secret, &mut stream)
+        .await
+        .context("Failed to send secret")?;
+
+    let version_bytes = receive_bytes(&mut stream)
+        .await
+        .context("Failed to receive version during handshake with daemon")?;
+    if version_bytes.is_empty() {
+        bail!("Daemon went away after sending secret. Did you use the correct secret?")
+    }
+
+    String::from_utf8(version_bytes).context("Daemon sent invalid UTF-8")
+}
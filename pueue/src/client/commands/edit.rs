@@ -16,12 +16,16 @@ use pueue_lib::process_helper::compile_shell_command;
 ///
 /// After receiving the task information, the user can then edit it in their editor.
 /// Upon exiting the text editor, the line will then be read and sent to the server
+///
+/// `editor` overrides the editor from the `$EDITOR` environment variable.
 pub async fn edit(
     stream: &mut GenericStream,
     task_id: usize,
     edit_command: bool,
     edit_path: bool,
     edit_label: bool,
+    edit_dependencies: bool,
+    editor: Option<&str>,
 ) -> Result<Message> {
     // Request the data to edit from the server and issue a task-lock while doing so.
     let init_message = Message::EditRequest(task_id);
@@ -32,12 +36,12 @@ pub async fn edit(
     // In case we don't receive an EditResponse, something went wrong
     // Return the response to the parent function and let the client handle it
     // by the generic message handler.
-    let Message::EditResponse(init_response ) = init_response else {
+    let Message::EditResponse(init_response) = init_response else {
         return Ok(init_response);
     };
 
     // Edit the command if explicitly specified or if no flags are provided (the default)
-    let edit_command = edit_command || !edit_path && !edit_label;
+    let edit_command = edit_command || !edit_path && !edit_label && !edit_dependencies;
 
     // Edit all requested properties.
     let edit_result = edit_task_properties(
@@ -47,7 +51,14 @@ pub async fn edit(
         edit_command,
         edit_path,
         edit_label,
-    );
+        editor,
+    )
+    .and_then(|mut props| {
+        if edit_dependencies {
+            props.dependencies = Some(edit_dependency_list(&init_response.dependencies, editor)?);
+        }
+        Ok(props)
+    });
 
     // Any error while editing will result in the client aborting the editing process.
     // However, as the daemon moves tasks that're edited into the `Locked` state, we cannot simply
@@ -79,6 +90,7 @@ pub async fn edit(
         path: edited_props.path,
         label: edited_props.label,
         delete_label: edited_props.delete_label,
+        dependencies: edited_props.dependencies,
     };
     send_message(edit_message, stream).await?;
 
@@ -91,12 +103,14 @@ pub struct EditedProperties {
     pub path: Option<PathBuf>,
     pub label: Option<String>,
     pub delete_label: bool,
+    pub dependencies: Option<Vec<usize>>,
 }
 
 /// Takes several task properties and edit them if requested.
 /// The `edit_*` booleans are used to determine which fields should be edited.
 ///
 /// Fields that have been edited will be returned as their `Some(T)` equivalent.
+/// If no `editor` is given, the editor from the `$EDITOR` environment variable is used.
 ///
 /// The returned values are: `(command, path, label)`
 pub fn edit_task_properties(
@@ -106,12 +120,13 @@ pub fn edit_task_properties(
     edit_command: bool,
     edit_path: bool,
     edit_label: bool,
+    editor: Option<&str>,
 ) -> Result<EditedProperties> {
     let mut props = EditedProperties::default();
 
     // Update the command if requested.
     if edit_command {
-        props.command = Some(edit_line(original_command)?);
+        props.command = Some(edit_line(original_command, editor)?);
     };
 
     // Update the path if requested.
@@ -119,13 +134,13 @@ pub fn edit_task_properties(
         let str_path = original_path
             .to_str()
             .context("Failed to convert task path to string")?;
-        let changed_path = edit_line(str_path)?;
+        let changed_path = edit_line(str_path, editor)?;
         props.path = Some(PathBuf::from(changed_path));
     }
 
     // Update the label if requested.
     if edit_label {
-        let edited_label = edit_line(&original_label.clone().unwrap_or_default())?;
+        let edited_label = edit_line(&original_label.clone().unwrap_or_default(), editor)?;
 
         // If the user deletes the label in their editor, an empty string will be returned.
         // This is an indicator that the task should no longer have a label, in which case we
@@ -140,21 +155,53 @@ pub fn edit_task_properties(
     Ok(props)
 }
 
+/// Edit a task's dependencies as a space separated list of task ids.
+/// Removing all ids removes all dependencies of the task.
+fn edit_dependency_list(
+    original_dependencies: &[usize],
+    editor: Option<&str>,
+) -> Result<Vec<usize>> {
+    let line = original_dependencies
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(" ");
+    let edited_line = edit_line(&line, editor)?;
+
+    edited_line
+        .split(|character: char| character.is_whitespace() || character == ',')
+        .filter(|id| !id.is_empty())
+        .map(|id| {
+            id.parse::<usize>()
+                .with_context(|| format!("Invalid task id '{id}' in dependencies. Aborting."))
+        })
+        .collect()
+}
+
 /// This function enables the user to edit a task's details.
-/// Save any string to a temporary file, which is opened in the specified `$EDITOR`.
+/// Save any string to a temporary file, which is opened in the given editor or the `$EDITOR`.
 /// As soon as the editor is closed, read the file content and return the line.
-fn edit_line(line: &str) -> Result<String> {
+fn edit_line(line: &str, editor: Option<&str>) -> Result<String> {
     // Create a temporary file with the command so we can edit it with the editor.
     let mut file = NamedTempFile::new().expect("Failed to create a temporary file");
     writeln!(file, "{line}").context("Failed to write to temporary file.")?;
 
-    // Get the editor that should be used from the environment.
-    let editor = match env::var("EDITOR") {
-        Err(_) => bail!("The '$EDITOR' environment variable couldn't be read. Aborting."),
-        Ok(editor) => editor,
+    // Get the editor that should be used.
+    // An explicitly specified editor takes precedence over the environment.
+    let editor = match editor {
+        Some(editor) => editor.to_string(),
+        None => match env::var("EDITOR") {
+            Err(_) => bail!("The '$EDITOR' environment variable couldn't be read. Aborting."),
+            Ok(editor) => editor,
+        },
     };
+    if editor.trim().is_empty() {
+        bail!("The editor command is empty. Aborting.");
+    }
 
     // Compile the command to start the editor on the temporary file.
+    // The editor is run in a shell, which splits it into the program and its arguments.
+    // That way, editors with arguments such as `code --wait` work as expected.
     // We escape the file path for good measure, but it shouldn't be necessary.
     let path = shell_escape::escape(file.path().to_string_lossy());
     let editor_command = format!("{editor} {path}");
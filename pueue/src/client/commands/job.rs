@@ -0,0 +1,70 @@
+use anyhow::{bail, Result};
+
+use pueue_lib::network::message::*;
+use pueue_lib::network::protocol::*;
+use pueue_lib::settings::Settings;
+use pueue_lib::task::Task;
+
+use super::get_state;
+use crate::client::cli::{JobCommand, SubCommand};
+use crate::client::display::{print_state, OutputStyle};
+
+/// Handle the `pueue job` subcommands.
+///
+/// Jobs only exist as a tag on tasks, which is why the job's name is resolved to the ids of its
+/// tasks on the client-side. Returns the response of the daemon, if a request has been sent.
+pub async fn job(
+    stream: &mut GenericStream,
+    cmd: &JobCommand,
+    style: &OutputStyle,
+    settings: &Settings,
+) -> Result<Option<Message>> {
+    let name = match cmd {
+        JobCommand::Status { name } | JobCommand::Kill { name } => name,
+    };
+
+    let state = get_state(stream).await?;
+    let tasks: Vec<Task> = state
+        .tasks
+        .values()
+        .filter(|task| task.job_id.as_ref() == Some(name))
+        .cloned()
+        .collect();
+
+    if tasks.is_empty() {
+        bail!("There are no tasks for job \"{name}\"");
+    }
+
+    match cmd {
+        JobCommand::Status { .. } => {
+            let status_command = SubCommand::Status {
+                json: false,
+                group: None,
+                query: Vec::new(),
+                preset: None,
+                group_summary: false,
+                group_by_label: false,
+                format: None,
+                watch: None,
+                single: false,
+                since: None,
+                until: None,
+            };
+            let output = print_state(state, tasks, &status_command, style, settings)?;
+            println!("{output}");
+
+            Ok(None)
+        }
+        JobCommand::Kill { .. } => {
+            let message = KillMessage {
+                tasks: TaskSelection::TaskIds(tasks.iter().map(|task| task.id).collect()),
+                signal: None,
+                retry: false,
+                skip_unstarted: true,
+            };
+            send_message(message, stream).await?;
+
+            Ok(Some(receive_message(stream).await?))
+        }
+    }
+}
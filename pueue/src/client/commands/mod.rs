@@ -4,29 +4,46 @@
 //! "non-trivial" vaguely means that we, for instance, have to do additional requests to the
 //! daemon, open some files on the filesystem, edit files and so on.
 //! All commands that cannot be simply handled by handling requests or using `pueue_lib`.
-use anyhow::Result;
+use anyhow::{bail, Result};
 
 use pueue_lib::network::message::Message;
 use pueue_lib::network::protocol::*;
 use pueue_lib::state::State;
 
+mod complete;
+mod doctor;
 mod edit;
 mod format_state;
+mod job;
 mod local_follow;
+mod remote_follow;
 mod restart;
+mod send;
+mod stash;
+mod top;
 mod wait;
+mod watch_status;
 
+pub use complete::complete;
+pub use doctor::doctor;
 pub use edit::edit;
 pub use format_state::format_state;
+pub use job::job;
 pub use local_follow::local_follow;
+pub use remote_follow::{remote_follow_all, remote_follow_from_start, remote_follow_json};
 pub use restart::restart;
+pub use send::send_input;
+pub use stash::stash_until;
+pub use top::top;
 pub use wait::{wait, WaitTargetStatus};
+pub use watch_status::watch_status;
 
 // This is a helper function for easy retrieval of the current daemon state.
 // The current daemon state is often needed in more complex commands.
 pub async fn get_state(stream: &mut GenericStream) -> Result<State> {
     // Create the message payload and send it to the daemon.
-    send_message(Message::Status, stream).await?;
+    // The daemon's status cache is bypassed, as the caller might act on the state.
+    send_message(Message::FreshStatus, stream).await?;
 
     // Check if we can receive the response from the daemon
     let message = receive_message(stream).await?;
@@ -36,3 +53,39 @@ pub async fn get_state(stream: &mut GenericStream) -> Result<State> {
         _ => unreachable!(),
     }
 }
+
+/// Determine the task that should be followed.
+/// If no `task_id` is given, we check whether there's a single running task and default to it.
+/// If there are multiple running tasks, the user has to specify which task they want to follow.
+pub async fn get_task_id_to_follow(
+    stream: &mut GenericStream,
+    task_id: &Option<usize>,
+) -> Result<usize> {
+    if let Some(task_id) = task_id {
+        return Ok(*task_id);
+    }
+
+    // The user didn't provide a task id.
+    // Check whether we can find a single running task to follow.
+    let state = get_state(stream).await?;
+    let running_ids: Vec<_> = state
+        .tasks
+        .iter()
+        .filter_map(|(&id, t)| if t.is_running() { Some(id) } else { None })
+        .collect();
+
+    match running_ids.len() {
+        0 => {
+            bail!("There are no running tasks.");
+        }
+        1 => Ok(running_ids[0]),
+        _ => {
+            let running_ids = running_ids
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            bail!("Multiple tasks are running, please select one of the following: {running_ids}",);
+        }
+    }
+}
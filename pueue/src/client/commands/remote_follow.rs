@@ -0,0 +1,148 @@
+use std::io::{self, Read, Write};
+
+use anyhow::{bail, Result};
+use snap::read::FrameDecoder;
+
+use pueue_lib::network::message::*;
+use pueue_lib::network::protocol::*;
+
+use crate::client::commands::get_task_id_to_follow;
+use crate::client::display::{JsonFollowPrinter, PrefixedFollowPrinter};
+
+/// Follow the output of a task via the daemon and print it as JSON Lines.
+///
+/// The plain-text remote `follow` is handled like any other simple command, since the client
+/// can just print each chunk it receives. For JSON output, we need to know the followed task
+/// and keep track of the printed bytes, which is why this is handled separately.
+pub async fn remote_follow_json(
+    stream: &mut GenericStream,
+    task_id: &Option<usize>,
+    lines: Option<usize>,
+) -> Result<()> {
+    let task_id = get_task_id_to_follow(stream, task_id).await?;
+
+    let message = StreamRequestMessage {
+        task_id: Some(task_id),
+        lines,
+        all: false,
+        offset: None,
+    };
+    send_message(message, stream).await?;
+
+    let mut printer = JsonFollowPrinter::new(task_id);
+    loop {
+        match receive_message(stream).await? {
+            Message::Stream(text) => printer.print(text.as_bytes()),
+            Message::Close => break,
+            // The daemon stopped streaming for some reason, e.g. the task has been removed.
+            // Don't pollute stdout with non-JSON output.
+            Message::Success(text) => {
+                eprintln!("{text}");
+                break;
+            }
+            Message::Failure(text) => {
+                printer.finish();
+                bail!(text);
+            }
+            _ => bail!("Received unexpected message while following task output"),
+        }
+    }
+    printer.finish();
+
+    Ok(())
+}
+
+/// Follow the output of all running tasks via the daemon.
+/// Each line is prefixed with the id of the task it belongs to.
+///
+/// The daemon closes the stream of each task once it finishes,
+/// the whole stream is closed once no task is running anymore.
+pub async fn remote_follow_all(stream: &mut GenericStream, lines: Option<usize>) -> Result<()> {
+    let message = StreamRequestMessage {
+        task_id: None,
+        lines,
+        all: true,
+        offset: None,
+    };
+    send_message(message, stream).await?;
+
+    let mut printer = PrefixedFollowPrinter::default();
+    loop {
+        match receive_message(stream).await? {
+            Message::TaskStream(message) => {
+                printer.print(message.task_id, message.text.as_bytes());
+                if message.finished {
+                    printer.finish(message.task_id);
+                }
+            }
+            Message::Close => break,
+            Message::Success(text) => {
+                println!("{text}");
+                break;
+            }
+            Message::Failure(text) => bail!(text),
+            _ => bail!("Received unexpected message while following task output"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Print the whole output of a task via the daemon and continue to follow it.
+///
+/// The output that already exists is requested as a regular log first.
+/// The stream then starts at the byte offset right behind that output, so no output is
+/// duplicated or lost in between.
+pub async fn remote_follow_from_start(stream: &mut GenericStream, task_id: usize) -> Result<()> {
+    let message = LogRequestMessage {
+        task_ids: vec![task_id],
+        send_logs: true,
+        lines: None,
+        offset: None,
+        stderr: false,
+        archived: false,
+    };
+    send_message(message, stream).await?;
+
+    let mut task_logs = match receive_message(stream).await? {
+        Message::LogResponse(task_logs) => task_logs,
+        _ => bail!("Received unexpected message while reading task output"),
+    };
+    let Some(task_log) = task_logs.remove(&task_id) else {
+        bail!("There's no task with id {task_id}");
+    };
+
+    let mut output = Vec::new();
+    if let Some(bytes) = task_log.output {
+        FrameDecoder::new(bytes.as_slice()).read_to_end(&mut output)?;
+    }
+    let mut stdout = io::stdout();
+    stdout.write_all(&output)?;
+    stdout.flush()?;
+
+    let message = StreamRequestMessage {
+        task_id: Some(task_id),
+        lines: None,
+        all: false,
+        offset: Some(output.len() as u64),
+    };
+    send_message(message, stream).await?;
+
+    loop {
+        match receive_message(stream).await? {
+            Message::Stream(text) => {
+                print!("{text}");
+                stdout.flush()?;
+            }
+            Message::Close => break,
+            Message::Success(text) => {
+                println!("{text}");
+                break;
+            }
+            Message::Failure(text) => bail!(text),
+            _ => bail!("Received unexpected message while following task output"),
+        }
+    }
+
+    Ok(())
+}
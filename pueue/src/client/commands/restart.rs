@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use anyhow::{bail, Result};
 
 use pueue_lib::network::message::*;
@@ -24,6 +26,7 @@ pub async fn restart(
     edit_command: bool,
     edit_path: bool,
     edit_label: bool,
+    working_directory: Option<PathBuf>,
 ) -> Result<()> {
     let new_status = if stashed {
         TaskStatus::Stashed { enqueue_at: None }
@@ -31,6 +34,14 @@ pub async fn restart(
         TaskStatus::Queued
     };
 
+    // Copies of tasks are added like new tasks, which don't check their working directory.
+    // Don't restart anything, if the tasks would fail to spawn anyway.
+    if let Some(path) = &working_directory {
+        if !in_place && !path.is_dir() {
+            bail!("The working directory {path:?} doesn't exist.");
+        }
+    }
+
     let state = get_state(stream).await?;
 
     // Filter to get done tasks
@@ -70,6 +81,7 @@ pub async fn restart(
         tasks: Vec::new(),
         stashed,
         start_immediately,
+        working_directory: working_directory.clone(),
     };
 
     // Go through all Done commands we found and restart them
@@ -86,6 +98,7 @@ pub async fn restart(
             edit_command,
             edit_path,
             edit_label,
+            None,
         )?;
 
         // Add the tasks to the singular message, if we want to restart the tasks in-place.
@@ -106,7 +119,10 @@ pub async fn restart(
         // Create a AddMessage to send the task to the daemon from the updated info and the old task.
         let add_task_message = AddMessage {
             command: edited_props.command.unwrap_or_else(|| task.command.clone()),
-            path: edited_props.path.unwrap_or_else(|| task.path.clone()),
+            path: working_directory
+                .clone()
+                .or(edited_props.path)
+                .unwrap_or_else(|| task.path.clone()),
             envs: task.envs.clone(),
             start_immediately,
             stashed,
@@ -115,6 +131,24 @@ pub async fn restart(
             dependencies: Vec::new(),
             label: edited_props.label.or_else(|| task.label.clone()),
             print_task_id: false,
+            timeout: task.timeout,
+            retries: task.retries_remaining,
+            retry_delay: task.retry_delay,
+            user: task.user.clone(),
+            path_is_default: false,
+            priority: task.priority,
+            cpu_affinity: task.cpu_affinity.clone(),
+            nice: task.nice,
+            when_output_contains: task.when_output_contains.clone(),
+            shell: task.shell.clone(),
+            manual_start: false,
+            only_if_success: task.only_if_success,
+            label_color: task.label_color.clone(),
+            touch_on_done: task.touch_on_done.clone(),
+            weight: task.weight,
+            job_id: task.job_id.clone(),
+            idempotency_key: None,
+            no_inherit_env: task.no_inherit_env,
         };
 
         // Send the cloned task to the daemon and abort on any failure messages.
@@ -0,0 +1,64 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+use pueue_lib::log::complete_utf8_len;
+use pueue_lib::network::message::*;
+use pueue_lib::network::protocol::*;
+
+/// The maximum amount of bytes that's sent to a task in a single message.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// Send the content of a file or, if no file is given, the client's stdin to the given tasks.
+///
+/// The input is read and sent in chunks, so large inputs don't have to be kept in memory.
+/// Returns the response of the daemon to the last chunk or the first failure.
+pub async fn send_input(
+    stream: &mut GenericStream,
+    task_ids: Vec<usize>,
+    stdin_file: Option<&Path>,
+) -> Result<Message> {
+    let mut reader: Box<dyn Read> = match stdin_file {
+        Some(path) => {
+            Box::new(File::open(path).with_context(|| format!("Failed to open file {path:?}"))?)
+        }
+        None => Box::new(io::stdin()),
+    };
+
+    let mut response = create_success_message("There's no input to send.");
+    let mut buffer = vec![0; CHUNK_SIZE];
+    // Bytes of an incomplete UTF-8 character at the end of the last chunk.
+    let mut pending = Vec::new();
+    loop {
+        let read = reader.read(&mut buffer).context("Failed to read input")?;
+        pending.extend_from_slice(&buffer[..read]);
+
+        // Keep incomplete characters for the next chunk, unless we reached the end of the input.
+        let complete = if read == 0 {
+            pending.len()
+        } else {
+            complete_utf8_len(&pending)
+        };
+        let rest = pending.split_off(complete);
+        let chunk = std::mem::replace(&mut pending, rest);
+
+        if !chunk.is_empty() {
+            let message = SendMessage {
+                task_ids: task_ids.clone(),
+                input: String::from_utf8_lossy(&chunk).to_string(),
+            };
+            send_message(message, stream).await?;
+            response = receive_message(stream).await?;
+
+            if matches!(response, Message::Failure(_)) {
+                return Ok(response);
+            }
+        }
+
+        if read == 0 {
+            return Ok(response);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use chrono::{DateTime, Local};
+
+use pueue_lib::network::message::*;
+use pueue_lib::network::protocol::*;
+
+/// Stash the given tasks and schedule them to be enqueued again at the given point in time.
+///
+/// This is done by stashing the tasks first and then enqueueing them with a delay.
+/// Returns the response of the daemon to the first failure or the enqueue request.
+pub async fn stash_until(
+    stream: &mut GenericStream,
+    task_ids: Vec<usize>,
+    until: DateTime<Local>,
+) -> Result<Message> {
+    send_message(Message::Stash(task_ids.clone()), stream).await?;
+    let response = receive_message(stream).await?;
+    if let Message::Failure(_) = response {
+        return Ok(response);
+    }
+
+    let message = EnqueueMessage {
+        task_ids,
+        enqueue_at: Some(until),
+        group: None,
+    };
+    send_message(message, stream).await?;
+
+    Ok(receive_message(stream).await?)
+}
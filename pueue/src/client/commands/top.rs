@@ -0,0 +1,364 @@
+use std::io::{stdout, Stdout};
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::{Backend, CrosstermBackend};
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+use ratatui::{Frame, Terminal};
+
+use pueue_lib::network::message::*;
+use pueue_lib::network::protocol::*;
+use pueue_lib::settings::Settings;
+use pueue_lib::task::{Task, TaskResult, TaskStatus};
+
+use crate::client::commands::get_state;
+use crate::client::display::helper::formatted_start_end;
+
+/// How often the task table is refreshed.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long we wait for user input, before checking whether a refresh is due.
+const INPUT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The amount of rows that are skipped when scrolling by page.
+const PAGE_SIZE: usize = 10;
+
+/// All state of the dashboard that survives between two renders.
+struct Dashboard {
+    tasks: Vec<Task>,
+    table_state: TableState,
+    /// The response of the last action, which is shown at the bottom of the screen.
+    message: Option<String>,
+    /// Whether destructive actions have to be confirmed, see `client.show_confirmation_questions`.
+    confirm_actions: bool,
+    /// The task that should be killed, once the user confirmed the kill.
+    pending_kill: Option<usize>,
+}
+
+/// The action that should be performed as a response to a key press.
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Nothing,
+    Quit,
+    Kill(usize),
+}
+
+impl Dashboard {
+    /// Replace the displayed tasks and make sure that the selection still points to a task.
+    fn update_tasks(&mut self, tasks: Vec<Task>) {
+        self.tasks = tasks;
+        let selected = match self.table_state.selected() {
+            _ if self.tasks.is_empty() => None,
+            Some(selected) => Some(selected.min(self.tasks.len() - 1)),
+            None => Some(0),
+        };
+        self.table_state.select(selected);
+    }
+
+    /// Move the selection by the given amount of rows.
+    fn scroll(&mut self, offset: isize) {
+        if self.tasks.is_empty() {
+            return;
+        }
+        let selected = self.table_state.selected().unwrap_or(0) as isize;
+        let last = self.tasks.len() as isize - 1;
+        self.table_state
+            .select(Some((selected + offset).clamp(0, last) as usize));
+    }
+
+    fn selected_task(&self) -> Option<&Task> {
+        self.table_state
+            .selected()
+            .and_then(|index| self.tasks.get(index))
+    }
+
+    /// Update the dashboard according to a key press and return the action that should follow.
+    fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> Action {
+        // A kill is waiting for confirmation. Any key other than `y` aborts it.
+        if let Some(task_id) = self.pending_kill.take() {
+            if let KeyCode::Char('y') | KeyCode::Char('Y') = code {
+                return Action::Kill(task_id);
+            }
+            self.message = Some(format!("Didn't kill task {task_id}"));
+            return Action::Nothing;
+        }
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return Action::Quit,
+            // The terminal is in raw mode, so we have to handle `Ctrl+c` ourselves.
+            KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => return Action::Quit,
+            KeyCode::Up => self.scroll(-1),
+            KeyCode::Down => self.scroll(1),
+            KeyCode::PageUp => self.scroll(-(PAGE_SIZE as isize)),
+            KeyCode::PageDown => self.scroll(PAGE_SIZE as isize),
+            KeyCode::Home => self.scroll(-(self.tasks.len() as isize)),
+            KeyCode::End => self.scroll(self.tasks.len() as isize),
+            KeyCode::Char('k') => {
+                if let Some(task_id) = self.selected_task().map(|task| task.id) {
+                    if !self.confirm_actions {
+                        return Action::Kill(task_id);
+                    }
+                    self.message = Some(format!("Kill task {task_id}? [y/N]"));
+                    self.pending_kill = Some(task_id);
+                }
+            }
+            _ => {}
+        }
+
+        Action::Nothing
+    }
+}
+
+/// Show a full-screen dashboard of all tasks, which is refreshed every second.
+///
+/// Keybindings:
+/// - `Up`/`Down`, `PageUp`/`PageDown`, `Home`/`End` to scroll.
+/// - `k` to kill the selected task. This has to be confirmed with `y`,
+///   if `client.show_confirmation_questions` is enabled.
+/// - `q`, `Esc` or `Ctrl+c` to quit.
+pub async fn top(stream: &mut GenericStream, settings: &Settings) -> Result<()> {
+    enable_raw_mode().context("Failed to enable raw mode for the terminal")?;
+    let mut terminal = match setup_terminal() {
+        Ok(terminal) => terminal,
+        Err(err) => {
+            disable_raw_mode()?;
+            return Err(err);
+        }
+    };
+
+    let result = run_dashboard(&mut terminal, stream, settings).await;
+
+    // Always restore the terminal, even if the dashboard failed.
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    result
+}
+
+fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
+    let mut stdout = stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    Terminal::new(CrosstermBackend::new(stdout)).context("Failed to initialize the terminal")
+}
+
+/// The render loop of the dashboard.
+/// Fetch the state each `REFRESH_INTERVAL` and handle user input in between.
+async fn run_dashboard<B: Backend>(
+    terminal: &mut Terminal<B>,
+    stream: &mut GenericStream,
+    settings: &Settings,
+) -> Result<()> {
+    let mut dashboard = Dashboard {
+        tasks: Vec::new(),
+        table_state: TableState::default(),
+        message: None,
+        confirm_actions: settings.client.show_confirmation_questions,
+        pending_kill: None,
+    };
+    let mut last_refresh: Option<Instant> = None;
+
+    loop {
+        let refresh_due = match last_refresh {
+            Some(last_refresh) => last_refresh.elapsed() >= REFRESH_INTERVAL,
+            None => true,
+        };
+        if refresh_due {
+            let state = get_state(stream).await?;
+            dashboard.update_tasks(state.tasks.into_values().collect());
+            last_refresh = Some(Instant::now());
+        }
+
+        terminal.draw(|frame| draw(frame, &mut dashboard, settings))?;
+
+        if !event::poll(INPUT_POLL_INTERVAL)? {
+            continue;
+        }
+        let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        else {
+            continue;
+        };
+
+        match dashboard.handle_key(code, modifiers) {
+            Action::Nothing => {}
+            Action::Quit => return Ok(()),
+            Action::Kill(task_id) => {
+                dashboard.message = Some(kill_task(stream, task_id).await?);
+                // Refresh immediately, so the user directly sees the result.
+                last_refresh = None;
+            }
+        }
+    }
+}
+
+/// Send a kill message for a single task and return the daemon's response text.
+async fn kill_task(stream: &mut GenericStream, task_id: usize) -> Result<String> {
+    let message = KillMessage {
+        tasks: TaskSelection::TaskIds(vec![task_id]),
+        signal: None,
+        retry: false,
+        skip_unstarted: false,
+    };
+    send_message(message, stream).await?;
+
+    Ok(match receive_message(stream).await? {
+        Message::Success(text) | Message::Failure(text) => text.trim().to_string(),
+        _ => "Received unexpected response from daemon".to_string(),
+    })
+}
+
+fn draw<B: Backend>(frame: &mut Frame<B>, dashboard: &mut Dashboard, settings: &Settings) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(frame.size());
+
+    let header = Row::new(["Id", "Status", "Group", "Label", "Command", "Start", "End"])
+        .style(Style::default().add_modifier(Modifier::BOLD));
+
+    let rows = dashboard.tasks.iter().map(|task| {
+        let (start, end) = formatted_start_end(task, settings);
+        Row::new(vec![
+            Cell::from(task.id.to_string()),
+            status_cell(task),
+            Cell::from(task.group.clone()),
+            Cell::from(task.label.clone().unwrap_or_default()),
+            Cell::from(task.command.clone()),
+            Cell::from(start),
+            Cell::from(end),
+        ])
+    });
+
+    let table = Table::new(rows)
+        .header(header)
+        .block(Block::default().borders(Borders::ALL).title("Pueue"))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .widths(&[
+            Constraint::Length(5),
+            Constraint::Length(18),
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Min(20),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ]);
+    frame.render_stateful_widget(table, chunks[0], &mut dashboard.table_state);
+
+    let footer = match &dashboard.message {
+        Some(message) => message.clone(),
+        None => "Up/Down: scroll | k: kill selected task | q: quit".to_string(),
+    };
+    frame.render_widget(Paragraph::new(footer), chunks[1]);
+}
+
+/// The human readable status of a task in the respective color.
+fn status_cell(task: &Task) -> Cell<'static> {
+    let (text, color) = match &task.status {
+        TaskStatus::Running => (task.status.to_string(), Color::Green),
+        TaskStatus::Paused | TaskStatus::Locked => (task.status.to_string(), Color::White),
+        TaskStatus::Done(TaskResult::Success) => (TaskResult::Success.to_string(), Color::Green),
+        TaskStatus::Done(TaskResult::Failed(code)) => (format!("Failed ({code})"), Color::Red),
+        TaskStatus::Done(result) => (result.to_string(), Color::Red),
+        _ => (task.status.to_string(), Color::Yellow),
+    };
+
+    Cell::from(text).style(Style::default().fg(color))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn dashboard(confirm_actions: bool) -> Dashboard {
+        let mut dashboard = Dashboard {
+            tasks: Vec::new(),
+            table_state: TableState::default(),
+            message: None,
+            confirm_actions,
+            pending_kill: None,
+        };
+        let tasks = (0..3)
+            .map(|id| {
+                let mut task = Task::new(
+                    "sleep 60".into(),
+                    std::path::PathBuf::from("/tmp"),
+                    std::collections::HashMap::new(),
+                    "default".into(),
+                    TaskStatus::Running,
+                    Vec::new(),
+                    None,
+                );
+                task.id = id;
+                task
+            })
+            .collect();
+        dashboard.update_tasks(tasks);
+
+        dashboard
+    }
+
+    fn press(dashboard: &mut Dashboard, code: KeyCode) -> Action {
+        dashboard.handle_key(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn kill_without_confirmation() {
+        let mut dashboard = dashboard(false);
+        press(&mut dashboard, KeyCode::Down);
+
+        assert_eq!(press(&mut dashboard, KeyCode::Char('k')), Action::Kill(1));
+    }
+
+    #[test]
+    fn kill_with_confirmation() {
+        let mut dashboard = dashboard(true);
+        press(&mut dashboard, KeyCode::Down);
+
+        // The kill only happens once it has been confirmed.
+        assert_eq!(press(&mut dashboard, KeyCode::Char('k')), Action::Nothing);
+        assert_eq!(dashboard.pending_kill, Some(1));
+        assert_eq!(press(&mut dashboard, KeyCode::Char('y')), Action::Kill(1));
+        assert_eq!(dashboard.pending_kill, None);
+    }
+
+    #[test]
+    fn kill_aborted() {
+        let mut dashboard = dashboard(true);
+
+        // Any other key aborts the kill, without performing its usual action.
+        assert_eq!(press(&mut dashboard, KeyCode::Char('k')), Action::Nothing);
+        assert_eq!(press(&mut dashboard, KeyCode::Char('q')), Action::Nothing);
+        assert_eq!(dashboard.pending_kill, None);
+        assert_eq!(press(&mut dashboard, KeyCode::Char('y')), Action::Nothing);
+    }
+
+    #[test]
+    fn quit_and_scroll() {
+        let mut dashboard = dashboard(true);
+
+        press(&mut dashboard, KeyCode::End);
+        assert_eq!(dashboard.table_state.selected(), Some(2));
+        press(&mut dashboard, KeyCode::Up);
+        assert_eq!(dashboard.table_state.selected(), Some(1));
+
+        assert_eq!(press(&mut dashboard, KeyCode::Esc), Action::Quit);
+        assert_eq!(
+            dashboard.handle_key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            Action::Quit
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use std::collections::{HashMap, HashSet};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use chrono::Local;
@@ -10,6 +10,7 @@ use strum_macros::{Display, EnumString};
 use tokio::time::sleep;
 
 use pueue_lib::network::protocol::GenericStream;
+use pueue_lib::process_helper::signal_name;
 use pueue_lib::task::{Task, TaskResult, TaskStatus};
 
 use crate::client::{commands::get_state, display::OutputStyle};
@@ -24,6 +25,13 @@ pub enum WaitTargetStatus {
     Queued,
     #[strum(serialize = "running", serialize = "Running")]
     Running,
+    /// Wait until all tasks finished successfully.
+    /// The client exits with code 1 as soon as a task fails.
+    #[strum(serialize = "success", serialize = "Success")]
+    Success,
+    /// Wait until all tasks finished, but exit with code 1 as soon as a task fails.
+    #[strum(serialize = "failed", serialize = "Failed")]
+    Failed,
 }
 
 /// Wait until tasks are done.
@@ -35,13 +43,18 @@ pub enum WaitTargetStatus {
 ///
 /// By default, this will output status changes of tasks to `stdout`.
 /// Pass `quiet == true` to supress any logging.
+///
+/// If a `timeout` (in seconds) is given and the tasks didn't reach the target status by then,
+/// the client exits with code 2.
 pub async fn wait(
     stream: &mut GenericStream,
     style: &OutputStyle,
     selection: TaskSelection,
     quiet: bool,
     target_status: &Option<WaitTargetStatus>,
+    timeout: Option<u64>,
 ) -> Result<()> {
+    let deadline = timeout.map(|seconds| Instant::now() + Duration::from_secs(seconds));
     let mut first_run = true;
     // Create a list of tracked tasks.
     // This way we can track any status changes and if any new tasks are added.
@@ -68,7 +81,7 @@ pub async fn wait(
             // Add it to the watchlist we we know this task yet.
             let Some(previous_status) = watched_tasks.get(&task.id).cloned() else {
                 if finished_tasks.contains(&task.id) {
-                    continue
+                    continue;
                 }
 
                 // Add new/unknown tasks to our watchlist
@@ -100,9 +113,20 @@ pub async fn wait(
             // Get the correct task. If it no longer exists, remove it from the task list.
             let Some(task) = tasks.iter().find(|task| task.id == task_id) else {
                 watched_tasks.remove(&task_id);
-                    continue;
+                continue;
             };
 
+            // Failed tasks can never finish successfully.
+            // Exit early with a non-zero code, if the user waits for failures or successes.
+            if task.failed()
+                && matches!(
+                    target_status,
+                    WaitTargetStatus::Success | WaitTargetStatus::Failed
+                )
+            {
+                std::process::exit(1);
+            }
+
             // Check if the task hit the target status.
             if reached_target_status(task, &target_status) {
                 watched_tasks.remove(&task_id);
@@ -114,6 +138,11 @@ pub async fn wait(
             break;
         }
 
+        if matches!(deadline, Some(deadline) if Instant::now() >= deadline) {
+            eprintln!("Timed out while waiting for tasks");
+            std::process::exit(2);
+        }
+
         // Sleep for a few seconds. We don't want to hurt the CPU.
         // However, we allow faster polling when in a test environment.
         let mut sleep_time = 2000;
@@ -139,7 +168,9 @@ fn reached_target_status(task: &Task, target_status: &WaitTargetStatus) -> bool
         WaitTargetStatus::Running => {
             task.status == TaskStatus::Running || matches!(task.status, TaskStatus::Done(_))
         }
-        WaitTargetStatus::Done => matches!(task.status, TaskStatus::Done(_)),
+        WaitTargetStatus::Done | WaitTargetStatus::Success | WaitTargetStatus::Failed => {
+            matches!(task.status, TaskStatus::Done(_))
+        }
     }
 }
 
@@ -219,6 +250,21 @@ fn log_status_change(previous_status: TaskStatus, task: &Task, style: &OutputSty
                 let status = style.style_text("killed", Some(Color::Red), None);
                 format!("Task {task_id} has been {status}")
             }
+            TaskResult::KilledBySignal(signal) => {
+                let status = style.style_text("killed", Some(Color::Red), None);
+                format!(
+                    "Task {task_id} has been {status} by {}",
+                    signal_name(*signal)
+                )
+            }
+            TaskResult::TimedOut => {
+                let status = style.style_text("timed out", Some(Color::Red), None);
+                format!("Task {task_id} {status}")
+            }
+            TaskResult::Skipped => {
+                let status = style.style_text("skipped", Some(Color::Yellow), None);
+                format!("Task {task_id} has been {status}")
+            }
         };
         println!("{current_time} - {text}");
 
@@ -0,0 +1,38 @@
+use std::io::stdout;
+use std::time::Duration;
+
+use anyhow::Result;
+use crossterm::cursor::MoveTo;
+use crossterm::execute;
+use crossterm::terminal::{Clear, ClearType};
+use tokio::time::sleep;
+
+use pueue_lib::network::protocol::*;
+use pueue_lib::settings::Settings;
+
+use super::get_state;
+use crate::client::cli::SubCommand;
+use crate::client::display::{print_state, OutputStyle};
+
+/// Print the status table every `interval` seconds, until the user presses `Ctrl+c`.
+///
+/// The screen is cleared before each refresh.
+/// All filters of the `status` subcommand are applied on each refresh.
+pub async fn watch_status(
+    stream: &mut GenericStream,
+    interval: u64,
+    cli_command: &SubCommand,
+    style: &OutputStyle,
+    settings: &Settings,
+) -> Result<()> {
+    loop {
+        let state = get_state(stream).await?;
+        let tasks = state.tasks.values().cloned().collect();
+        let output = print_state(state, tasks, cli_command, style, settings)?;
+
+        execute!(stdout(), Clear(ClearType::All), MoveTo(0, 0))?;
+        println!("{output}");
+
+        sleep(Duration::from_secs(interval)).await;
+    }
+}
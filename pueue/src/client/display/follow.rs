@@ -1,19 +1,135 @@
-use std::io;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, Read, Write};
 use std::path::Path;
 use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Local};
+use serde_derive::Serialize;
 use tokio::time::sleep;
 
 use pueue_lib::{
-    log::{get_log_file_handle, get_log_path, seek_to_last_lines},
+    log::{
+        complete_utf8_len, get_log_file_handle, get_log_path, rewind_if_truncated,
+        seek_to_last_lines,
+    },
     network::protocol::GenericStream,
 };
 
 use crate::client::commands::get_state;
 
+/// A single chunk of log output, as it's printed by `pueue follow --json`.
+#[derive(Debug, Serialize)]
+struct JsonLogChunk<'a> {
+    task_id: usize,
+    chunk: &'a str,
+    /// The amount of bytes that have been printed before this chunk.
+    offset: usize,
+    timestamp: DateTime<Local>,
+}
+
+/// Print followed log output as JSON Lines, one [JsonLogChunk] per received chunk.
+///
+/// Log output is read in arbitrarily sized byte chunks, which might split multi-byte UTF-8
+/// characters. Incomplete characters at the end of a chunk are buffered until the next chunk
+/// arrives, so we never emit invalid strings.
+pub struct JsonFollowPrinter {
+    task_id: usize,
+    offset: usize,
+    pending: Vec<u8>,
+}
+
+impl JsonFollowPrinter {
+    pub fn new(task_id: usize) -> Self {
+        JsonFollowPrinter {
+            task_id,
+            offset: 0,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Print all complete characters of the given bytes (and any previously buffered bytes).
+    pub fn print(&mut self, bytes: &[u8]) {
+        self.pending.extend_from_slice(bytes);
+
+        let complete = complete_utf8_len(&self.pending);
+        let rest = self.pending.split_off(complete);
+        let bytes = std::mem::replace(&mut self.pending, rest);
+        self.print_bytes(&bytes);
+    }
+
+    /// Print any remaining buffered bytes, even if they don't form a complete character.
+    pub fn finish(&mut self) {
+        let bytes = std::mem::take(&mut self.pending);
+        self.print_bytes(&bytes);
+    }
+
+    fn print_bytes(&mut self, bytes: &[u8]) {
+        if bytes.is_empty() {
+            return;
+        }
+
+        let chunk = String::from_utf8_lossy(bytes);
+        let line = JsonLogChunk {
+            task_id: self.task_id,
+            chunk: &chunk,
+            offset: self.offset,
+            timestamp: Local::now(),
+        };
+        self.offset += bytes.len();
+
+        println!("{}", serde_json::to_string(&line).unwrap());
+        io::stdout().flush().unwrap();
+    }
+}
+
+/// Print the output of multiple tasks line by line, each line prefixed with `[task_id]`.
+///
+/// Output is received in arbitrarily sized chunks. Incomplete lines are buffered per task,
+/// until the rest of the line arrives or the task finishes.
+#[derive(Default)]
+pub struct PrefixedFollowPrinter {
+    pending: BTreeMap<usize, Vec<u8>>,
+}
+
+impl PrefixedFollowPrinter {
+    /// Print all complete lines of the given bytes (and any previously buffered bytes).
+    pub fn print(&mut self, task_id: usize, bytes: &[u8]) {
+        let pending = self.pending.entry(task_id).or_default();
+        pending.extend_from_slice(bytes);
+
+        let Some(last_newline) = pending.iter().rposition(|byte| *byte == b'\n') else {
+            return;
+        };
+        let rest = pending.split_off(last_newline + 1);
+        let complete = std::mem::replace(pending, rest);
+
+        let mut stdout = io::stdout().lock();
+        for line in String::from_utf8_lossy(&complete).lines() {
+            writeln!(stdout, "[{task_id}] {line}").unwrap();
+        }
+        stdout.flush().unwrap();
+    }
+
+    /// Print any remaining buffered output of a task, even if it isn't terminated by a newline.
+    pub fn finish(&mut self, task_id: usize) {
+        let Some(pending) = self.pending.remove(&task_id) else {
+            return;
+        };
+        if pending.is_empty() {
+            return;
+        }
+
+        println!("[{task_id}] {}", String::from_utf8_lossy(&pending));
+        io::stdout().flush().unwrap();
+    }
+}
+
 /// Follow the log ouput of running task.
 ///
+/// If `json` is set, the output is printed as JSON Lines via [JsonFollowPrinter].
+///
 /// If no task is specified, this will check for the following cases:
 ///
 /// - No running task: Print an error that there are no running tasks.
@@ -24,6 +140,7 @@ pub async fn follow_local_task_logs(
     pueue_directory: &Path,
     task_id: usize,
     lines: Option<usize>,
+    json: bool,
 ) -> Result<()> {
     let mut handle = match get_log_file_handle(task_id, pueue_directory) {
         Ok(stdout) => stdout,
@@ -37,6 +154,7 @@ pub async fn follow_local_task_logs(
     // Stdout handle to directly stream log file output to `io::stdout`.
     // This prevents us from allocating any large amounts of memory.
     let mut stdout = io::stdout();
+    let mut json_printer = json.then(|| JsonFollowPrinter::new(task_id));
 
     // If `lines` is passed as an option, we only want to show the last `X` lines.
     // To achieve this, we seek the file handle to the start of the `Xth` line
@@ -61,8 +179,20 @@ pub async fn follow_local_task_logs(
             println!("Log file has gone away. Has the task been removed?");
             return Ok(());
         }
+        // Start from the beginning, if the log file has been truncated in the meantime.
+        if let Err(err) = rewind_if_truncated(&mut handle) {
+            println!("Error while reading file: {err}");
+            return Ok(());
+        }
         // Read the next chunk of text from the last position.
-        if let Err(err) = io::copy(&mut handle, &mut stdout) {
+        if let Some(printer) = json_printer.as_mut() {
+            let mut buffer = Vec::new();
+            if let Err(err) = handle.read_to_end(&mut buffer) {
+                println!("Error while reading file: {err}");
+                return Ok(());
+            };
+            printer.print(&buffer);
+        } else if let Err(err) = io::copy(&mut handle, &mut stdout) {
             println!("Error while reading file: {err}");
             return Ok(());
         };
@@ -81,8 +211,88 @@ pub async fn follow_local_task_logs(
 
             // The task is done.
             if !task.is_running() {
+                if let Some(printer) = json_printer.as_mut() {
+                    printer.finish();
+                }
+                return Ok(());
+            }
+        }
+
+        last_check += log_check_interval;
+        let timeout = Duration::from_millis(log_check_interval);
+        sleep(timeout).await;
+    }
+}
+
+/// Follow the log output of all running tasks at once via [PrefixedFollowPrinter].
+///
+/// Tasks that are started while following are picked up as well.
+/// Once a task finishes, its remaining output is printed and it's no longer followed.
+/// This returns as soon as no task is running anymore.
+pub async fn follow_local_all_task_logs(
+    stream: &mut GenericStream,
+    pueue_directory: &Path,
+    lines: Option<usize>,
+) -> Result<()> {
+    let mut printer = PrefixedFollowPrinter::default();
+    let mut handles: BTreeMap<usize, File> = BTreeMap::new();
+    let mut first_check = true;
+
+    // We check in regular intervals which tasks are running.
+    // See [follow_local_task_logs] for why this isn't done in every loop.
+    let task_check_interval = 2000;
+    let mut last_check = 0;
+
+    let log_check_interval = 100;
+    loop {
+        if (last_check % task_check_interval) == 0 {
+            let state = get_state(stream).await?;
+            let running_ids: Vec<usize> = state
+                .tasks
+                .iter()
+                .filter_map(|(&id, t)| if t.is_running() { Some(id) } else { None })
+                .collect();
+
+            // Print the remaining output of all tasks that finished and stop following them.
+            let finished_ids: Vec<usize> = handles
+                .keys()
+                .filter(|id| !running_ids.contains(id))
+                .cloned()
+                .collect();
+            for task_id in finished_ids {
+                let mut handle = handles.remove(&task_id).unwrap();
+                print_next_chunk(&mut printer, task_id, &mut handle);
+                printer.finish(task_id);
+            }
+
+            // Start following all tasks that have been started in the meantime.
+            // Only the output of tasks that already ran at the start is limited to `lines`.
+            for task_id in running_ids {
+                if handles.contains_key(&task_id) {
+                    continue;
+                }
+                let Ok(mut handle) = get_log_file_handle(task_id, pueue_directory) else {
+                    continue;
+                };
+                if let (true, Some(lines)) = (first_check, lines) {
+                    if let Err(err) = seek_to_last_lines(&mut handle, lines) {
+                        println!("Error seeking to last lines from log: {err}");
+                    }
+                }
+                handles.insert(task_id, handle);
+            }
+
+            if handles.is_empty() {
+                if first_check {
+                    bail!("There are no running tasks.");
+                }
                 return Ok(());
             }
+            first_check = false;
+        }
+
+        for (task_id, handle) in handles.iter_mut() {
+            print_next_chunk(&mut printer, *task_id, handle);
         }
 
         last_check += log_check_interval;
@@ -90,3 +300,19 @@ pub async fn follow_local_task_logs(
         sleep(timeout).await;
     }
 }
+
+/// Read all new output of a task's log file and pass it to the printer.
+fn print_next_chunk(printer: &mut PrefixedFollowPrinter, task_id: usize, handle: &mut File) {
+    // Start from the beginning, if the log file has been truncated in the meantime.
+    if let Err(err) = rewind_if_truncated(handle) {
+        println!("Error while reading file of task {task_id}: {err}");
+        return;
+    }
+
+    let mut buffer = Vec::new();
+    if let Err(err) = handle.read_to_end(&mut buffer) {
+        println!("Error while reading file of task {task_id}: {err}");
+        return;
+    };
+    printer.print(task_id, &buffer);
+}
@@ -1,6 +1,9 @@
 use std::collections::BTreeMap;
 
-use chrono::{DateTime, Local, LocalResult};
+use chrono::{DateTime, Duration, Local, LocalResult};
+use comfy_table::modifiers::UTF8_ROUND_CORNERS;
+use comfy_table::presets::{ASCII_FULL, NOTHING, UTF8_FULL, UTF8_HORIZONTAL_ONLY};
+use comfy_table::Table;
 
 use pueue_lib::{settings::Settings, task::Task};
 
@@ -22,6 +25,14 @@ pub fn start_of_today() -> DateTime<Local> {
     }
 }
 
+/// Render a task id, including the configured `id_prefix`.
+pub fn format_task_id(task_id: usize, settings: &Settings) -> String {
+    match &settings.shared.id_prefix {
+        Some(prefix) => format!("{prefix}-{task_id}"),
+        None => task_id.to_string(),
+    }
+}
+
 /// Sort given tasks by their groups.
 /// This is needed to print a table for each group.
 pub fn sort_tasks_by_group(tasks: Vec<Task>) -> BTreeMap<String, Vec<Task>> {
@@ -37,10 +48,33 @@ pub fn sort_tasks_by_group(tasks: Vec<Task>) -> BTreeMap<String, Vec<Task>> {
     sorted_task_groups
 }
 
+/// Sort given tasks by their labels.
+/// Returns the labeled tasks ordered alphabetically by their label and all unlabeled tasks.
+/// The tasks of each label are ordered by their id.
+pub fn sort_tasks_by_label(tasks: Vec<Task>) -> (BTreeMap<String, Vec<Task>>, Vec<Task>) {
+    let mut labeled: BTreeMap<String, Vec<Task>> = BTreeMap::new();
+    let mut unlabeled = Vec::new();
+    for task in tasks.into_iter() {
+        match &task.label {
+            Some(label) => labeled.entry(label.clone()).or_default().push(task),
+            None => unlabeled.push(task),
+        }
+    }
+
+    for tasks in labeled.values_mut() {
+        tasks.sort_by_key(|task| task.id);
+    }
+    unlabeled.sort_by_key(|task| task.id);
+
+    (labeled, unlabeled)
+}
+
 /// Returns the formatted `start` and `end` text for a given task.
 ///
-/// 1. If the start || end is today, skip the date.
-/// 2. Otherwise show the date in both.
+/// 1. If relative times are enabled and the start || end is less than a day ago,
+///    show how long ago it was, e.g. `5m ago`.
+/// 2. If the start || end is today, skip the date.
+/// 3. Otherwise show the date in both.
 ///
 /// If the task doesn't have a start and/or end yet, an empty string will be returned
 /// for the respective field.
@@ -52,18 +86,7 @@ pub fn formatted_start_end(task: &Task, settings: &Settings) -> (String, String)
         None => return ("".into(), "".into()),
     };
 
-    // If the task started today, just show the time.
-    // Otherwise show the full date and time.
-    let started_today = start >= start_of_today();
-    let formatted_start = if started_today {
-        start
-            .format(&settings.client.status_time_format)
-            .to_string()
-    } else {
-        start
-            .format(&settings.client.status_datetime_format)
-            .to_string()
-    };
+    let formatted_start = format_timestamp(start, settings);
 
     // Get finish time, if already set. Otherwise only return the formatted start.
     let end = match task.end {
@@ -71,15 +94,75 @@ pub fn formatted_start_end(task: &Task, settings: &Settings) -> (String, String)
         None => return (formatted_start, "".into()),
     };
 
-    // If the task ended today we only show the time.
-    // In all other circumstances, we show the full date.
-    let finished_today = end >= start_of_today();
-    let formatted_end = if finished_today {
-        end.format(&settings.client.status_time_format).to_string()
+    let formatted_end = format_timestamp(end, settings);
+
+    (formatted_start, formatted_end)
+}
+
+/// Format a single start or end time of a task.
+fn format_timestamp(time: DateTime<Local>, settings: &Settings) -> String {
+    if settings.client.status_relative_time {
+        if let Some(relative) = relative_time(time) {
+            return relative;
+        }
+    }
+
+    // If the time is today, just show the time.
+    // Otherwise show the full date and time.
+    if time >= start_of_today() {
+        time.format(&settings.client.status_time_format).to_string()
     } else {
-        end.format(&settings.client.status_datetime_format)
+        time.format(&settings.client.status_datetime_format)
             .to_string()
-    };
+    }
+}
 
-    (formatted_start, formatted_end)
+/// Express how long ago the given time was, e.g. `5m ago` or `2h ago`.
+/// Returns `None` for times that are a day or more ago.
+fn relative_time(time: DateTime<Local>) -> Option<String> {
+    // Clamp times in the future, which might happen if the clocks of client and daemon differ.
+    let elapsed = (Local::now() - time).max(Duration::zero());
+
+    if elapsed < Duration::minutes(1) {
+        Some(format!("{}s ago", elapsed.num_seconds()))
+    } else if elapsed < Duration::hours(1) {
+        Some(format!("{}m ago", elapsed.num_minutes()))
+    } else if elapsed < Duration::days(1) {
+        Some(format!("{}h ago", elapsed.num_hours()))
+    } else {
+        None
+    }
+}
+
+/// Returns the formatted resource usage of a running task, e.g. `120MB / 3.2%`.
+///
+/// Tasks that aren't running don't have any resource usage, in which case an empty string
+/// will be returned.
+pub fn formatted_resources(task: &Task) -> String {
+    match &task.resources {
+        Some(resources) if task.is_running() => format!(
+            "{}MB / {}.{}%",
+            resources.memory / 1_000_000,
+            resources.cpu_usage_permille / 10,
+            resources.cpu_usage_permille % 10
+        ),
+        _ => String::new(),
+    }
+}
+
+/// All values of the `client.table_style` setting.
+pub const TABLE_STYLES: [&str; 5] = ["horizontal", "full", "rounded", "ascii", "none"];
+
+/// Load the comfy-table preset of the configured `client.table_style`.
+/// Unknown styles fall back to the default `horizontal` style.
+pub fn load_table_style(table: &mut Table, settings: &Settings) {
+    match settings.client.table_style.as_str() {
+        "full" => table.load_preset(UTF8_FULL),
+        "rounded" => table
+            .load_preset(UTF8_FULL)
+            .apply_modifier(UTF8_ROUND_CORNERS),
+        "ascii" => table.load_preset(ASCII_FULL),
+        "none" => table.load_preset(NOTHING),
+        _ => table.load_preset(UTF8_HORIZONTAL_ONLY),
+    };
 }
@@ -0,0 +1,30 @@
+use pueue_lib::settings::Settings;
+use pueue_lib::task::Task;
+
+use super::{table_builder::TableBuilder, OutputStyle};
+use crate::client::cli::SubCommand;
+
+/// Get the output for archived tasks, which have been removed via `pueue clean`.
+pub fn print_history(
+    tasks: Vec<Task>,
+    cli_command: &SubCommand,
+    style: &OutputStyle,
+    settings: &Settings,
+) -> String {
+    let json = match cli_command {
+        SubCommand::History { json, .. } => *json,
+        _ => {
+            panic!("Got wrong Subcommand {cli_command:?} in print_history. This shouldn't happen!")
+        }
+    };
+
+    if json {
+        return serde_json::to_string(&tasks).unwrap();
+    }
+
+    if tasks.is_empty() {
+        return "No archived tasks found.".to_string();
+    }
+
+    TableBuilder::new(settings, style).build(&tasks).to_string()
+}
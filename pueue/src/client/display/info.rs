@@ -0,0 +1,67 @@
+use chrono::Local;
+use comfy_table::*;
+
+use pueue_lib::network::message::InfoResponseMessage;
+
+use super::OutputStyle;
+use crate::client::cli::SubCommand;
+
+/// Get the output for the information about the daemon.
+pub fn print_info(
+    info: InfoResponseMessage,
+    cli_command: &SubCommand,
+    style: &OutputStyle,
+) -> String {
+    let json = match cli_command {
+        SubCommand::Info { json } => *json,
+        _ => panic!("Got wrong Subcommand {cli_command:?} in print_info. This shouldn't happen!"),
+    };
+
+    if json {
+        return serde_json::to_string(&info).unwrap();
+    }
+
+    let uptime = Local::now() - info.start;
+    let uptime = format!(
+        "{}h {}m {}s",
+        uptime.num_hours(),
+        uptime.num_minutes() % 60,
+        uptime.num_seconds() % 60
+    );
+    let config_path = info
+        .config_path
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|| "none".to_string());
+
+    let id_prefix = info.id_prefix.unwrap_or_else(|| "none".to_string());
+
+    let mut table = Table::new();
+    table.load_preset(comfy_table::presets::NOTHING);
+    table.set_content_arrangement(ContentArrangement::Dynamic);
+
+    let rows = vec![
+        ("Version:", info.version),
+        (
+            "Started:",
+            info.start.format("%Y-%m-%d %H:%M:%S").to_string(),
+        ),
+        ("Uptime:", uptime),
+        ("PID:", info.pid.to_string()),
+        ("Running tasks:", info.running_tasks.to_string()),
+        ("Config:", config_path),
+        ("ID prefix:", id_prefix),
+    ];
+    for (name, value) in rows {
+        table.add_row(vec![
+            style.styled_cell(name, None, Some(Attribute::Bold)),
+            Cell::new(value),
+        ]);
+    }
+
+    // Explicitly force styling, in case we aren't on a tty, but `--color=always` is set.
+    if style.enabled {
+        table.enforce_styling();
+    }
+
+    table.to_string()
+}
@@ -4,7 +4,7 @@ use std::io::Read;
 use serde_derive::{Deserialize, Serialize};
 use snap::read::FrameDecoder;
 
-use pueue_lib::log::{get_log_file_handle, read_last_lines};
+use pueue_lib::log::{get_stream_log_file_handle, read_last_lines, read_line_range};
 use pueue_lib::network::message::TaskLogMessage;
 use pueue_lib::settings::Settings;
 use pueue_lib::task::Task;
@@ -20,6 +20,8 @@ pub fn print_log_json(
     task_log_messages: BTreeMap<usize, TaskLogMessage>,
     settings: &Settings,
     lines: Option<usize>,
+    offset: Option<usize>,
+    stderr: bool,
 ) {
     let mut tasks: BTreeMap<usize, Task> = BTreeMap::new();
     let mut task_log: BTreeMap<usize, String> = BTreeMap::new();
@@ -30,7 +32,7 @@ pub fn print_log_json(
         tasks.insert(id, message.task);
 
         if settings.client.read_local_logs {
-            let output = get_local_log(settings, id, lines);
+            let output = get_local_log(settings, id, lines, offset, stderr);
             task_log.insert(id, output);
         } else {
             let output = get_remote_log(message.output);
@@ -51,14 +53,29 @@ pub fn print_log_json(
 }
 
 /// Read logs directly from local files for a specific task.
-fn get_local_log(settings: &Settings, id: usize, lines: Option<usize>) -> String {
-    let mut file = match get_log_file_handle(id, &settings.shared.pueue_directory()) {
+fn get_local_log(
+    settings: &Settings,
+    id: usize,
+    lines: Option<usize>,
+    offset: Option<usize>,
+    stderr: bool,
+) -> String {
+    let pueue_directory = settings.shared.pueue_directory();
+    let mut file = match get_stream_log_file_handle(id, &pueue_directory, stderr) {
         Ok(file) => file,
         Err(err) => {
             return format!("(Pueue error) Failed to get log file handle: {err}");
         }
     };
 
+    // Only return the requested range of lines.
+    if let Some(offset) = offset {
+        return match read_line_range(&mut file, offset, lines) {
+            Ok(output) => String::from_utf8_lossy(&output).into_owned(),
+            Err(err) => format!("(Pueue error) Failed to read local log output file: {err:?}"),
+        };
+    }
+
     // Only return the last few lines.
     if let Some(lines) = lines {
         return read_last_lines(&mut file, lines);
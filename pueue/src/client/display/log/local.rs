@@ -1,11 +1,12 @@
 use std::fs::File;
-use std::io::{self, Stdout};
+use std::io::{self, Stdout, Write};
 
 use comfy_table::*;
 
-use pueue_lib::log::{get_log_file_handle, seek_to_last_lines};
+use pueue_lib::log::{get_stream_log_file_handle, read_line_range, seek_to_last_lines};
 use pueue_lib::settings::Settings;
 
+use super::log_header;
 use crate::client::display::OutputStyle;
 
 /// The daemon didn't send any log output, thereby we didn't request any.
@@ -15,8 +16,11 @@ pub fn print_local_log(
     style: &OutputStyle,
     settings: &Settings,
     lines: Option<usize>,
+    offset: Option<usize>,
+    stderr: bool,
 ) {
-    let mut file = match get_log_file_handle(task_id, &settings.shared.pueue_directory()) {
+    let pueue_directory = settings.shared.pueue_directory();
+    let mut file = match get_stream_log_file_handle(task_id, &pueue_directory, stderr) {
         Ok(file) => file,
         Err(err) => {
             println!("Failed to get log file handle: {err}");
@@ -26,13 +30,47 @@ pub fn print_local_log(
     // Stdout handler to directly write log file output to io::stdout
     // without having to load anything into memory.
     let mut stdout = io::stdout();
-
-    print_local_file(
-        &mut stdout,
-        &mut file,
-        &lines,
-        style.style_text("output:", Some(Color::Green), Some(Attribute::Bold)),
+    let header = style.style_text(
+        log_header(stderr),
+        Some(Color::Green),
+        Some(Attribute::Bold),
     );
+
+    // Only print the requested range of lines.
+    if let Some(offset) = offset {
+        print_local_file_range(&mut stdout, &mut file, offset, lines, header);
+        return;
+    }
+
+    print_local_file(&mut stdout, &mut file, &lines, header);
+}
+
+/// Print a specific range of lines of a local log file.
+/// In contrast to [print_local_file], no hint about truncated output is shown.
+fn print_local_file_range(
+    stdout: &mut Stdout,
+    file: &mut File,
+    offset: usize,
+    lines: Option<usize>,
+    header: String,
+) {
+    let output = match read_line_range(file, offset, lines) {
+        Ok(output) => output,
+        Err(err) => {
+            println!("Failed reading local log file: {err}");
+            return;
+        }
+    };
+    if output.is_empty() {
+        return;
+    }
+
+    // Print a newline between the task information and the first output.
+    println!("\n{header}");
+
+    if let Err(err) = stdout.write_all(&output) {
+        println!("Failed writing local log output: {err}");
+    };
 }
 
 /// Print a local log file of a task.
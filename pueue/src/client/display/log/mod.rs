@@ -3,10 +3,11 @@ use std::collections::BTreeMap;
 use comfy_table::*;
 
 use pueue_lib::network::message::TaskLogMessage;
+use pueue_lib::process_helper::signal_name;
 use pueue_lib::settings::Settings;
 use pueue_lib::task::{Task, TaskResult, TaskStatus};
 
-use super::OutputStyle;
+use super::{helper::format_task_id, OutputStyle};
 use crate::client::cli::SubCommand;
 
 mod json;
@@ -26,17 +27,34 @@ use remote::*;
 ///
 /// `full` always forces the full log output
 /// `lines` force a specific amount of lines
-pub fn determine_log_line_amount(full: bool, lines: &Option<usize>) -> Option<usize> {
+/// `offset` indicates that an explicit range is requested, which includes everything
+///     after the offset, unless `lines` is set.
+pub fn determine_log_line_amount(
+    full: bool,
+    lines: &Option<usize>,
+    offset: &Option<usize>,
+) -> Option<usize> {
     if full {
         None
     } else if let Some(lines) = lines {
         Some(*lines)
+    } else if offset.is_some() {
+        None
     } else {
         // By default, only some lines are shown per task
         Some(15)
     }
 }
 
+/// The header that's printed above the log output of a task.
+fn log_header(stderr: bool) -> &'static str {
+    if stderr {
+        "stderr:"
+    } else {
+        "output:"
+    }
+}
+
 /// Print the log ouput of finished tasks.
 /// Either print the logs of every task
 /// or only print the logs of the specified tasks.
@@ -48,21 +66,40 @@ pub fn print_logs(
 ) {
     // Get actual commandline options.
     // This is necessary to know how we should display/return the log information.
-    let (json, task_ids, lines, full) = match cli_command {
+    let (json, task_ids, lines, full, offset, stderr, archived) = match cli_command {
         SubCommand::Log {
             json,
             task_ids,
             lines,
             full,
-        } => (*json, task_ids.clone(), *lines, *full),
+            offset,
+            stderr,
+            archived,
+            ..
+        } => (
+            *json,
+            task_ids.clone(),
+            *lines,
+            *full,
+            *offset,
+            *stderr,
+            *archived,
+        ),
         _ => panic!("Got wrong Subcommand {cli_command:?} in print_log. This shouldn't happen"),
     };
 
-    let lines = determine_log_line_amount(full, &lines);
+    let lines = determine_log_line_amount(full, &lines, &offset);
+
+    // Archived logs are always sent by the daemon, as they aren't at their usual location.
+    let mut settings = settings.clone();
+    if archived {
+        settings.client.read_local_logs = false;
+    }
+    let settings = &settings;
 
     // Return the server response in json representation.
     if json {
-        print_log_json(task_logs, settings, lines);
+        print_log_json(task_logs, settings, lines, offset, stderr);
         return;
     }
 
@@ -80,7 +117,7 @@ pub fn print_logs(
     // Iterate over each task and print the respective log.
     let mut task_iter = task_logs.iter_mut().peekable();
     while let Some((_, task_log)) = task_iter.next() {
-        print_log(task_log, style, settings, lines);
+        print_log(task_log, style, settings, lines, offset, stderr);
 
         // Add a newline if there is another task that's going to be printed.
         if let Some((_, task_log)) = task_iter.peek() {
@@ -101,11 +138,15 @@ pub fn print_logs(
 /// lines: Whether we should reduce the log output of each task to a specific number of lines.
 ///         `None` implicates that everything should be printed.
 ///         This is only important, if we read local lines.
+/// offset: Print `lines` lines starting at this line, instead of the last `lines` lines.
+/// stderr: Print the separate stderr log instead of the default log.
 fn print_log(
     message: &mut TaskLogMessage,
     style: &OutputStyle,
     settings: &Settings,
     lines: Option<usize>,
+    offset: Option<usize>,
+    stderr: bool,
 ) {
     let task = &message.task;
     // We only show logs of finished or running tasks.
@@ -116,21 +157,22 @@ fn print_log(
         return;
     }
 
-    print_task_info(task, style);
+    print_task_info(task, style, settings);
 
     if settings.client.read_local_logs {
-        print_local_log(message.task.id, style, settings, lines);
+        print_local_log(message.task.id, style, settings, lines, offset, stderr);
     } else if message.output.is_some() {
-        print_remote_log(message, style, lines);
+        print_remote_log(message, style, lines, offset.is_some(), stderr);
     } else {
         println!("Logs requested from pueue daemon, but none received. Please report this bug.");
     }
 }
 
 /// Print some information about a task, which is displayed on top of the task's log output.
-fn print_task_info(task: &Task, style: &OutputStyle) {
+fn print_task_info(task: &Task, style: &OutputStyle, settings: &Settings) {
     // Print task id and exit code.
-    let task_cell = style.styled_cell(format!("Task {}: ", task.id), None, Some(Attribute::Bold));
+    let task_id = format_task_id(task.id, settings);
+    let task_cell = style.styled_cell(format!("Task {task_id}: "), None, Some(Attribute::Bold));
 
     let (exit_status, color) = match &task.status {
         TaskStatus::Paused => ("paused".into(), Color::White),
@@ -142,8 +184,14 @@ fn print_task_info(task: &Task, style: &OutputStyle) {
             }
             TaskResult::FailedToSpawn(err) => (format!("failed to spawn: {err}"), Color::Red),
             TaskResult::Killed => ("killed by system or user".into(), Color::Red),
+            TaskResult::KilledBySignal(signal) => (
+                format!("killed by signal {}", signal_name(*signal)),
+                Color::Red,
+            ),
             TaskResult::Errored => ("some IO error.\n Check daemon log.".into(), Color::Red),
             TaskResult::DependencyFailed => ("dependency failed".into(), Color::Red),
+            TaskResult::TimedOut => ("killed due to timeout".into(), Color::Red),
+            TaskResult::Skipped => ("skipped".into(), Color::Yellow),
         },
         _ => (task.status.to_string(), Color::White),
     };
@@ -6,23 +6,37 @@ use snap::read::FrameDecoder;
 
 use pueue_lib::network::message::TaskLogMessage;
 
-use super::OutputStyle;
+use super::{log_header, OutputStyle};
 
 /// Prints log output received from the daemon.
 /// We can safely call .unwrap() on output in here, since this
 /// branch is always called after ensuring that it is `Some`.
-pub fn print_remote_log(task_log: &TaskLogMessage, style: &OutputStyle, lines: Option<usize>) {
+///
+/// `is_range` indicates that an explicit range of lines has been requested.
+/// No truncation hint is shown in that case.
+/// `stderr` indicates that the separate stderr log has been requested.
+pub fn print_remote_log(
+    task_log: &TaskLogMessage,
+    style: &OutputStyle,
+    lines: Option<usize>,
+    is_range: bool,
+    stderr: bool,
+) {
     if let Some(bytes) = task_log.output.as_ref() {
         if !bytes.is_empty() {
             // Add a hint if we should limit the output to X lines **and** there are actually more
             // lines than that given limit.
             let mut line_info = String::new();
-            if !task_log.output_complete {
+            if !task_log.output_complete && !is_range {
                 line_info = lines.map_or(String::new(), |lines| format!(" (last {lines} lines)"));
             }
 
             // Print a newline between the task information and the first output.
-            let header = style.style_text("output:", Some(Color::Green), Some(Attribute::Bold));
+            let header = style.style_text(
+                log_header(stderr),
+                Some(Color::Green),
+                Some(Attribute::Bold),
+            );
             println!("\n{header}{line_info}");
 
             if let Err(err) = decompress_and_print_remote_log(bytes) {
@@ -0,0 +1,144 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use pueue_lib::state::State;
+use pueue_lib::task::TaskStatus;
+
+/// All task statuses in the form they're exported as label values.
+const STATUSES: [&str; 6] = ["queued", "stashed", "running", "paused", "done", "locked"];
+
+/// Render the state in the Prometheus text exposition format.
+///
+/// This includes:
+/// - The amount of tasks per group and status.
+/// - The amount of finished and failed tasks per group.
+/// - The parallel limit and the amount of running tasks per group.
+///
+/// All metrics are gauges, as cleaning or removing tasks also decreases the counts.
+pub fn format_metrics(state: &State) -> String {
+    // Count the tasks by group and status. Each group starts with all statuses at zero.
+    let mut tasks: BTreeMap<&str, BTreeMap<&str, usize>> = state
+        .groups
+        .keys()
+        .map(|group| {
+            let statuses = STATUSES.iter().map(|status| (*status, 0)).collect();
+            (group.as_str(), statuses)
+        })
+        .collect();
+    let mut completed: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut failed: BTreeMap<&str, usize> = BTreeMap::new();
+
+    for task in state.tasks.values() {
+        let group = task.group.as_str();
+        let status = match task.status {
+            TaskStatus::Queued => "queued",
+            TaskStatus::Stashed { .. } => "stashed",
+            TaskStatus::Running => "running",
+            TaskStatus::Paused => "paused",
+            TaskStatus::Done(_) => "done",
+            TaskStatus::Locked => "locked",
+        };
+        *tasks.entry(group).or_default().entry(status).or_default() += 1;
+
+        if task.is_done() {
+            *completed.entry(group).or_default() += 1;
+        }
+        if task.failed() {
+            *failed.entry(group).or_default() += 1;
+        }
+    }
+
+    let mut output = String::new();
+
+    write_header(
+        &mut output,
+        "pueue_tasks",
+        "gauge",
+        "The amount of tasks by group and status.",
+    );
+    for (group, statuses) in tasks.iter() {
+        for (status, count) in statuses {
+            let group = escape_label(group);
+            writeln!(
+                output,
+                "pueue_tasks{{group=\"{group}\",status=\"{status}\"}} {count}"
+            )
+            .unwrap();
+        }
+    }
+
+    write_header(
+        &mut output,
+        "pueue_tasks_completed",
+        "gauge",
+        "The amount of finished tasks by group.",
+    );
+    for group in tasks.keys() {
+        let count = completed.get(group).unwrap_or(&0);
+        let group = escape_label(group);
+        writeln!(output, "pueue_tasks_completed{{group=\"{group}\"}} {count}").unwrap();
+    }
+
+    write_header(
+        &mut output,
+        "pueue_tasks_failed",
+        "gauge",
+        "The amount of finished tasks that didn't succeed by group.",
+    );
+    for group in tasks.keys() {
+        let count = failed.get(group).unwrap_or(&0);
+        let group = escape_label(group);
+        writeln!(output, "pueue_tasks_failed{{group=\"{group}\"}} {count}").unwrap();
+    }
+
+    write_header(
+        &mut output,
+        "pueue_group_parallel_tasks",
+        "gauge",
+        "The amount of tasks a group may run in parallel.",
+    );
+    for (name, group) in state.groups.iter() {
+        let name = escape_label(name);
+        let limit = group.parallel_tasks;
+        writeln!(
+            output,
+            "pueue_group_parallel_tasks{{group=\"{name}\"}} {limit}"
+        )
+        .unwrap();
+    }
+
+    write_header(
+        &mut output,
+        "pueue_group_running_tasks",
+        "gauge",
+        "The amount of running tasks by group.",
+    );
+    for name in state.groups.keys() {
+        let running = state
+            .tasks
+            .values()
+            .filter(|task| &task.group == name && task.is_running())
+            .count();
+        let name = escape_label(name);
+        writeln!(
+            output,
+            "pueue_group_running_tasks{{group=\"{name}\"}} {running}"
+        )
+        .unwrap();
+    }
+
+    output
+}
+
+fn write_header(output: &mut String, name: &str, kind: &str, help: &str) {
+    writeln!(output, "# HELP {name} {help}").unwrap();
+    writeln!(output, "# TYPE {name} {kind}").unwrap();
+}
+
+/// Escape backslashes, double-quotes and line feeds, as required for label values.
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -5,7 +5,10 @@
 mod follow;
 mod group;
 pub mod helper;
+mod history;
+mod info;
 mod log;
+mod metrics;
 mod state;
 pub mod style;
 pub mod table_builder;
@@ -13,10 +16,15 @@ pub mod table_builder;
 use crossterm::style::Color;
 
 // Re-exports
-pub use self::follow::follow_local_task_logs;
+pub use self::follow::{
+    follow_local_all_task_logs, follow_local_task_logs, JsonFollowPrinter, PrefixedFollowPrinter,
+};
 pub use self::group::format_groups;
+pub use self::history::print_history;
+pub use self::info::print_info;
 pub use self::log::{determine_log_line_amount, print_logs};
-pub use self::state::print_state;
+pub use self::metrics::format_metrics;
+pub use self::state::{print_state, print_task};
 pub use self::style::OutputStyle;
 
 /// Used to style any generic success message from the daemon.
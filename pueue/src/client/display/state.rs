@@ -1,11 +1,14 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Local};
+use comfy_table::{Attribute, Cell, Color, ContentArrangement, Table};
 
+use pueue_lib::api::StatusV1;
 use pueue_lib::settings::Settings;
-use pueue_lib::state::{State, PUEUE_DEFAULT_GROUP};
-use pueue_lib::task::Task;
+use pueue_lib::state::{GroupStatus, State, PUEUE_DEFAULT_GROUP};
+use pueue_lib::task::{Task, TaskStatus};
 
 use super::{helper::*, table_builder::TableBuilder, OutputStyle};
-use crate::client::cli::SubCommand;
+use crate::client::cli::{StatusFormat, SubCommand};
 use crate::client::display::group::get_group_headline;
 use crate::client::query::apply_query;
 
@@ -23,15 +26,44 @@ pub fn print_state(
 ) -> Result<String> {
     let mut output = String::new();
 
-    let (json, group_only, query) = match cli_command {
-        SubCommand::Status { json, group, query } => (*json, group.clone(), Some(query)),
-        SubCommand::FormatStatus { group } => (false, group.clone(), None),
+    let (json, format, group_only, query, group_summary, group_by_label) = match cli_command {
+        SubCommand::Status {
+            json,
+            group,
+            query,
+            preset,
+            group_summary,
+            group_by_label,
+            format,
+            ..
+        } => {
+            let mut query = query.clone();
+            if let Some(preset) = preset {
+                query.insert(0, resolve_column_preset(preset, settings)?);
+            }
+            (
+                *json,
+                *format,
+                group.clone(),
+                Some(query),
+                *group_summary,
+                *group_by_label,
+            )
+        }
+        SubCommand::FormatStatus { group } => (false, None, group.clone(), None, false, false),
         _ => panic!("Got wrong Subcommand {cli_command:?} in print_state. This shouldn't happen!"),
     };
 
+    // Only keep tasks that have been running at some point during the given time window.
+    if let SubCommand::Status { since, until, .. } = cli_command {
+        if since.is_some() || until.is_some() {
+            tasks.retain(|task| ran_during(task, *since, *until));
+        }
+    }
+
     let mut table_builder = TableBuilder::new(settings, style);
 
-    if let Some(query) = query {
+    if let Some(query) = &query {
         let query_result = apply_query(&query.join(" "))?;
         table_builder.set_visibility_by_rules(&query_result.selected_columns);
         tasks = query_result.apply_filters(tasks);
@@ -48,6 +80,42 @@ pub fn print_state(
         return Ok(output);
     }
 
+    // Print the state in the requested stable format.
+    if let Some(StatusFormat::JsonV1) = format {
+        let status = StatusV1::new(&state, &tasks);
+        output.push_str(&serde_json::to_string(&status).unwrap());
+        return Ok(output);
+    }
+
+    if let Some(StatusFormat::Line) = format {
+        if let Some(group) = &group_only {
+            tasks.retain(|task| &task.group == group);
+        }
+        print_lines(tasks, settings, &mut output);
+        return Ok(output);
+    }
+
+    if !TABLE_STYLES.contains(&settings.client.table_style.as_str()) {
+        eprintln!(
+            "Unknown table style \"{}\", using the default style. Available styles: {}",
+            settings.client.table_style,
+            TABLE_STYLES.join(", ")
+        );
+    }
+
+    if group_summary {
+        print_group_summary(state, tasks, style, settings, group_only, &mut output);
+        return Ok(output);
+    }
+
+    if group_by_label {
+        if let Some(group) = &group_only {
+            tasks.retain(|task| &task.group == group);
+        }
+        print_labels(tasks, style, table_builder, &mut output);
+        return Ok(output);
+    }
+
     if let Some(group) = group_only {
         print_single_group(state, tasks, style, group, table_builder, &mut output);
         return Ok(output);
@@ -58,6 +126,111 @@ pub fn print_state(
     Ok(output)
 }
 
+/// Get the output for a single task, which has been requested via `pueue status <id> --single`.
+pub fn print_task(
+    task: Task,
+    cli_command: &SubCommand,
+    style: &OutputStyle,
+    settings: &Settings,
+) -> String {
+    if let SubCommand::Status { json: true, .. } = cli_command {
+        return serde_json::to_string(&task).unwrap();
+    }
+
+    TableBuilder::new(settings, style)
+        .build(&[task])
+        .to_string()
+}
+
+/// Check whether a task has been running at some point between `since` and `until`.
+/// Tasks that are still running are considered to be running until now.
+/// Tasks that have never been started are never included.
+fn ran_during(task: &Task, since: Option<DateTime<Local>>, until: Option<DateTime<Local>>) -> bool {
+    let Some(start) = task.start else {
+        return false;
+    };
+    let end = task.end.unwrap_or_else(Local::now);
+
+    since.map_or(true, |since| end >= since) && until.map_or(true, |until| start <= until)
+}
+
+/// Resolve a column preset from the client settings into a `columns=...` query clause.
+fn resolve_column_preset(name: &str, settings: &Settings) -> Result<String> {
+    let Some(columns) = settings.client.column_presets.get(name) else {
+        bail!("Unknown column preset \"{name}\". Check the `client.column_presets` setting.");
+    };
+
+    if columns.is_empty() {
+        bail!("Column preset \"{name}\" doesn't contain any columns.");
+    }
+
+    Ok(format!("columns={}", columns.join(",")))
+}
+
+/// Print a single table with one row per group, which contains the amount of tasks per status.
+///
+/// If `group_only` is set, only the row of that group will be printed.
+fn print_group_summary(
+    state: State,
+    tasks: Vec<Task>,
+    style: &OutputStyle,
+    settings: &Settings,
+    group_only: Option<String>,
+    output: &mut String,
+) {
+    if let Some(group_name) = &group_only {
+        if !state.groups.contains_key(group_name) {
+            eprintln!("There exists no group \"{group_name}\"");
+            return;
+        }
+    }
+
+    let sorted_tasks = sort_tasks_by_group(tasks);
+
+    let mut table = Table::new();
+    load_table_style(&mut table, settings);
+    table
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            "Group", "Status", "Parallel", "Queued", "Running", "Paused", "Done", "Failed",
+        ]);
+
+    for (name, group) in state.groups.iter() {
+        if matches!(&group_only, Some(group_only) if group_only != name) {
+            continue;
+        }
+
+        let tasks = sorted_tasks
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let count = |filter: fn(&Task) -> bool| tasks.iter().filter(|task| filter(task)).count();
+
+        let (status, color) = match group.status {
+            GroupStatus::Running => ("running", Color::Green),
+            GroupStatus::Paused => ("paused", Color::Yellow),
+        };
+
+        table.add_row(vec![
+            Cell::new(name),
+            style.styled_cell(status, Some(color), None),
+            Cell::new(group.parallel_tasks),
+            Cell::new(count(|task| task.is_queued())),
+            Cell::new(count(|task| task.status == TaskStatus::Running)),
+            Cell::new(count(|task| task.status == TaskStatus::Paused)),
+            Cell::new(count(|task| task.is_done() && !task.failed())),
+            Cell::new(count(|task| task.failed())),
+        ]);
+    }
+
+    // Explicitly force styling, in case we aren't on a tty, but `--color=always` is set.
+    if style.enabled {
+        table.enforce_styling();
+    }
+
+    output.push_str(&table.to_string());
+}
+
 /// The user requested only a single group to be displayed.
 ///
 /// Print this group or show an error if this group doesn't exist.
@@ -94,6 +267,43 @@ fn print_single_group(
     output.push_str(&format!("\n{table}"));
 }
 
+/// Print a table for each label. All tasks will be shown in the table of their label.
+///
+/// Labels are ordered alphabetically. Tasks without a label are shown in a last, separate table.
+fn print_labels(
+    tasks: Vec<Task>,
+    style: &OutputStyle,
+    table_builder: TableBuilder,
+    output: &mut String,
+) {
+    if tasks.is_empty() {
+        output.push_str("Task list is empty. Add tasks with `pueue add -- [cmd]`");
+        return;
+    }
+
+    let (labeled, unlabeled) = sort_tasks_by_label(tasks);
+    let mut sections: Vec<(String, Vec<Task>)> = labeled
+        .into_iter()
+        .map(|(label, tasks)| (format!("Label \"{label}\""), tasks))
+        .collect();
+    if !unlabeled.is_empty() {
+        sections.push(("Unlabeled".to_string(), unlabeled));
+    }
+
+    let mut sections = sections.into_iter().peekable();
+    while let Some((headline, tasks)) = sections.next() {
+        let headline = style.style_text(headline, None, Some(Attribute::Bold));
+        output.push_str(&headline);
+        let table = table_builder.clone().build(&tasks);
+        output.push_str(&format!("\n{table}"));
+
+        // Add a newline between labels
+        if sections.peek().is_some() {
+            output.push_str("\n\n");
+        }
+    }
+}
+
 /// Print all groups. All tasks will be shown in the table of their assigned group.
 ///
 /// This will create multiple tables, one table for each group.
@@ -122,7 +332,7 @@ fn print_all_groups(
     let sorted_tasks = sort_tasks_by_group(tasks);
 
     // Always print the default queue at the very top, if no specific group is requested.
-    if sorted_tasks.get(PUEUE_DEFAULT_GROUP).is_some() {
+    if sorted_tasks.contains_key(PUEUE_DEFAULT_GROUP) {
         let tasks = sorted_tasks.get(PUEUE_DEFAULT_GROUP).unwrap();
         let headline = get_group_headline(
             PUEUE_DEFAULT_GROUP,
@@ -162,3 +372,25 @@ fn print_all_groups(
         }
     }
 }
+
+/// Print one line per task with its id, status, group and command, separated by tabs.
+/// Finished tasks show their result as status.
+/// This format is meant to be easily processed by tools like `awk` or `cut`.
+fn print_lines(tasks: Vec<Task>, settings: &Settings, output: &mut String) {
+    let lines: Vec<String> = tasks
+        .iter()
+        .map(|task| {
+            let status = match &task.status {
+                TaskStatus::Done(result) => result.to_string(),
+                status => status.to_string(),
+            };
+            // Multi-line commands would break the line-based format.
+            let command = task.command.replace('\n', " ");
+
+            let id = format_task_id(task.id, settings);
+            format!("{id}\t{status}\t{}\t{command}", task.group)
+        })
+        .collect();
+
+    output.push_str(&lines.join("\n"));
+}
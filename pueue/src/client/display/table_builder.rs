@@ -1,11 +1,13 @@
 use chrono::Duration;
-use comfy_table::presets::UTF8_HORIZONTAL_ONLY;
 use comfy_table::*;
 
+use pueue_lib::process_helper::signal_name;
 use pueue_lib::settings::Settings;
 use pueue_lib::task::{Task, TaskResult, TaskStatus};
 
-use super::helper::{formatted_start_end, start_of_today};
+use super::helper::{
+    format_task_id, formatted_resources, formatted_start_end, load_table_style, start_of_today,
+};
 use super::OutputStyle;
 use crate::client::query::Rule;
 
@@ -30,6 +32,9 @@ pub struct TableBuilder<'a> {
     label: bool,
     command: bool,
     path: bool,
+    /// The resource usage is only shown, if it's explicitly selected.
+    resources: bool,
+    note: bool,
     start: bool,
     end: bool,
 }
@@ -49,6 +54,8 @@ impl<'a> TableBuilder<'a> {
             label: false,
             command: true,
             path: true,
+            resources: false,
+            note: false,
             start: true,
             end: true,
         }
@@ -58,9 +65,9 @@ impl<'a> TableBuilder<'a> {
         self.determine_special_columns(tasks);
 
         let mut table = Table::new();
+        load_table_style(&mut table, self.settings);
         table
             .set_content_arrangement(ContentArrangement::Dynamic)
-            .load_preset(UTF8_HORIZONTAL_ONLY)
             .set_header(self.build_header())
             .add_rows(self.build_task_rows(tasks));
 
@@ -101,6 +108,11 @@ impl<'a> TableBuilder<'a> {
         if tasks.iter().any(|task| task.label.is_some()) {
             self.label = true;
         }
+
+        // Check whether there are any tasks with a note.
+        if tasks.iter().any(|task| task.note.is_some()) {
+            self.note = true;
+        }
     }
 
     /// Take a list of given [pest] rules from our `crate::client::query::column_selection::apply` logic.
@@ -119,6 +131,8 @@ impl<'a> TableBuilder<'a> {
         self.label = false;
         self.command = false;
         self.path = false;
+        self.resources = false;
+        self.note = false;
         self.start = false;
         self.end = false;
 
@@ -134,6 +148,8 @@ impl<'a> TableBuilder<'a> {
                 Rule::column_label => self.label = true,
                 Rule::column_command => self.command = true,
                 Rule::column_path => self.path = true,
+                Rule::column_resources => self.resources = true,
+                Rule::column_note => self.note = true,
                 Rule::column_start => self.start = true,
                 Rule::column_end => self.end = true,
                 _ => (),
@@ -168,6 +184,12 @@ impl<'a> TableBuilder<'a> {
         if self.path {
             header.push(Cell::new("Path"));
         }
+        if self.resources {
+            header.push(Cell::new("Resources"));
+        }
+        if self.note {
+            header.push(Cell::new("Note"));
+        }
         if self.start {
             header.push(Cell::new("Start"));
         }
@@ -189,26 +211,51 @@ impl<'a> TableBuilder<'a> {
             }
 
             if self.id {
-                row.add_cell(Cell::new(task.id));
+                row.add_cell(Cell::new(format_task_id(task.id, self.settings)));
             }
 
             if self.status {
                 // Determine the human readable task status representation and the respective color.
                 let status_string = task.status.to_string();
                 let (status_text, color) = match &task.status {
+                    TaskStatus::Running if task.immediate => {
+                        (format!("{status_string} (immediate)"), Color::Green)
+                    }
                     TaskStatus::Running => (status_string, Color::Green),
+                    TaskStatus::Queued if task.held => {
+                        (format!("{status_string} (held)"), Color::White)
+                    }
+                    TaskStatus::Queued if task.manual_start => {
+                        (format!("{status_string} (paused)"), Color::White)
+                    }
+                    TaskStatus::Stashed { .. } if task.stashed_by_age => {
+                        (format!("{status_string} (too old)"), Color::Yellow)
+                    }
                     TaskStatus::Paused | TaskStatus::Locked => (status_string, Color::White),
                     TaskStatus::Done(result) => match result {
                         TaskResult::Success => (TaskResult::Success.to_string(), Color::Green),
                         TaskResult::DependencyFailed => {
                             ("Dependency failed".to_string(), Color::Red)
                         }
-                        TaskResult::FailedToSpawn(_) => ("Failed to spawn".to_string(), Color::Red),
+                        TaskResult::FailedToSpawn(err) => {
+                            (format!("Failed to spawn: {err}"), Color::Red)
+                        }
                         TaskResult::Failed(code) => (format!("Failed ({code})"), Color::Red),
+                        TaskResult::TimedOut => ("Timed out".to_string(), Color::Red),
+                        TaskResult::Skipped => ("Skipped".to_string(), Color::Yellow),
+                        TaskResult::KilledBySignal(signal) => {
+                            (format!("Killed ({})", signal_name(*signal)), Color::Red)
+                        }
                         _ => (result.to_string(), Color::Red),
                     },
                     _ => (status_string, Color::Yellow),
                 };
+                // Show the attempt number for tasks that have been re-enqueued.
+                let status_text = if task.attempt() > 1 {
+                    format!("{status_text} (attempt {})", task.attempt())
+                } else {
+                    status_text
+                };
                 row.add_cell(self.style.styled_cell(status_text, Some(color), None));
             }
 
@@ -241,7 +288,17 @@ impl<'a> TableBuilder<'a> {
             }
 
             if self.label {
-                row.add_cell(Cell::new(task.label.as_deref().unwrap_or_default()));
+                // Labels aren't colored in dark mode, as the chosen colors might be unreadable.
+                let color = task
+                    .label_color
+                    .as_deref()
+                    .filter(|_| !self.settings.client.dark_mode)
+                    .and_then(|color| Color::try_from(color).ok());
+                row.add_cell(self.style.styled_cell(
+                    task.label.as_deref().unwrap_or_default(),
+                    color,
+                    None,
+                ));
             }
 
             // Add command and path.
@@ -257,6 +314,14 @@ impl<'a> TableBuilder<'a> {
                 row.add_cell(Cell::new(task.path.to_string_lossy()));
             }
 
+            if self.resources {
+                row.add_cell(Cell::new(formatted_resources(task)));
+            }
+
+            if self.note {
+                row.add_cell(Cell::new(task.note.as_deref().unwrap_or_default()));
+            }
+
             // Add start and end info
             let (start, end) = formatted_start_end(task, self.settings);
             if self.start {
@@ -4,3 +4,6 @@ pub mod client;
 mod commands;
 pub(crate) mod display;
 pub mod query;
+
+// The diagnostics have to run without a connection to the daemon.
+pub use commands::doctor;
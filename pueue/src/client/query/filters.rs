@@ -68,8 +68,7 @@ pub fn datetime(section: Pair<'_, Rule>, query_result: &mut QueryResult) -> Resu
     let mut filter = section.into_inner();
     // Get the column this filter should be applied to.
     // Either of [Rule::column_enqueue_at | Rule::column_start | Rule::column_end]
-    let column = filter.next().unwrap();
-    let column = column.as_rule();
+    let column_rule = filter.next().unwrap().as_rule();
 
     // Get the operator that should be applied in this filter.
     // Either of [Rule::eq | Rule::neq | Rule::lt | Rule::gt]
@@ -102,7 +101,7 @@ pub fn datetime(section: Pair<'_, Rule>, query_result: &mut QueryResult) -> Resu
 
     let filter_function = Box::new(move |task: &Task| -> bool {
         // Get the field we should apply the filter to.
-        let field = match column {
+        let field = match column_rule {
             Rule::column_enqueue_at => {
                 if let TaskStatus::Stashed {
                     enqueue_at: Some(enqueue_at),
@@ -114,8 +114,8 @@ impl QueryResult {
 /// `pueue status` process:
 ///
 /// - TableBuilder: The component responsible for building the table and determining which
-///         columns should or need to be displayed.
-///         A `columns [columns]` statement will define the set of visible columns.
+///   columns should or need to be displayed.
+///   A `columns [columns]` statement will define the set of visible columns.
 pub fn apply_query(query: &str) -> Result<QueryResult> {
     let mut parsed = QueryParser::parse(Rule::query, query).context("Failed to parse query")?;
 
@@ -69,7 +69,7 @@ pub fn order_by(section: Pair<'_, Rule>, query_result: &mut QueryResult) -> Resu
     // Get the column we should order by.
     // The column is wrapped by a `Rule::column` keyword.
     let column_keyword = order_by_condition.next().unwrap();
-    let column = column_keyword.into_inner().next().unwrap().as_rule();
+    let column_rule = column_keyword.into_inner().next().unwrap().as_rule();
 
     // Get the direction we should order by.
     // If no direction is provided, default to `Ascending`.
@@ -79,6 +79,6 @@ pub fn order_by(section: Pair<'_, Rule>, query_result: &mut QueryResult) -> Resu
         _ => Direction::Ascending,
     };
 
-    query_result.order_by = Some((column, direction));
+    query_result.order_by = Some((column_rule, direction));
     Ok(())
 }
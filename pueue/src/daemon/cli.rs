@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use clap::{ArgAction, Parser, ValueHint};
+use clap::{ArgAction, Parser, ValueEnum, ValueHint};
 
 #[derive(Parser, Debug)]
 #[command(
@@ -20,12 +20,32 @@ pub struct CliArguments {
     #[arg(short, long)]
     pub daemonize: bool,
 
+    /// Explicitly run the daemon in the foreground, e.g. inside of a container.
+    /// This is the default behavior, unless `--daemonize` is given.
+    #[arg(long, conflicts_with = "daemonize")]
+    pub foreground: bool,
+
+    /// The format of the daemon's log output.
+    /// `json` writes a single JSON object per log line to stdout.
+    #[arg(long, value_enum, default_value_t = LogFormat::Human, env = "PUEUED_LOG_FORMAT")]
+    pub log_format: LogFormat,
+
     /// Path to a specific pueue config file to use.
     /// This ignores all other config files.
     #[arg(short, long, value_hint = ValueHint::FilePath)]
     pub config: Option<PathBuf>,
 
     /// The name of the profile that should be loaded from your config file.
+    /// If this isn't given, the `default_profile` from your config file is used, if it's set.
     #[arg(short, long)]
     pub profile: Option<String>,
 }
+
+/// The formats in which the daemon can write its log output.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human readable log lines.
+    Human,
+    /// One JSON object per line, which can be processed by log collectors.
+    Json,
+}
@@ -0,0 +1,75 @@
+use std::io::Write;
+
+use chrono::Local;
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+use serde_json::{json, Value};
+
+/// Write every log record as a single JSON object to stdout.
+///
+/// Each line contains the `timestamp`, `level`, `target` and `message` of the record.
+pub struct JsonLogger {
+    level: LevelFilter,
+}
+
+impl JsonLogger {
+    /// Install the logger as the global logger for the `log` crate.
+    pub fn init(level: LevelFilter) -> Result<(), SetLoggerError> {
+        log::set_max_level(level);
+        log::set_logger(Box::leak(Box::new(JsonLogger { level })))
+    }
+}
+
+impl Log for JsonLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format_record(record);
+
+        // There's nowhere to report a failed write to, so the line is dropped.
+        let _ = writeln!(std::io::stdout().lock(), "{line}");
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stdout().flush();
+    }
+}
+
+/// Convert a log record into its JSON representation.
+fn format_record(record: &Record) -> Value {
+    json!({
+        "timestamp": Local::now().to_rfc3339(),
+        "level": record.level().as_str(),
+        "target": record.target(),
+        "message": record.args().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use log::Level;
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_format_record() {
+        // The formatted arguments only live until the end of the statement.
+        let line = format_record(
+            &Record::builder()
+                .level(Level::Warn)
+                .target("pueue::daemon")
+                .args(format_args!("Task {} failed", 3))
+                .build(),
+        );
+        assert_eq!(line["level"], "WARN");
+        assert_eq!(line["target"], "pueue::daemon");
+        assert_eq!(line["message"], "Task 3 failed");
+        assert!(line["timestamp"].is_string());
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::{Arc, Mutex};
 use std::{fs::create_dir_all, path::PathBuf};
 
 use anyhow::{bail, Context, Result};
+use chrono::Local;
 use log::{error, warn};
 use std::sync::mpsc::channel;
 
@@ -11,14 +12,17 @@ use pueue_lib::network::certificate::create_certificates;
 use pueue_lib::network::message::Shutdown;
 use pueue_lib::network::protocol::socket_cleanup;
 use pueue_lib::network::secret::init_shared_secret;
-use pueue_lib::settings::Settings;
+use pueue_lib::settings::{configuration_directories, Settings};
 use pueue_lib::state::State;
 
 use self::state_helper::{restore_state, save_state};
+use crate::daemon::network::message_handler::DaemonMetadata;
 use crate::daemon::network::socket::accept_incoming;
 use crate::daemon::task_handler::{TaskHandler, TaskSender};
 
 pub mod cli;
+/// A logger that writes structured JSON lines.
+pub mod json_logger;
 mod network;
 mod pid;
 /// Contains re-usable helper functions, that operate on the pueue-lib state.
@@ -34,6 +38,8 @@ mod task_handler;
 /// This is due to the fact, that tests in the same file are executed in multiple threads.
 /// Since the threads own the same global space, this would crash.
 pub async fn run(config_path: Option<PathBuf>, profile: Option<String>, test: bool) -> Result<()> {
+    let start = Local::now();
+
     // Try to read settings from the configuration file.
     let (mut settings, config_found) =
         Settings::read(&config_path).context("Error while reading configuration.")?;
@@ -47,9 +53,23 @@ pub async fn run(config_path: Option<PathBuf>, profile: Option<String>, test: bo
         }
     };
 
-    // Load any requested profile.
-    if let Some(profile) = &profile {
-        settings.load_profile(profile)?;
+    // Remember the config file that's used, so it can be shown via `pueue info`.
+    let config_path = config_path.or_else(|| {
+        configuration_directories()
+            .into_iter()
+            .map(|directory| directory.join("pueue.yml"))
+            .find(|path| path.is_file())
+    });
+
+    // Load any requested profile or the default profile.
+    settings.load_requested_profile(&profile)?;
+
+    #[cfg(not(target_os = "linux"))]
+    if settings.daemon.log_to_journald {
+        bail!(
+            "The 'daemon.log_to_journald' setting is only supported on Linux.\n\
+            Please remove it from your config file."
+        );
     }
 
     #[allow(deprecated)]
@@ -68,6 +88,9 @@ pub async fn run(config_path: Option<PathBuf>, profile: Option<String>, test: bo
     }
     init_shared_secret(&settings.shared.shared_secret_path())
         .context("Failed to initialize shared secret.")?;
+    if let Some(path) = settings.shared.read_only_secret_path() {
+        init_shared_secret(&path).context("Failed to initialize read-only secret.")?;
+    }
     pid::create_pid_file(&settings.shared.pid_path()).context("Failed to create pid file.")?;
 
     // Restore the previous state and save any changes that might have happened during this
@@ -102,7 +125,12 @@ pub async fn run(config_path: Option<PathBuf>, profile: Option<String>, test: bo
         task_handler.run();
     });
 
-    accept_incoming(sender, state.clone(), settings.clone()).await?;
+    let metadata = DaemonMetadata {
+        start,
+        config_path,
+        id_prefix: settings.shared.id_prefix.clone(),
+    };
+    accept_incoming(sender, state.clone(), settings.clone(), metadata).await?;
 
     Ok(())
 }
@@ -1,4 +1,6 @@
-use std::io::Read;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
 use std::path::Path;
 use std::time::Duration;
 
@@ -16,6 +18,10 @@ pub async fn handle_follow(
     state: &SharedState,
     message: StreamRequestMessage,
 ) -> Result<Message> {
+    if message.all {
+        return handle_follow_all(pueue_directory, stream, state, message.lines).await;
+    }
+
     // The user can specify the id of the task they want to follow
     // If the id isn't specified and there's only a single running task, this task will be used.
     // However, if there are multiple running tasks, the user will have to specify an id.
@@ -63,16 +69,27 @@ pub async fn handle_follow(
     // since the file can go away (e.g. due to finishing a task).
     let path = get_log_path(task_id, pueue_directory);
 
-    // If `lines` is passed as an option, we only want to show the last `X` lines.
+    // If an `offset` is passed, the client already knows the output up to this byte.
+    // Otherwise, if `lines` is passed as an option, we only want to show the last `X` lines.
     // To achieve this, we seek the file handle to the start of the `Xth` line
     // from the end of the file.
     // The loop following this section will then only copy those last lines to stdout.
-    if let Some(lines) = message.lines {
+    if let Some(offset) = message.offset {
+        if let Err(err) = handle.seek(SeekFrom::Start(offset)) {
+            return Ok(create_failure_message(format!(
+                "Error seeking to offset {offset} of log: {err}"
+            )));
+        }
+    } else if let Some(lines) = message.lines {
         if let Err(err) = seek_to_last_lines(&mut handle, lines) {
             println!("Error seeking to last lines from log: {err}");
         }
     }
 
+    // Bytes of an incomplete UTF-8 character at the end of the last chunk.
+    // They're prepended to the next chunk, so multi-byte characters don't get mangled.
+    let mut pending = Vec::new();
+
     loop {
         // Check whether the file still exists. Exit if it doesn't.
         if !path.exists() {
@@ -80,12 +97,17 @@ pub async fn handle_follow(
                 "Log file has gone away. Has the task been removed?",
             ));
         }
+        // Start from the beginning, if the log file has been truncated in the meantime.
+        if let Err(err) = rewind_if_truncated(&mut handle) {
+            return Ok(create_failure_message(format!("Error: {err}")));
+        }
         // Read the next chunk of text from the last position.
-        let mut buffer = Vec::new();
+        let mut buffer = std::mem::take(&mut pending);
 
         if let Err(err) = handle.read_to_end(&mut buffer) {
             return Ok(create_failure_message(format!("Error: {err}")));
         };
+        pending = buffer.split_off(complete_utf8_len(&buffer));
         let text = String::from_utf8_lossy(&buffer).to_string();
 
         // Only send a message, if there's actual new content.
@@ -118,3 +140,105 @@ pub async fn handle_follow(
         tokio::time::sleep(Duration::from_millis(1000)).await;
     }
 }
+
+/// The log file of a task, that's followed as part of [handle_follow_all].
+struct FollowedLog {
+    handle: File,
+    /// Bytes of an incomplete UTF-8 character at the end of the last chunk.
+    pending: Vec<u8>,
+}
+
+/// Handle the continuous stream of the output of all running tasks.
+///
+/// Tasks that are started while following are picked up as well.
+/// Once a task finishes, its remaining output is sent with `finished` set, which closes the
+/// stream of that task. The whole stream is closed, once no task is running anymore.
+async fn handle_follow_all(
+    pueue_directory: &Path,
+    stream: &mut GenericStream,
+    state: &SharedState,
+    lines: Option<usize>,
+) -> Result<Message> {
+    let mut logs: BTreeMap<usize, FollowedLog> = BTreeMap::new();
+    let mut first_iteration = true;
+
+    loop {
+        let running_ids: Vec<usize> = {
+            let state = state.lock().unwrap();
+            state
+                .tasks
+                .iter()
+                .filter_map(|(&id, t)| if t.is_running() { Some(id) } else { None })
+                .collect()
+        };
+
+        if running_ids.is_empty() && logs.is_empty() {
+            if first_iteration {
+                return Ok(create_failure_message("There are no running tasks."));
+            }
+            return Ok(Message::Close);
+        }
+
+        // Start following all tasks that have been started in the meantime.
+        // Only the output of tasks that already ran at the start is limited to `lines`.
+        for task_id in running_ids.iter() {
+            if logs.contains_key(task_id) {
+                continue;
+            }
+            let Ok(mut handle) = get_log_file_handle(*task_id, pueue_directory) else {
+                continue;
+            };
+            if let (true, Some(lines)) = (first_iteration, lines) {
+                if let Err(err) = seek_to_last_lines(&mut handle, lines) {
+                    println!("Error seeking to last lines from log: {err}");
+                }
+            }
+            logs.insert(
+                *task_id,
+                FollowedLog {
+                    handle,
+                    pending: Vec::new(),
+                },
+            );
+        }
+        first_iteration = false;
+
+        // Send the next chunk of each task.
+        // Tasks that are no longer running won't produce any more output, so their stream is closed.
+        let mut finished_ids = Vec::new();
+        for (task_id, log) in logs.iter_mut() {
+            let finished = !running_ids.contains(task_id);
+
+            if let Err(err) = rewind_if_truncated(&mut log.handle) {
+                return Ok(create_failure_message(format!("Error: {err}")));
+            }
+            let mut buffer = std::mem::take(&mut log.pending);
+            if let Err(err) = log.handle.read_to_end(&mut buffer) {
+                return Ok(create_failure_message(format!("Error: {err}")));
+            };
+            if !finished {
+                log.pending = buffer.split_off(complete_utf8_len(&buffer));
+            }
+            let text = String::from_utf8_lossy(&buffer).to_string();
+
+            if !text.is_empty() || finished {
+                let response = TaskStreamMessage {
+                    task_id: *task_id,
+                    text,
+                    finished,
+                };
+                send_message(response, stream).await?;
+            }
+
+            if finished {
+                finished_ids.push(*task_id);
+            }
+        }
+        for task_id in finished_ids {
+            logs.remove(&task_id);
+        }
+
+        // Wait for 1 second before sending the next chunks.
+        tokio::time::sleep(Duration::from_millis(1000)).await;
+    }
+}
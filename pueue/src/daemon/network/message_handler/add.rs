@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use chrono::Local;
 use pueue_lib::aliasing::insert_alias;
 use pueue_lib::network::message::*;
@@ -5,7 +7,7 @@ use pueue_lib::state::{GroupStatus, SharedState};
 use pueue_lib::task::{Task, TaskStatus};
 
 use super::*;
-use crate::daemon::state_helper::save_state;
+use crate::daemon::state_helper::{save_state, LockedState};
 use crate::ok_or_return_failure_message;
 
 /// Invoked when calling `pueue add`.
@@ -18,8 +20,190 @@ pub fn add_task(
     settings: &Settings,
 ) -> Message {
     let mut state = state.lock().unwrap();
-    if let Err(message) = ensure_group_exists(&mut state, &message.group) {
-        return message;
+
+    let start_immediately = message.start_immediately;
+    let print_task_id = message.print_task_id;
+    let enqueue_at = message.enqueue_at;
+
+    let task = match build_task(message, &mut state, settings) {
+        Ok(NewTask::Task(task)) => *task,
+        // Don't add the task twice, if a task with the same idempotency key already exists.
+        Ok(NewTask::Existing(task_id)) => {
+            let response = if print_task_id {
+                task_id.to_string()
+            } else {
+                format!("Task with this idempotency key already exists (id {task_id}).")
+            };
+            return create_success_message(response);
+        }
+        Err(message) => return message,
+    };
+
+    // Check if the task's group is paused before we pass it to the state
+    let group_status = state
+        .groups
+        .get(&task.group)
+        .expect("We ensured that the group exists.")
+        .status;
+    let group_is_paused = matches!(group_status, GroupStatus::Paused);
+
+    // Add the task and persist the state.
+    let task_id = state.add_task(task);
+    ok_or_return_failure_message!(save_state(&state, settings));
+
+    // Notify the task handler, in case the client wants to start the task immediately.
+    if start_immediately {
+        sender
+            .send(StartMessage {
+                tasks: TaskSelection::TaskIds(vec![task_id]),
+            })
+            .expect(SENDER_ERR);
+    }
+
+    // Create the customized response for the client.
+    let mut response = if print_task_id {
+        task_id.to_string()
+    } else if let Some(enqueue_at) = enqueue_at {
+        let enqueue_at = enqueue_at.format("%Y-%m-%d %H:%M:%S");
+        format!("New task added (id {task_id}). It will be enqueued at {enqueue_at}")
+    } else {
+        format!("New task added (id {task_id}).")
+    };
+
+    // Notify the user if the task's group is paused
+    if !print_task_id && group_is_paused {
+        response.push_str("\nThe group of this task is currently paused!")
+    }
+
+    create_success_message(response)
+}
+
+/// Invoked when calling `pueue add --from-file`.
+/// Queues several tasks to the state at once.
+/// All tasks are validated before the first one is added, so either all or none are added.
+pub fn add_tasks(
+    messages: Vec<AddMessage>,
+    sender: &TaskSender,
+    state: &SharedState,
+    settings: &Settings,
+) -> Message {
+    let mut state = state.lock().unwrap();
+    let print_task_id = messages.iter().any(|message| message.print_task_id);
+
+    // The tasks of a batch haven't been added yet, so their keys have to be unique among each other.
+    let mut keys = HashSet::new();
+    for key in messages
+        .iter()
+        .filter_map(|message| message.idempotency_key.as_ref())
+    {
+        if !keys.insert(key) {
+            return create_failure_message(format!(
+                "The idempotency key \"{key}\" is used by multiple tasks."
+            ));
+        }
+    }
+
+    let mut tasks = Vec::new();
+    for message in messages {
+        match build_task(message, &mut state, settings) {
+            Ok(task) => tasks.push(task),
+            Err(message) => return message,
+        }
+    }
+
+    let mut task_ids = Vec::new();
+    let mut added_ids = Vec::new();
+    let mut start_ids = Vec::new();
+    for task in tasks {
+        let task = match task {
+            NewTask::Task(task) => *task,
+            NewTask::Existing(task_id) => {
+                task_ids.push(task_id);
+                continue;
+            }
+        };
+        let start_immediately = task.immediate;
+        let task_id = state.add_task(task);
+        task_ids.push(task_id);
+        added_ids.push(task_id);
+        if start_immediately {
+            start_ids.push(task_id);
+        }
+    }
+    ok_or_return_failure_message!(save_state(&state, settings));
+
+    // Notify the task handler, in case the client wants to start the tasks immediately.
+    if !start_ids.is_empty() {
+        sender
+            .send(StartMessage {
+                tasks: TaskSelection::TaskIds(start_ids),
+            })
+            .expect(SENDER_ERR);
+    }
+
+    if print_task_id {
+        let ids: Vec<String> = task_ids.iter().map(ToString::to_string).collect();
+        return create_success_message(ids.join("\n"));
+    }
+
+    let first = added_ids.first().copied().unwrap_or_default();
+    let last = added_ids.last().copied().unwrap_or_default();
+    let mut response = format!("{} new tasks added (ids {first}-{last}).", added_ids.len());
+
+    let existing: Vec<String> = task_ids
+        .iter()
+        .filter(|id| !added_ids.contains(id))
+        .map(ToString::to_string)
+        .collect();
+    if !existing.is_empty() {
+        response.push_str(&format!(
+            "\nTasks with these idempotency keys already exist (ids {}).",
+            existing.join(", ")
+        ));
+    }
+
+    create_success_message(response)
+}
+
+/// The result of validating an [AddMessage].
+enum NewTask {
+    /// A new task, which can be added to the state.
+    Task(Box<Task>),
+    /// The id of an existing task with the same idempotency key.
+    /// No new task should be added in this case.
+    Existing(usize),
+}
+
+/// Validate the given message and create the corresponding task.
+/// The task isn't added to the state yet.
+fn build_task(
+    mut message: AddMessage,
+    state: &mut LockedState,
+    settings: &Settings,
+) -> Result<NewTask, Message> {
+    // Don't add the task twice, if a task with the same idempotency key already exists.
+    if let Some(key) = &message.idempotency_key {
+        let existing = state
+            .tasks
+            .values()
+            .find(|task| task.idempotency_key.as_ref() == Some(key));
+        if let Some(task) = existing {
+            return Ok(NewTask::Existing(task.id));
+        }
+    }
+
+    ensure_group_exists(state, &message.group)?;
+
+    // Use the group's working directory, if no working directory has been specified.
+    if message.path_is_default {
+        if let Some(path) = settings
+            .daemon
+            .group_settings
+            .get(&message.group)
+            .and_then(|group| group.working_directory())
+        {
+            message.path = path;
+        }
     }
 
     // Ensure that specified dependencies actually exist.
@@ -29,11 +213,36 @@ pub fn add_task(
         .filter(|id| !state.tasks.contains_key(id))
         .collect();
     if !not_found.is_empty() {
-        return create_failure_message(format!(
+        return Err(create_failure_message(format!(
             "Unable to setup dependencies : task(s) {not_found:?} not found",
+        )));
+    }
+
+    // A task without weight wouldn't occupy any slot and could be started without limit.
+    if message.weight == 0 {
+        return Err(create_failure_message(
+            "The weight of a task has to be at least 1.",
         ));
     }
 
+    // Only keep the allowed environment variables, if an allowlist is configured.
+    let allowlist = &settings.daemon.env_allowlist;
+    if !allowlist.is_empty() {
+        message.envs.retain(|name, _| allowlist.contains(name));
+    }
+
+    // Inject the group's environment variables, unless the task should run with a clean environment.
+    // Variables of the task take precedence.
+    let group_settings = settings.daemon.group_settings.get(&message.group);
+    if let Some(group) = group_settings.filter(|_| !message.no_inherit_env) {
+        for (name, value) in &group.env {
+            message
+                .envs
+                .entry(name.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
+
     // Create a new task and add it to the state.
     let mut task = Task::new(
         message.command,
@@ -45,6 +254,25 @@ pub fn add_task(
         message.label,
     );
 
+    task.timeout = message.timeout;
+    task.retries_remaining = message.retries;
+    task.retry_delay = message.retry_delay;
+    task.user = message.user;
+    task.priority = message.priority;
+    task.cpu_affinity = message.cpu_affinity;
+    task.nice = message.nice;
+    task.when_output_contains = message.when_output_contains;
+    task.shell = message.shell;
+    task.manual_start = message.manual_start;
+    task.immediate = message.start_immediately;
+    task.only_if_success = message.only_if_success;
+    task.label_color = message.label_color;
+    task.touch_on_done = message.touch_on_done;
+    task.weight = message.weight;
+    task.job_id = message.job_id;
+    task.idempotency_key = message.idempotency_key;
+    task.no_inherit_env = message.no_inherit_env;
+
     // Set the starting status.
     if message.stashed || message.enqueue_at.is_some() {
         task.status = TaskStatus::Stashed {
@@ -61,47 +289,14 @@ pub fn add_task(
     //
     // This allows us to have a debug experience and the user can opt to either show the
     // original command or the expanded command in their `status` view.
-    task.command = insert_alias(settings, task.original_command.clone());
+    task.command = match insert_alias(settings, task.original_command.clone()) {
+        Ok(command) => command,
+        Err(err) => return Err(create_failure_message(err.to_string())),
+    };
 
     // Sort and deduplicate dependency ids.
     task.dependencies.sort_unstable();
     task.dependencies.dedup();
 
-    // Check if the task's group is paused before we pass it to the state
-    let group_status = state
-        .groups
-        .get(&task.group)
-        .expect("We ensured that the group exists.")
-        .status;
-    let group_is_paused = matches!(group_status, GroupStatus::Paused);
-
-    // Add the task and persist the state.
-    let task_id = state.add_task(task);
-    ok_or_return_failure_message!(save_state(&state, settings));
-
-    // Notify the task handler, in case the client wants to start the task immediately.
-    if message.start_immediately {
-        sender
-            .send(StartMessage {
-                tasks: TaskSelection::TaskIds(vec![task_id]),
-            })
-            .expect(SENDER_ERR);
-    }
-
-    // Create the customized response for the client.
-    let mut response = if message.print_task_id {
-        task_id.to_string()
-    } else if let Some(enqueue_at) = message.enqueue_at {
-        let enqueue_at = enqueue_at.format("%Y-%m-%d %H:%M:%S");
-        format!("New task added (id {task_id}). It will be enqueued at {enqueue_at}")
-    } else {
-        format!("New task added (id {task_id}).")
-    };
-
-    // Notify the user if the task's group is paused
-    if !message.print_task_id && group_is_paused {
-        response.push_str("\nThe group of this task is currently paused!")
-    }
-
-    create_success_message(response)
+    Ok(NewTask::Task(Box::new(task)))
 }
@@ -1,10 +1,11 @@
+use pueue_lib::archive::append_to_archive;
 use pueue_lib::log::clean_log_handles;
 use pueue_lib::network::message::*;
 use pueue_lib::state::SharedState;
-use pueue_lib::task::{TaskResult, TaskStatus};
+use pueue_lib::task::Task;
 
 use super::*;
-use crate::daemon::state_helper::{is_task_removable, save_state};
+use crate::daemon::state_helper::save_state;
 use crate::ok_or_return_failure_message;
 
 fn construct_success_clean_message(message: CleanMessage) -> String {
@@ -27,30 +28,20 @@ fn construct_success_clean_message(message: CleanMessage) -> String {
 pub fn clean(message: CleanMessage, state: &SharedState, settings: &Settings) -> Message {
     let mut state = state.lock().unwrap();
 
-    let (matching, _) = state.filter_tasks(|task| matches!(task.status, TaskStatus::Done(_)), None);
+    // The client uses the same selection for `pueue clean --dry-run`.
+    let task_ids = state.cleanable_task_ids(message.successful_only, message.group.as_deref());
 
-    for task_id in &matching {
-        // Ensure the task is removable, i.e. there are no dependant tasks.
-        if !is_task_removable(&state, task_id, &[]) {
-            continue;
-        }
-
-        if message.successful_only || message.group.is_some() {
-            if let Some(task) = state.tasks.get(task_id) {
-                // Check if we should ignore this task, if only successful tasks should be removed.
-                if message.successful_only
-                    && !matches!(task.status, TaskStatus::Done(TaskResult::Success))
-                {
-                    continue;
-                }
+    // Archive the tasks before they're removed, so they can still be inspected via `pueue history`.
+    let tasks: Vec<Task> = task_ids
+        .iter()
+        .filter_map(|task_id| state.tasks.get(task_id).cloned())
+        .collect();
+    ok_or_return_failure_message!(append_to_archive(
+        &settings.shared.pueue_directory(),
+        &tasks
+    ));
 
-                // User's can specify a specific group to be cleaned.
-                // Skip the task if that's the case and the task's group doesn't match.
-                if message.group.is_some() && message.group.as_deref() != Some(&task.group) {
-                    continue;
-                }
-            }
-        }
+    for task_id in &task_ids {
         let _ = state.tasks.remove(task_id).unwrap();
         clean_log_handles(*task_id, &settings.shared.pueue_directory());
     }
@@ -161,7 +152,7 @@ mod tests {
         // Assert that only the first entry has been deleted (TaskResult::Success)
         let state = state.lock().unwrap();
         assert_eq!(state.tasks.len(), 5);
-        assert!(state.tasks.get(&0).is_none());
+        assert!(!state.tasks.contains_key(&0));
     }
 
     #[test]
@@ -207,6 +198,6 @@ mod tests {
         // Assert that only the first entry has been deleted from the 'other' group (TaskResult::Success)
         let state = state.lock().unwrap();
         assert_eq!(state.tasks.len(), 11);
-        assert!(state.tasks.get(&6).is_none());
+        assert!(!state.tasks.contains_key(&6));
     }
 }
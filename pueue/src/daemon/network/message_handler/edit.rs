@@ -1,10 +1,12 @@
+use std::collections::HashSet;
+
 use pueue_lib::aliasing::insert_alias;
 use pueue_lib::network::message::*;
 use pueue_lib::state::SharedState;
 use pueue_lib::task::TaskStatus;
 
 use super::*;
-use crate::daemon::state_helper::save_state;
+use crate::daemon::state_helper::{save_state, LockedState};
 use crate::ok_or_return_failure_message;
 
 /// Invoked when calling `pueue edit`.
@@ -26,6 +28,7 @@ pub fn edit_request(task_id: usize, state: &SharedState) -> Message {
                 command: task.original_command.clone(),
                 path: task.path.clone(),
                 label: task.label.clone(),
+                dependencies: task.dependencies.clone(),
             }
             .into()
         }
@@ -38,6 +41,34 @@ pub fn edit_request(task_id: usize, state: &SharedState) -> Message {
 pub fn edit(message: EditMessage, state: &SharedState, settings: &Settings) -> Message {
     // Check whether the task exists and is locked. Abort if that's not the case.
     let mut state = state.lock().unwrap();
+
+    // Ensure that the new dependencies actually exist and that the task doesn't depend on itself.
+    let dependency_error = message.dependencies.as_ref().and_then(|dependencies| {
+        if dependencies.contains(&message.task_id) {
+            return Some("A task cannot depend on itself".to_string());
+        }
+        let not_found: Vec<_> = dependencies
+            .iter()
+            .filter(|id| !state.tasks.contains_key(id))
+            .collect();
+        if !not_found.is_empty() {
+            return Some(format!(
+                "Unable to setup dependencies : task(s) {not_found:?} not found"
+            ));
+        }
+        // Tasks that depend on each other would wait for each other forever.
+        let cyclic: Vec<_> = dependencies
+            .iter()
+            .filter(|id| depends_on(&state, **id, message.task_id))
+            .collect();
+        if !cyclic.is_empty() {
+            return Some(format!(
+                "Unable to setup dependencies : task(s) {cyclic:?} already depend on this task"
+            ));
+        }
+        None
+    });
+
     match state.tasks.get_mut(&message.task_id) {
         Some(task) => {
             if !(task.status == TaskStatus::Locked) {
@@ -47,10 +78,18 @@ pub fn edit(message: EditMessage, state: &SharedState, settings: &Settings) -> M
             // Restore the task to its previous state.
             task.status = task.prev_status.clone();
 
+            // Abort without any changes, if the new dependencies are invalid.
+            if let Some(error) = dependency_error {
+                return create_failure_message(error);
+            }
+
             // Update command if applicable.
             if let Some(command) = message.command {
-                task.original_command = command.clone();
-                task.command = insert_alias(settings, command);
+                task.command = match insert_alias(settings, command.clone()) {
+                    Ok(expanded) => expanded,
+                    Err(err) => return create_failure_message(err.to_string()),
+                };
+                task.original_command = command;
             }
             // Update path if applicable.
             if let Some(path) = message.path {
@@ -62,6 +101,12 @@ pub fn edit(message: EditMessage, state: &SharedState, settings: &Settings) -> M
             } else if message.delete_label {
                 task.label = None;
             }
+            // Update dependencies if applicable.
+            if let Some(mut dependencies) = message.dependencies {
+                dependencies.sort_unstable();
+                dependencies.dedup();
+                task.dependencies = dependencies;
+            }
 
             ok_or_return_failure_message!(save_state(&state, settings));
 
@@ -90,3 +135,22 @@ pub fn edit_restore(task_id: usize, state: &SharedState) -> Message {
         None => create_failure_message("No task with this id."),
     }
 }
+
+/// Check whether the task `from` directly or transitively depends on the task `target`.
+fn depends_on(state: &LockedState, from: usize, target: usize) -> bool {
+    let mut visited = HashSet::new();
+    let mut to_visit = vec![from];
+    while let Some(id) = to_visit.pop() {
+        if id == target {
+            return true;
+        }
+        if !visited.insert(id) {
+            continue;
+        }
+        if let Some(task) = state.tasks.get(&id) {
+            to_visit.extend(task.dependencies.iter().copied());
+        }
+    }
+
+    false
+}
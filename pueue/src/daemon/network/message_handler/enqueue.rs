@@ -5,11 +5,11 @@ use pueue_lib::task::TaskStatus;
 
 use crate::daemon::network::response_helper::*;
 
-/// Invoked when calling `pueue enqueue`.
-/// Enqueue specific stashed tasks.
+/// Invoked when calling `pueue enqueue` or `pueue start --stashed`.
+/// Enqueue specific stashed tasks or all stashed tasks of a group.
 pub fn enqueue(message: EnqueueMessage, state: &SharedState) -> Message {
     let mut state = state.lock().unwrap();
-    let (matching, mismatching) = {
+    let (mut matching, mismatching) = {
         let (matching, mismatching) = state.filter_tasks(
             |task| matches!(task.status, TaskStatus::Stashed { .. } | TaskStatus::Locked),
             Some(message.task_ids),
@@ -18,6 +18,26 @@ pub fn enqueue(message: EnqueueMessage, state: &SharedState) -> Message {
         (matching, mismatching)
     };
 
+    // Add all stashed tasks of the given group.
+    if let Some(group) = &message.group {
+        if let Err(message) = ensure_group_exists(&mut state, group) {
+            return message;
+        }
+
+        let (stashed, _) = state.filter_tasks_of_group(
+            |task| matches!(task.status, TaskStatus::Stashed { .. }),
+            group,
+        );
+        if stashed.is_empty() && matching.is_empty() {
+            return create_success_message(format!(
+                "There are no stashed tasks in group \"{group}\""
+            ));
+        }
+        matching.extend(stashed);
+        matching.sort_unstable();
+        matching.dedup();
+    }
+
     for task_id in &matching {
         // We just checked that they're there and the state is locked. It's safe to unwrap.
         let task = state.tasks.get_mut(task_id).expect("Task should be there.");
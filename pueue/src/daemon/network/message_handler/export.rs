@@ -0,0 +1,9 @@
+use pueue_lib::network::message::*;
+use pueue_lib::state::SharedState;
+
+/// Invoked when calling `pueue export`.
+/// Return the full current state, so the client can save it as a backup.
+pub fn export(state: &SharedState) -> Message {
+    let state = state.lock().unwrap().clone();
+    Message::ExportResponse(Box::new(state))
+}
@@ -1,4 +1,5 @@
 use pueue_lib::network::message::*;
+use pueue_lib::settings::Settings;
 use pueue_lib::state::{SharedState, PUEUE_DEFAULT_GROUP};
 
 use super::TaskSender;
@@ -9,9 +10,15 @@ use crate::ok_or_return_failure_message;
 /// Invoked on `pueue groups`.
 /// Manage groups.
 /// - Show groups
-/// - Add group
+/// - Add group or update the parallel limit of an existing group
 /// - Remove group
-pub fn group(message: GroupMessage, sender: &TaskSender, state: &SharedState) -> Message {
+/// - Rename group
+pub fn group(
+    message: GroupMessage,
+    sender: &TaskSender,
+    state: &SharedState,
+    settings: &Settings,
+) -> Message {
     let mut state = state.lock().unwrap();
 
     match message {
@@ -26,8 +33,17 @@ pub fn group(message: GroupMessage, sender: &TaskSender, state: &SharedState) ->
             name,
             parallel_tasks,
         } => {
-            if state.groups.contains_key(&name) {
-                return create_failure_message(format!("Group \"{name}\" already exists"));
+            if let Some(group) = state.groups.get_mut(&name) {
+                // Adding an existing group with a parallel limit only updates its limit.
+                let Some(parallel_tasks) = parallel_tasks else {
+                    return create_failure_message(format!("Group \"{name}\" already exists"));
+                };
+                let previous = group.parallel_tasks;
+                group.parallel_tasks = parallel_tasks;
+
+                return create_success_message(format!(
+                    "Parallel tasks setting for group \"{name}\" adjusted from {previous} to {parallel_tasks}"
+                ));
             }
 
             // Propagate the message to the TaskHandler, which is responsible for actually
@@ -63,5 +79,37 @@ pub fn group(message: GroupMessage, sender: &TaskSender, state: &SharedState) ->
 
             create_success_message(format!("Group \"{group}\" is being removed"))
         }
+        GroupMessage::Rename { name, new_name } => {
+            if let Err(message) = ensure_group_exists(&mut state, &name) {
+                return message;
+            }
+
+            if name == PUEUE_DEFAULT_GROUP {
+                return create_failure_message("You cannot rename the default group");
+            }
+
+            if state.groups.contains_key(&new_name) {
+                return create_failure_message(format!("Group \"{new_name}\" already exists"));
+            }
+
+            // The group settings live in the configuration file and cannot be moved to the new name.
+            if settings.daemon.group_settings.contains_key(&name) {
+                return create_failure_message(format!(
+                    "Group \"{name}\" is configured via the group_settings and cannot be renamed"
+                ));
+            }
+
+            // Propagate the message to the TaskHandler, which is responsible for actually
+            // manipulating our internal data, as the worker pool has to be moved as well.
+            let result = sender.send(GroupMessage::Rename {
+                name: name.clone(),
+                new_name: new_name.clone(),
+            });
+            ok_or_return_failure_message!(result);
+
+            create_success_message(format!(
+                "Group \"{name}\" is being renamed to \"{new_name}\""
+            ))
+        }
     }
 }
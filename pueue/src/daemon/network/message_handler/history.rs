@@ -0,0 +1,33 @@
+use pueue_lib::archive::read_archive;
+use pueue_lib::network::message::*;
+use pueue_lib::task::{TaskResult, TaskStatus};
+
+use super::*;
+use crate::ok_or_return_failure_message;
+
+/// Invoked when calling `pueue history`.
+/// Return all archived tasks that match the given filters.
+pub fn history(message: HistoryMessage, settings: &Settings) -> Message {
+    let tasks = ok_or_return_failure_message!(read_archive(&settings.shared.pueue_directory()));
+
+    let tasks = tasks
+        .into_iter()
+        .filter(|task| match message.since {
+            Some(since) => task.end.unwrap_or(task.created_at) >= since,
+            None => true,
+        })
+        .filter(|task| match &message.group {
+            Some(group) => &task.group == group,
+            None => true,
+        })
+        .filter(|task| match message.status {
+            Some(HistoryStatus::Success) => {
+                matches!(task.status, TaskStatus::Done(TaskResult::Success))
+            }
+            Some(HistoryStatus::Failed) => task.failed(),
+            None => true,
+        })
+        .collect();
+
+    Message::HistoryResponse(tasks)
+}
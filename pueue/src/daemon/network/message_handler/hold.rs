@@ -0,0 +1,48 @@
+use pueue_lib::network::message::*;
+use pueue_lib::state::SharedState;
+use pueue_lib::task::TaskStatus;
+
+use crate::daemon::network::response_helper::*;
+
+/// Invoked when calling `pueue hold`.
+/// Hold specific queued tasks.
+/// They won't be started by the scheduler, but keep their position in the queue.
+pub fn hold(task_ids: Vec<usize>, state: &SharedState) -> Message {
+    let (matching, mismatching) = {
+        let mut state = state.lock().unwrap();
+        let (matching, mismatching) =
+            state.filter_tasks(|task| task.status == TaskStatus::Queued, Some(task_ids));
+
+        for task_id in &matching {
+            if let Some(task) = state.tasks.get_mut(task_id) {
+                task.held = true;
+            }
+        }
+
+        (matching, mismatching)
+    };
+
+    compile_task_response("Tasks are held", matching, mismatching)
+}
+
+/// Invoked when calling `pueue release`.
+/// Release specific held tasks, so they're started once it's their turn.
+pub fn release(task_ids: Vec<usize>, state: &SharedState) -> Message {
+    let (matching, mismatching) = {
+        let mut state = state.lock().unwrap();
+        let (matching, mismatching) = state.filter_tasks(
+            |task| task.status == TaskStatus::Queued && task.held,
+            Some(task_ids),
+        );
+
+        for task_id in &matching {
+            if let Some(task) = state.tasks.get_mut(task_id) {
+                task.held = false;
+            }
+        }
+
+        (matching, mismatching)
+    };
+
+    compile_task_response("Tasks are released", matching, mismatching)
+}
@@ -0,0 +1,71 @@
+use std::collections::HashMap;
+
+use chrono::Local;
+use pueue_lib::network::message::*;
+use pueue_lib::state::{SharedState, State};
+use pueue_lib::task::TaskStatus;
+
+use super::*;
+use crate::daemon::state_helper::save_state;
+use crate::ok_or_return_failure_message;
+
+/// Invoked when calling `pueue import`.
+/// Add all tasks of a previously exported state as new tasks.
+///
+/// - Groups that don't exist yet are created with their exported parallel limit.
+/// - Tasks get new ids and their dependencies are remapped to those new ids.
+/// - Tasks that were running or paused during the export are queued again.
+pub fn import(imported: State, state: &SharedState, settings: &Settings) -> Message {
+    let mut state = state.lock().unwrap();
+
+    // Create the groups right away, as the imported tasks are added to them below.
+    // The TaskHandler creates the worker pools for these groups, before it schedules any tasks.
+    for (name, group) in imported.groups {
+        if state.groups.contains_key(&name) {
+            continue;
+        }
+
+        state.create_group(&name).parallel_tasks = group.parallel_tasks;
+    }
+
+    // Add all tasks and remember which new id belongs to which exported id.
+    let mut id_mapping = HashMap::new();
+    for (old_id, mut task) in imported.tasks {
+        match task.status {
+            TaskStatus::Running | TaskStatus::Paused | TaskStatus::Locked => {
+                task.status = TaskStatus::Queued;
+                task.enqueued_at = Some(Local::now());
+                task.start = None;
+                task.end = None;
+            }
+            _ => (),
+        }
+
+        let new_id = state.add_task(task);
+        id_mapping.insert(old_id, new_id);
+    }
+
+    // Point all dependencies to the new ids.
+    // Dependencies that weren't part of the export are dropped.
+    for new_id in id_mapping.values() {
+        let task = state.tasks.get_mut(new_id).unwrap();
+        task.dependencies = task
+            .dependencies
+            .iter()
+            .filter_map(|old_id| id_mapping.get(old_id).copied())
+            .collect();
+        task.dependencies.sort_unstable();
+    }
+
+    ok_or_return_failure_message!(save_state(&state, settings));
+
+    let mut new_ids: Vec<usize> = id_mapping.into_values().collect();
+    new_ids.sort_unstable();
+    match (new_ids.first(), new_ids.last()) {
+        (Some(first), Some(last)) => create_success_message(format!(
+            "Imported {} tasks (ids {first}-{last}).",
+            new_ids.len()
+        )),
+        _ => create_success_message("The export doesn't contain any tasks."),
+    }
+}
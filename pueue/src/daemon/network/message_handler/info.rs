@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use chrono::{DateTime, Local};
+use clap::crate_version;
+
+use pueue_lib::network::message::*;
+use pueue_lib::state::SharedState;
+
+/// Information about the daemon process, which is determined once on startup.
+#[derive(Clone, Debug)]
+pub struct DaemonMetadata {
+    pub start: DateTime<Local>,
+    pub config_path: Option<PathBuf>,
+    pub id_prefix: Option<String>,
+}
+
+/// Invoked when calling `pueue info`.
+/// Return some information about the daemon process.
+pub fn info(metadata: &DaemonMetadata, state: &SharedState) -> Message {
+    let state = state.lock().unwrap();
+    let running_tasks = state
+        .tasks
+        .values()
+        .filter(|task| task.is_running())
+        .count();
+
+    InfoResponseMessage {
+        version: crate_version!().to_string(),
+        start: metadata.start,
+        pid: std::process::id(),
+        running_tasks,
+        config_path: metadata.config_path.clone(),
+        id_prefix: metadata.id_prefix.clone(),
+    }
+    .into()
+}
@@ -1,9 +1,16 @@
 use pueue_lib::network::message::*;
 use pueue_lib::state::SharedState;
+use pueue_lib::task::{Task, TaskStatus};
 
 use super::{TaskSender, SENDER_ERR};
 use crate::daemon::network::response_helper::{ensure_group_exists, task_action_response_helper};
 
+/// Whether the given task is one of the selected tasks, that haven't been started yet and
+/// should be skipped.
+fn skip_unstarted(message: &KillMessage, task: &Task) -> bool {
+    message.skip_unstarted && matches!(task.status, TaskStatus::Queued | TaskStatus::Stashed { .. })
+}
+
 /// Invoked when calling `pueue kill`.
 /// Forward the kill message to the task handler, which then kills the process.
 pub fn kill(message: KillMessage, sender: &TaskSender, state: &SharedState) -> Message {
@@ -16,13 +23,37 @@ pub fn kill(message: KillMessage, sender: &TaskSender, state: &SharedState) -> M
         }
     }
 
+    // Tasks are only re-queued once they're finished, which never happens for these signals.
+    if let Some(signal) = message.signal.as_ref().filter(|_| message.retry) {
+        if !signal.is_terminating() {
+            return create_failure_message(format!(
+                "Tasks cannot be retried after sending {signal}, as it doesn't terminate them."
+            ));
+        }
+    }
+
     // Construct a response depending on the selected tasks.
-    let response = if let Some(signal) = &message.signal {
+    let response = if message.retry {
+        match &message.tasks {
+            TaskSelection::TaskIds(task_ids) => task_action_response_helper(
+                "Tasks are being killed and will be re-queued",
+                task_ids.clone(),
+                |task| task.is_running() || skip_unstarted(&message, task),
+                &state,
+            ),
+            TaskSelection::Group(group) => create_success_message(format!(
+                "All tasks of group \"{group}\" are being killed and will be re-queued."
+            )),
+            TaskSelection::All => {
+                create_success_message("All tasks are being killed and will be re-queued.")
+            }
+        }
+    } else if let Some(signal) = &message.signal {
         match &message.tasks {
             TaskSelection::TaskIds(task_ids) => task_action_response_helper(
                 "Tasks are being killed",
                 task_ids.clone(),
-                |task| task.is_running(),
+                |task| task.is_running() || skip_unstarted(&message, task),
                 &state,
             ),
             TaskSelection::Group(group) => create_success_message(format!(
@@ -37,7 +68,7 @@ pub fn kill(message: KillMessage, sender: &TaskSender, state: &SharedState) -> M
             TaskSelection::TaskIds(task_ids) => task_action_response_helper(
                 "Tasks are being killed",
                 task_ids.clone(),
-                |task| task.is_running(),
+                |task| task.is_running() || skip_unstarted(&message, task),
                 &state,
             ),
             TaskSelection::Group(group) => create_success_message(format!(
@@ -1,6 +1,7 @@
 use std::collections::BTreeMap;
 
-use pueue_lib::log::read_and_compress_log_file;
+use pueue_lib::archive::read_archive;
+use pueue_lib::log::{get_log_archive_path, get_log_path, read_and_compress_log_file};
 use pueue_lib::network::message::*;
 use pueue_lib::settings::Settings;
 use pueue_lib::state::SharedState;
@@ -8,6 +9,10 @@ use pueue_lib::state::SharedState;
 /// Invoked when calling `pueue log`.
 /// Return tasks and their output to the client.
 pub fn get_log(message: LogRequestMessage, state: &SharedState, settings: &Settings) -> Message {
+    if message.archived {
+        return get_archived_log(message, settings);
+    }
+
     let state = { state.lock().unwrap().clone() };
     // Return all logs, if no specific task id is specified.
     let task_ids = if message.task_ids.is_empty() {
@@ -27,6 +32,8 @@ pub fn get_log(message: LogRequestMessage, state: &SharedState, settings: &Setti
                     *task_id,
                     &settings.shared.pueue_directory(),
                     message.lines,
+                    message.offset,
+                    message.stderr,
                 ) {
                     Ok((output, output_complete)) => (Some(output), output_complete),
                     Err(err) => {
@@ -50,3 +57,55 @@ pub fn get_log(message: LogRequestMessage, state: &SharedState, settings: &Setti
     }
     Message::LogResponse(tasks)
 }
+
+/// Invoked when calling `pueue log --archived`.
+/// Return archived tasks and the output that has been kept via `pueue remove --keep-logs`.
+fn get_archived_log(message: LogRequestMessage, settings: &Settings) -> Message {
+    let pueue_directory = settings.shared.pueue_directory();
+    let archived_tasks = match read_archive(&pueue_directory) {
+        Ok(tasks) => tasks,
+        Err(err) => return create_failure_message(format!("Failed to read archive: {err}")),
+    };
+
+    let mut tasks = BTreeMap::new();
+    // Task ids can be reused, which is why later entries of the archive take precedence.
+    for task in archived_tasks.into_iter().rev() {
+        let archive_path = get_log_archive_path(&task, &pueue_directory);
+        if tasks.contains_key(&task.id)
+            || !get_log_path(task.id, &archive_path).exists()
+            || (!message.task_ids.is_empty() && !message.task_ids.contains(&task.id))
+        {
+            continue;
+        }
+
+        let (output, output_complete) = match read_and_compress_log_file(
+            task.id,
+            &archive_path,
+            message.lines,
+            message.offset,
+            message.stderr,
+        ) {
+            Ok((output, output_complete)) => (Some(output), output_complete),
+            Err(err) => {
+                return create_failure_message(format!(
+                    "Failed reading archived process output file: {err:?}"
+                ));
+            }
+        };
+
+        tasks.insert(
+            task.id,
+            TaskLogMessage {
+                task,
+                output,
+                output_complete,
+            },
+        );
+    }
+
+    if tasks.is_empty() {
+        return create_failure_message("No archived logs found.");
+    }
+
+    Message::LogResponse(tasks)
+}
@@ -11,18 +11,27 @@ mod add;
 mod clean;
 mod edit;
 mod enqueue;
+mod export;
 mod group;
+mod history;
+mod hold;
+mod import;
+mod info;
 mod kill;
 mod log;
+mod note;
 mod parallel;
 mod pause;
 mod remove;
+mod reorder;
 mod restart;
 mod send;
 mod start;
 mod stash;
 mod switch;
 
+pub use info::{info, DaemonMetadata};
+
 pub static SENDER_ERR: &str = "Failed to send message to task handler thread";
 
 pub fn handle_message(
@@ -32,25 +41,35 @@ pub fn handle_message(
     settings: &Settings,
 ) -> Message {
     match message {
-        Message::Add(message) => add::add_task(message, sender, state, settings),
+        Message::Add(message) => add::add_task(*message, sender, state, settings),
+        Message::AddBatch(messages) => add::add_tasks(messages, sender, state, settings),
         Message::Clean(message) => clean::clean(message, state, settings),
         Message::Edit(message) => edit::edit(message, state, settings),
         Message::EditRequest(task_id) => edit::edit_request(task_id, state),
         Message::EditRestore(task_id) => edit::edit_restore(task_id, state),
         Message::Enqueue(message) => enqueue::enqueue(message, state),
-        Message::Group(message) => group::group(message, sender, state),
+        Message::Export => export::export(state),
+        Message::Group(message) => group::group(message, sender, state, settings),
+        Message::History(message) => history::history(message, settings),
+        Message::Hold(task_ids) => hold::hold(task_ids, state),
+        Message::Import(imported) => import::import(*imported, state, settings),
         Message::Kill(message) => kill::kill(message, sender, state),
         Message::Log(message) => log::get_log(message, state, settings),
         Message::Parallel(message) => parallel::set_parallel_tasks(message, state),
         Message::Pause(message) => pause::pause(message, sender, state),
-        Message::Remove(task_ids) => remove::remove(task_ids, state, settings),
-        Message::Reset(message) => reset(message, sender),
+        Message::Release(task_ids) => hold::release(task_ids, state),
+        Message::Remove(task_ids) => remove::remove(task_ids, false, state, settings),
+        Message::RemoveKeepLogs(task_ids) => remove::remove(task_ids, true, state, settings),
+        Message::Reorder(message) => reorder::reorder(message, state, settings),
+        Message::Reset(message) => reset(message, sender, state),
         Message::Restart(message) => restart::restart_multiple(message, sender, state, settings),
         Message::Send(message) => send::send(message, sender, state),
         Message::Start(message) => start::start(message, sender, state),
         Message::Stash(task_ids) => stash::stash(task_ids, state),
         Message::Switch(message) => switch::switch(message, state, settings),
-        Message::Status => get_status(state),
+        Message::Note(message) => note::note(message, state, settings),
+        Message::Status | Message::FreshStatus => get_status(state),
+        Message::TaskRequest(message) => get_task(message, state),
         _ => create_failure_message("Not yet implemented"),
     }
 }
@@ -58,9 +77,21 @@ pub fn handle_message(
 /// Invoked when calling `pueue reset`.
 /// Forward the reset request to the task handler.
 /// The handler then kills all children and clears the task queue.
-fn reset(message: ResetMessage, sender: &TaskSender) -> Message {
+/// If a group is specified, only the children and tasks of that group are affected.
+fn reset(message: ResetMessage, sender: &TaskSender, state: &SharedState) -> Message {
+    let text = match &message.group {
+        Some(group) => {
+            let mut state = state.lock().unwrap();
+            if let Err(message) = ensure_group_exists(&mut state, group) {
+                return message;
+            }
+            format!("Group \"{group}\" is being reset right now.")
+        }
+        None => "Everything is being reset right now.".to_string(),
+    };
+
     sender.send(message).expect(SENDER_ERR);
-    create_success_message("Everything is being reset right now.")
+    create_success_message(text)
 }
 
 /// Invoked when calling `pueue status`.
@@ -70,6 +101,16 @@ fn get_status(state: &SharedState) -> Message {
     Message::StatusResponse(Box::new(state))
 }
 
+/// Invoked when calling `pueue status <id> --single`.
+/// Return a single task, which avoids cloning and sending the whole state.
+fn get_task(message: TaskRequestMessage, state: &SharedState) -> Message {
+    let state = state.lock().unwrap();
+    match state.tasks.get(&message.id) {
+        Some(task) => Message::TaskResponse(Box::new(task.clone())),
+        None => create_failure_message(format!("There's no task with id {}", message.id)),
+    }
+}
+
 fn ok_or_failure_message<T, E: Display>(result: Result<T, E>) -> Result<T, Message> {
     match result {
         Ok(inner) => Ok(inner),
@@ -93,19 +134,13 @@ macro_rules! ok_or_return_failure_message {
 mod fixtures {
     use std::collections::HashMap;
     use std::env::temp_dir;
-    pub use std::sync::mpsc::Sender;
     use std::sync::{Arc, Mutex};
     use tempfile::TempDir;
 
-    pub use pueue_lib::network::message::*;
-    pub use pueue_lib::network::protocol::socket_cleanup;
     pub use pueue_lib::settings::Settings;
     pub use pueue_lib::state::{SharedState, State, PUEUE_DEFAULT_GROUP};
     pub use pueue_lib::task::{Task, TaskResult, TaskStatus};
 
-    pub use super::*;
-    pub use crate::daemon::network::response_helper::*;
-
     pub fn get_settings() -> (Settings, TempDir) {
         let tempdir = TempDir::new().expect("Failed to create test pueue directory");
         let mut settings = Settings::default();
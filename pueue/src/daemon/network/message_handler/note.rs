@@ -0,0 +1,26 @@
+use pueue_lib::network::message::*;
+use pueue_lib::settings::Settings;
+use pueue_lib::state::SharedState;
+
+use super::ok_or_failure_message;
+use crate::daemon::state_helper::save_state;
+use crate::ok_or_return_failure_message;
+
+/// Invoked when calling `pueue note`.
+/// Set or remove the note of a task. Notes can be changed, regardless of the task's status.
+pub fn note(message: NoteMessage, state: &SharedState, settings: &Settings) -> Message {
+    let mut state = state.lock().unwrap();
+    let Some(task) = state.tasks.get_mut(&message.task_id) else {
+        return create_failure_message("No task with this id.");
+    };
+
+    let response = match message.note {
+        Some(_) => format!("Note of task {} has been set", message.task_id),
+        None => format!("Note of task {} has been removed", message.task_id),
+    };
+    task.note = message.note;
+
+    ok_or_return_failure_message!(save_state(&state, settings));
+
+    create_success_message(response)
+}
@@ -11,10 +11,11 @@ pub fn set_parallel_tasks(message: ParallelMessage, state: &SharedState) -> Mess
         Err(message) => return message,
     };
 
+    let previous = group.parallel_tasks;
     group.parallel_tasks = message.parallel_tasks;
 
     create_success_message(format!(
-        "Parallel tasks setting for group \"{}\" adjusted",
-        &message.group
+        "Parallel tasks setting for group \"{}\" adjusted from {previous} to {}",
+        &message.group, message.parallel_tasks
     ))
 }
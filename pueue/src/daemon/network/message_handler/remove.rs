@@ -1,4 +1,6 @@
-use pueue_lib::log::clean_log_handles;
+use log::error;
+use pueue_lib::archive::append_to_archive;
+use pueue_lib::log::{archive_log_files, clean_log_handles};
 use pueue_lib::network::message::*;
 use pueue_lib::settings::Settings;
 use pueue_lib::state::SharedState;
@@ -12,7 +14,13 @@ use crate::ok_or_return_failure_message;
 /// Invoked when calling `pueue remove`.
 /// Remove tasks from the queue.
 /// We have to ensure that those tasks aren't running!
-pub fn remove(task_ids: Vec<usize>, state: &SharedState, settings: &Settings) -> Message {
+/// If `keep_logs` is set, the tasks and their logs are archived instead of being deleted.
+pub fn remove(
+    task_ids: Vec<usize>,
+    keep_logs: bool,
+    state: &SharedState,
+    settings: &Settings,
+) -> Message {
     let mut state = state.lock().unwrap();
     let filter = |task: &Task| {
         matches!(
@@ -34,10 +42,26 @@ pub fn remove(task_ids: Vec<usize>, state: &SharedState, settings: &Settings) ->
         };
     }
 
+    // Archive the tasks, so their logs can still be inspected via `pueue log --archived`.
+    let pueue_directory = settings.shared.pueue_directory();
+    if keep_logs {
+        let tasks: Vec<Task> = not_running
+            .iter()
+            .filter_map(|task_id| state.tasks.get(task_id).cloned())
+            .collect();
+        ok_or_return_failure_message!(append_to_archive(&pueue_directory, &tasks));
+    }
+
     for task_id in &not_running {
-        state.tasks.remove(task_id);
+        let Some(task) = state.tasks.remove(task_id) else {
+            continue;
+        };
 
-        clean_log_handles(*task_id, &settings.shared.pueue_directory());
+        if !keep_logs {
+            clean_log_handles(*task_id, &pueue_directory);
+        } else if let Err(err) = archive_log_files(&task, &pueue_directory) {
+            error!("Failed to archive logs of task {task_id}: {err}");
+        }
     }
 
     ok_or_return_failure_message!(save_state(&state, settings));
@@ -58,7 +82,7 @@ mod tests {
 
         // 3 and 4 aren't allowed to be removed, since they're running.
         // The rest will succeed.
-        let message = remove(vec![0, 1, 2, 3, 4], &state, &settings);
+        let message = remove(vec![0, 1, 2, 3, 4], false, &state, &settings);
 
         // Return message is correct
         assert!(matches!(message, Message::Success(_)));
@@ -91,7 +115,7 @@ mod tests {
         }
 
         // Make sure we cannot remove a task with dependencies.
-        let message = remove(vec![1], &state, &settings);
+        let message = remove(vec![1], false, &state, &settings);
 
         // Return message is correct
         assert!(matches!(message, Message::Failure(_)));
@@ -105,7 +129,7 @@ mod tests {
         }
 
         // Make sure we cannot remove a task with recursive dependencies.
-        let message = remove(vec![1, 5], &state, &settings);
+        let message = remove(vec![1, 5], false, &state, &settings);
 
         // Return message is correct
         assert!(matches!(message, Message::Failure(_)));
@@ -119,7 +143,7 @@ mod tests {
         }
 
         // Make sure we can remove tasks with dependencies if all dependencies are specified.
-        let message = remove(vec![1, 5, 6], &state, &settings);
+        let message = remove(vec![1, 5, 6], false, &state, &settings);
 
         // Return message is correct
         assert!(matches!(message, Message::Success(_)));
@@ -0,0 +1,118 @@
+use pueue_lib::network::message::*;
+use pueue_lib::settings::Settings;
+use pueue_lib::state::SharedState;
+use pueue_lib::task::TaskStatus;
+
+use super::ok_or_failure_message;
+use crate::daemon::state_helper::save_state;
+use crate::ok_or_return_failure_message;
+
+/// Invoked when calling `pueue move`.
+/// Move a task in front of another task in the upcoming queue.
+/// We have to ensure that those tasks are either `Queued` or `Stashed`
+pub fn reorder(message: ReorderMessage, state: &SharedState, settings: &Settings) -> Message {
+    let mut state = state.lock().unwrap();
+
+    let (_, mismatching) = state.filter_tasks(
+        |task| matches!(task.status, TaskStatus::Queued | TaskStatus::Stashed { .. }),
+        Some(vec![message.task_id, message.before]),
+    );
+    if !mismatching.is_empty() {
+        return create_failure_message("Tasks have to be either queued or stashed.");
+    }
+    if message.task_id == message.before {
+        return create_failure_message("You cannot move a task in front of itself.");
+    }
+
+    // Make room for the moved task by moving the target and all tasks behind it back by one.
+    let target_order = state.tasks.get(&message.before).unwrap().order;
+    for (id, task) in state.tasks.iter_mut() {
+        if *id != message.task_id && task.order >= target_order {
+            task.order += 1;
+        }
+    }
+    state.tasks.get_mut(&message.task_id).unwrap().order = target_order;
+
+    ok_or_return_failure_message!(save_state(&state, settings));
+    create_success_message("Task has been moved")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tempfile::TempDir;
+
+    use super::super::fixtures::*;
+    use super::*;
+
+    fn get_message(task_id: usize, before: usize) -> ReorderMessage {
+        ReorderMessage { task_id, before }
+    }
+
+    fn get_test_state() -> (SharedState, Settings, TempDir) {
+        let (state, settings, tempdir) = get_state();
+
+        {
+            let mut state = state.lock().unwrap();
+            let task = get_stub_task("0", TaskStatus::Running);
+            state.add_task(task);
+
+            let task = get_stub_task("1", TaskStatus::Queued);
+            state.add_task(task);
+
+            let task = get_stub_task("2", TaskStatus::Stashed { enqueue_at: None });
+            state.add_task(task);
+
+            let task = get_stub_task("3", TaskStatus::Queued);
+            state.add_task(task);
+        }
+
+        (state, settings, tempdir)
+    }
+
+    #[test]
+    /// A task can be moved in front of another queued task.
+    fn reorder_normal() {
+        let (state, settings, _tempdir) = get_test_state();
+
+        let message = reorder(get_message(3, 1), &state, &settings);
+
+        // Return message is correct
+        assert!(matches!(message, Message::Success(_)));
+
+        let state = state.lock().unwrap();
+        // Get the ids of all queued and stashed tasks by their position in the queue.
+        let mut queue: Vec<_> = state.tasks.values().filter(|task| task.id != 0).collect();
+        queue.sort_by_key(|task| task.order);
+        let queue: Vec<_> = queue.iter().map(|task| task.id).collect();
+        assert_eq!(queue, vec![3, 1, 2]);
+    }
+
+    #[test]
+    /// Running or finished tasks cannot be moved.
+    fn reorder_running_task() {
+        let (state, settings, _tempdir) = get_test_state();
+
+        let message = reorder(get_message(0, 1), &state, &settings);
+
+        // Return message is correct
+        assert!(matches!(message, Message::Failure(_)));
+        if let Message::Failure(text) = message {
+            assert_eq!(text, "Tasks have to be either queued or stashed.");
+        };
+    }
+
+    #[test]
+    /// Tasks cannot be moved in front of themselves.
+    fn reorder_task_before_itself() {
+        let (state, settings, _tempdir) = get_test_state();
+
+        let message = reorder(get_message(1, 1), &state, &settings);
+
+        // Return message is correct
+        assert!(matches!(message, Message::Failure(_)));
+        if let Message::Failure(text) = message {
+            assert_eq!(text, "You cannot move a task in front of itself.");
+        };
+    }
+}
@@ -19,6 +19,22 @@ pub fn restart_multiple(
     state: &SharedState,
     settings: &Settings,
 ) -> Message {
+    // Don't restart anything, if the tasks would fail to spawn anyway.
+    if let Some(path) = &message.working_directory {
+        if !path.is_dir() {
+            return create_failure_message(format!(
+                "The working directory {path:?} doesn't exist."
+            ));
+        }
+    }
+
+    // Don't restart anything, if one of the new commands cannot be expanded.
+    for command in message.tasks.iter().filter_map(|task| task.command.clone()) {
+        if let Err(err) = insert_alias(settings, command) {
+            return create_failure_message(err.to_string());
+        }
+    }
+
     let task_ids: Vec<usize> = message.tasks.iter().map(|task| task.task_id).collect();
     let mut state = state.lock().unwrap();
 
@@ -32,7 +48,10 @@ pub fn restart_multiple(
     );
 
     // Actually restart all tasks
-    for task in message.tasks.into_iter() {
+    for mut task in message.tasks.into_iter() {
+        if message.working_directory.is_some() {
+            task.path = message.working_directory.clone();
+        }
         restart(&mut state, task, message.stashed, settings);
     }
 
@@ -70,6 +89,7 @@ fn restart(
     }
 
     // Either enqueue the task or stash it.
+    task.stashed_by_age = false;
     if stashed {
         task.status = TaskStatus::Stashed { enqueue_at: None };
         task.enqueued_at = None;
@@ -81,7 +101,8 @@ fn restart(
     // Update command if applicable.
     if let Some(new_command) = to_restart.command {
         task.original_command = new_command.clone();
-        task.command = insert_alias(settings, new_command);
+        // The command has already been checked in `restart_multiple`.
+        task.command = insert_alias(settings, new_command.clone()).unwrap_or(new_command);
     }
 
     // Update path if applicable.
@@ -99,4 +120,5 @@ fn restart(
     // Reset all variables of any previous run.
     task.start = None;
     task.end = None;
+    task.immediate = false;
 }
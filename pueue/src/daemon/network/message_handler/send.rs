@@ -3,26 +3,36 @@ use pueue_lib::state::SharedState;
 use pueue_lib::task::TaskStatus;
 
 use super::{TaskSender, SENDER_ERR};
+use crate::daemon::network::response_helper::*;
 
 /// Invoked when calling `pueue send`.
-/// The message will be forwarded to the task handler, which then sends the user input to the process.
+/// The message will be forwarded to the task handler, which then sends the user input to the processes.
 /// In here we only do some error handling.
+/// Tasks that don't exist or aren't running are skipped.
 pub fn send(message: SendMessage, sender: &TaskSender, state: &SharedState) -> Message {
-    // Check whether the task exists and is running. Abort if that's not the case.
-    {
+    // Check which of the tasks exist and are running.
+    let (matching, mismatching) = {
         let state = state.lock().unwrap();
-        match state.tasks.get(&message.task_id) {
-            Some(task) => {
-                if task.status != TaskStatus::Running {
-                    return create_failure_message("You can only send input to a running task");
-                }
-            }
-            None => return create_failure_message("No task with this id."),
-        }
+        state.filter_tasks(
+            |task| task.status == TaskStatus::Running,
+            Some(message.task_ids),
+        )
+    };
+
+    if matching.is_empty() {
+        return create_failure_message("You can only send input to a running task");
     }
 
-    // Check whether the task exists and is running, abort if that's not the case.
-    sender.send(message).expect(SENDER_ERR);
+    sender
+        .send(SendMessage {
+            task_ids: matching.clone(),
+            input: message.input,
+        })
+        .expect(SENDER_ERR);
 
-    create_success_message("Message is being send to the process.")
+    compile_task_response(
+        "Message is being send to the processes of tasks",
+        matching,
+        mismatching,
+    )
 }
@@ -19,6 +19,7 @@ pub fn stash(task_ids: Vec<usize>, state: &SharedState) -> Message {
             if let Some(ref mut task) = state.tasks.get_mut(task_id) {
                 task.status = TaskStatus::Stashed { enqueue_at: None };
                 task.enqueued_at = None;
+                task.stashed_by_age = false;
             }
         }
 
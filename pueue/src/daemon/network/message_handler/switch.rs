@@ -13,7 +13,7 @@ use crate::ok_or_return_failure_message;
 pub fn switch(message: SwitchMessage, state: &SharedState, settings: &Settings) -> Message {
     let mut state = state.lock().unwrap();
 
-    let task_ids = vec![message.task_id_1, message.task_id_2];
+    let task_ids = [message.task_id_1, message.task_id_2];
     let (_, mismatching) = state.filter_tasks(
         |task| matches!(task.status, TaskStatus::Queued | TaskStatus::Stashed { .. }),
         Some(task_ids.to_vec()),
@@ -34,6 +34,8 @@ pub fn switch(message: SwitchMessage, state: &SharedState, settings: &Settings)
     let second_id = second_task.id;
     first_task.id = second_id;
     second_task.id = first_id;
+    // Switch the queue positions as well.
+    std::mem::swap(&mut first_task.order, &mut second_task.order);
 
     // Put tasks back in again
     state.tasks.insert(first_task.id, first_task);
@@ -2,5 +2,6 @@ pub mod follow_log;
 pub mod message_handler;
 pub mod response_helper;
 pub mod socket;
+pub mod status_cache;
 
 use super::TaskSender;
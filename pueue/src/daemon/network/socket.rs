@@ -1,3 +1,5 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use anyhow::{bail, Context, Result};
@@ -8,24 +10,55 @@ use tokio::time::sleep;
 use pueue_lib::error::Error;
 use pueue_lib::network::message::*;
 use pueue_lib::network::protocol::*;
-use pueue_lib::network::secret::read_shared_secret;
+use pueue_lib::network::secret::{read_secret_file, read_shared_secret};
 use pueue_lib::settings::Settings;
 use pueue_lib::state::SharedState;
+use pueue_lib::task::Task;
 
 use crate::daemon::network::follow_log::handle_follow;
-use crate::daemon::network::message_handler::{handle_message, SENDER_ERR};
+use crate::daemon::network::message_handler::{handle_message, info, DaemonMetadata, SENDER_ERR};
+use crate::daemon::network::status_cache::{get_cached_status, StatusCache};
 use crate::daemon::task_handler::TaskSender;
 
+/// Everything a connection handler needs to process the messages of a client.
+#[derive(Clone)]
+struct ConnectionContext {
+    sender: TaskSender,
+    state: SharedState,
+    settings: Settings,
+    secret: Vec<u8>,
+    read_only_secret: Option<Vec<u8>>,
+    metadata: DaemonMetadata,
+    status_cache: StatusCache,
+    /// Whether the daemon is being drained, i.e. it waits for running tasks before shutting down.
+    draining: Arc<AtomicBool>,
+}
+
 /// Poll the listener and accept new incoming connections.
 /// Create a new future to handle the message and spawn it.
 pub async fn accept_incoming(
     sender: TaskSender,
     state: SharedState,
     settings: Settings,
+    metadata: DaemonMetadata,
 ) -> Result<()> {
     let listener = get_listener(&settings.shared).await?;
     // Read secret once to prevent multiple disk reads.
     let secret = read_shared_secret(&settings.shared.shared_secret_path())?;
+    let read_only_secret = match settings.shared.read_only_secret_path() {
+        Some(path) => Some(read_secret_file(&path)?),
+        None => None,
+    };
+    let context = ConnectionContext {
+        sender,
+        state,
+        settings,
+        secret,
+        read_only_secret,
+        metadata,
+        status_cache: StatusCache::default(),
+        draining: Arc::new(AtomicBool::new(false)),
+    };
 
     loop {
         // Poll incoming connections.
@@ -38,19 +71,9 @@ pub async fn accept_incoming(
         };
 
         // Start a new task for the request
-        let sender_clone = sender.clone();
-        let state_clone = state.clone();
-        let secret_clone = secret.clone();
-        let settings_clone = settings.clone();
+        let context_clone = context.clone();
         tokio::spawn(async move {
-            let _result = handle_incoming(
-                stream,
-                sender_clone,
-                state_clone,
-                settings_clone,
-                secret_clone,
-            )
-            .await;
+            let _result = handle_incoming(stream, context_clone).await;
         });
     }
 }
@@ -58,13 +81,18 @@ pub async fn accept_incoming(
 /// Continuously poll the existing incoming futures.
 /// In case we received an instruction, handle it and create a response future.
 /// The response future is added to unix_responses and handled in a separate function.
-async fn handle_incoming(
-    mut stream: GenericStream,
-    sender: TaskSender,
-    state: SharedState,
-    settings: Settings,
-    secret: Vec<u8>,
-) -> Result<()> {
+async fn handle_incoming(mut stream: GenericStream, context: ConnectionContext) -> Result<()> {
+    let ConnectionContext {
+        sender,
+        state,
+        settings,
+        secret,
+        read_only_secret,
+        metadata,
+        status_cache,
+        draining,
+    } = context;
+
     // Receive the secret once and check, whether the client is allowed to connect
     let payload_bytes = receive_bytes(&mut stream).await?;
 
@@ -76,8 +104,11 @@ async fn handle_incoming(
 
     let start = SystemTime::now();
 
+    // Clients that authenticate with the read-only secret may only read the state.
+    let read_only = payload_bytes != secret && read_only_secret.as_ref() == Some(&payload_bytes);
+
     // Return immediately, if we got a wrong secret from the client.
-    if payload_bytes != secret {
+    if payload_bytes != secret && !read_only {
         let received_secret = String::from_utf8(payload_bytes)?;
         warn!("Received invalid secret: {received_secret}");
 
@@ -120,7 +151,38 @@ async fn handle_incoming(
 
         let message = message_result?;
 
-        let response = match message {
+        // Reject any messages that would change the state, if the client only has read access.
+        if read_only && !message.is_read_only() {
+            send_message(
+                create_failure_message("This connection only has read-only access."),
+                &mut stream,
+            )
+            .await?;
+            continue;
+        }
+
+        // Don't spawn any new processes, while the daemon waits for running tasks to finish.
+        if draining.load(Ordering::SeqCst) && starts_tasks(&message) {
+            send_message(
+                create_failure_message("The daemon is shutting down and doesn't start new tasks."),
+                &mut stream,
+            )
+            .await?;
+            continue;
+        }
+
+        // Serve the status from the cache, if caching is enabled.
+        // The cached response is already serialized, which is why it's sent directly.
+        // Read-only clients aren't served from the cache, as their responses are stripped.
+        let cache_ms = settings.daemon.status_cache_ms.filter(|_| !read_only);
+        if let (Message::Status, Some(cache_ms)) = (&message, cache_ms) {
+            let payload =
+                get_cached_status(&state, &status_cache, Duration::from_millis(cache_ms))?;
+            send_bytes(&payload, &mut stream).await?;
+            continue;
+        }
+
+        let mut response = match message {
             // The client requested the output of a task.
             // Since this involves streaming content, we have to do some special handling.
             Message::StreamRequest(message) => {
@@ -134,7 +196,15 @@ async fn handle_incoming(
             // Otherwise it might happen, that the daemon shuts down too fast and we aren't
             // capable of actually sending the message back to the client.
             Message::DaemonShutdown(shutdown_type) => {
-                let response = create_success_message("Daemon is shutting down");
+                let response = match shutdown_type {
+                    Shutdown::Drain { .. } => create_success_message(
+                        "Daemon is shutting down once all running tasks are finished",
+                    ),
+                    _ => create_success_message("Daemon is shutting down"),
+                };
+                if matches!(shutdown_type, Shutdown::Drain { .. }) {
+                    draining.store(true, Ordering::SeqCst);
+                }
                 send_message(response, &mut stream).await?;
 
                 // Notify the task handler.
@@ -142,6 +212,9 @@ async fn handle_incoming(
 
                 return Ok(());
             }
+            // Information about the daemon process isn't part of the state,
+            // which is why this isn't handled by the normal message handlers.
+            Message::Info => info(&metadata, &state),
             _ => {
                 // Process a normal message.
                 handle_message(message, &sender, &state, &settings)
@@ -149,6 +222,37 @@ async fn handle_incoming(
         };
 
         // Respond to the client.
+        if read_only {
+            hide_task_envs(&mut response);
+        }
         send_message(response, &mut stream).await?;
     }
 }
+
+/// Whether the given message would spawn task processes right away.
+fn starts_tasks(message: &Message) -> bool {
+    match message {
+        Message::Start(_) => true,
+        Message::Restart(message) => message.start_immediately,
+        Message::Add(message) => message.start_immediately,
+        Message::AddBatch(messages) => messages.iter().any(|message| message.start_immediately),
+        _ => false,
+    }
+}
+
+/// Remove the environment variables of all tasks in the given response.
+/// They might contain secrets, which must not be readable by read-only clients.
+fn hide_task_envs(response: &mut Message) {
+    let tasks: Vec<&mut Task> = match response {
+        Message::StatusResponse(state) | Message::ExportResponse(state) => {
+            state.tasks.values_mut().collect()
+        }
+        Message::TaskResponse(task) => vec![task.as_mut()],
+        Message::HistoryResponse(tasks) => tasks.iter_mut().collect(),
+        Message::LogResponse(logs) => logs.values_mut().map(|log| &mut log.task).collect(),
+        _ => Vec::new(),
+    };
+    for task in tasks {
+        task.envs.clear();
+    }
+}
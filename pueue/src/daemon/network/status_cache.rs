@@ -0,0 +1,36 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use pueue_lib::error::Error;
+use pueue_lib::network::message::Message;
+use pueue_lib::network::protocol::serialize_message;
+use pueue_lib::state::SharedState;
+
+/// The serialized response to a `status` request and the point in time it has been created.
+/// This is shared between all client connections.
+pub type StatusCache = Arc<Mutex<Option<(Instant, Vec<u8>)>>>;
+
+/// Get the serialized status response from the cache.
+/// The cached payload is rebuilt, once it's older than `max_age`.
+///
+/// The cache is only accessed while the state is locked.
+/// That way, the state cannot change while the payload is rebuilt.
+pub fn get_cached_status(
+    state: &SharedState,
+    cache: &StatusCache,
+    max_age: Duration,
+) -> Result<Vec<u8>, Error> {
+    let state = state.lock().unwrap();
+    let mut cache = cache.lock().unwrap();
+
+    if let Some((created_at, payload)) = cache.as_ref() {
+        if created_at.elapsed() < max_age {
+            return Ok(payload.clone());
+        }
+    }
+
+    let payload = serialize_message(Message::StatusResponse(Box::new(state.clone())))?;
+    *cache = Some((Instant::now(), payload.clone()));
+
+    Ok(payload)
+}
@@ -6,11 +6,11 @@ use std::time::SystemTime;
 
 use anyhow::{Context, Result};
 use chrono::prelude::*;
-use log::{debug, info};
+use log::{debug, error, info};
 
 use pueue_lib::settings::Settings;
 use pueue_lib::state::{Group, GroupStatus, State, PUEUE_DEFAULT_GROUP};
-use pueue_lib::task::{TaskResult, TaskStatus};
+use pueue_lib::task::{Task, TaskResult, TaskStatus};
 
 pub type LockedState<'a> = MutexGuard<'a, State>;
 
@@ -63,6 +63,29 @@ pub fn pause_on_failure(state: &mut LockedState, settings: &Settings, group: &st
     }
 }
 
+/// Create the task's marker file, if it requested one via `touch_on_done`.
+/// The result is encoded in the file's suffix, i.e. `<path>.success` or `<path>.failed`.
+/// Errors are only logged, as they shouldn't affect the task itself.
+pub fn touch_done_marker(task: &Task) {
+    let Some(path) = &task.touch_on_done else {
+        return;
+    };
+
+    let suffix = match task.status {
+        TaskStatus::Done(TaskResult::Success) => "success",
+        _ => "failed",
+    };
+    let mut marker = path.clone().into_os_string();
+    marker.push(format!(".{suffix}"));
+
+    if let Err(err) = std::fs::File::create(&marker) {
+        error!(
+            "Failed to create marker file {marker:?} for task {}: {err}",
+            task.id
+        );
+    }
+}
+
 /// Do a full reset of the state.
 /// This doesn't reset any processes!
 pub fn reset_state(state: &mut LockedState, settings: &Settings) -> Result<()> {
@@ -73,6 +96,31 @@ pub fn reset_state(state: &mut LockedState, settings: &Settings) -> Result<()> {
     save_state(state, settings)
 }
 
+/// Remove all tasks of a single group and resume that group.
+/// Returns the ids of the removed tasks, so their logs can be cleaned up.
+pub fn reset_group_state(
+    state: &mut LockedState,
+    settings: &Settings,
+    group: &str,
+) -> Result<Vec<usize>> {
+    backup_state(state, settings)?;
+    let task_ids: Vec<usize> = state
+        .tasks
+        .iter()
+        .filter(|(_, task)| task.group == group)
+        .map(|(id, _)| *id)
+        .collect();
+    for task_id in &task_ids {
+        state.tasks.remove(task_id);
+    }
+    if let Some(group) = state.groups.get_mut(group) {
+        group.status = GroupStatus::Running;
+    }
+
+    save_state(state, settings)?;
+    Ok(task_ids)
+}
+
 /// Convenience wrapper around save_to_file.
 pub fn save_state(state: &State, settings: &Settings) -> Result<()> {
     save_state_to_file(state, settings, false)
@@ -158,6 +206,8 @@ pub fn restore_state(pueue_directory: &Path) -> Result<Option<State>> {
                 TaskResult::Killed
             );
             task.status = TaskStatus::Done(TaskResult::Killed);
+            task.resources = None;
+            touch_done_marker(task);
         }
 
         // Handle crash during editing of the task command.
@@ -189,6 +239,8 @@ pub fn restore_state(pueue_directory: &Path) -> Result<Option<State>> {
                 &task.group
             );
             group.status = GroupStatus::Paused;
+            // The start message of tasks that should've been started immediately is gone.
+            task.immediate = false;
         }
     }
 
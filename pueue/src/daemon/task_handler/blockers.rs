@@ -0,0 +1,65 @@
+use log::warn;
+
+use super::*;
+
+/// The amount by which the niceness of a blocking task is lowered.
+const BLOCKER_BOOST: i32 = 5;
+
+impl TaskHandler {
+    /// Lower the niceness of running tasks, as soon as other queued or stashed tasks depend on
+    /// them. This is only done if `daemon.boost_blockers` is set.
+    ///
+    /// Each blocker is boosted only once, based on the nice value it has been started with.
+    pub fn boost_blockers(&mut self) {
+        if !self.settings.daemon.boost_blockers {
+            return;
+        }
+
+        // Forget all tasks that are no longer running.
+        let children = &self.children;
+        self.boosted_blockers
+            .retain(|task_id| children.has_child(*task_id));
+
+        let blockers: Vec<(usize, usize, i32)> = {
+            let state = self.state.lock().unwrap();
+
+            // Count the tasks that are still waiting for each of their dependencies.
+            let mut dependents: HashMap<usize, usize> = HashMap::new();
+            for task in state.tasks.values() {
+                if !matches!(task.status, TaskStatus::Queued | TaskStatus::Stashed { .. }) {
+                    continue;
+                }
+                for dependency in task.dependencies.iter() {
+                    *dependents.entry(*dependency).or_default() += 1;
+                }
+            }
+
+            dependents
+                .into_iter()
+                .filter(|(task_id, _)| !self.boosted_blockers.contains(task_id))
+                .filter_map(|(task_id, count)| {
+                    let task = state.tasks.get(&task_id)?;
+                    let nice = task.nice.unwrap_or(0) - BLOCKER_BOOST;
+                    task.is_running().then_some((task_id, count, nice))
+                })
+                .collect()
+        };
+
+        for (task_id, dependents, nice) in blockers {
+            // Only try once per task, even if it fails, to not spam the log.
+            self.boosted_blockers.insert(task_id);
+
+            let Some(child) = self.children.get_child_mut(task_id) else {
+                continue;
+            };
+
+            // Tasks are spawned in their own process group, whose id is the id of the child.
+            match set_process_group_nice(child.id(), nice) {
+                Ok(()) => info!("Boosted task {task_id}, which blocks {dependents} tasks"),
+                Err(err) => {
+                    warn!("Failed to boost task {task_id}, which blocks other tasks: {err}")
+                }
+            }
+        }
+    }
+}
@@ -1,16 +1,68 @@
+use std::time::Duration;
+
 use handlebars::RenderError;
+use serde_derive::Serialize;
 
 use super::*;
 
+/// The maximum amount of time a callback webhook request may take.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The JSON body that's sent to the callback webhook whenever a task finishes.
+#[derive(Debug, Serialize)]
+pub struct CallbackPayload {
+    pub id: usize,
+    pub command: String,
+    pub path: String,
+    pub group: String,
+    pub result: String,
+    pub exit_code: Option<i32>,
+    pub start: Option<DateTime<Local>>,
+    pub end: Option<DateTime<Local>>,
+    /// The last `callback_log_lines` lines of the task's output.
+    pub output: String,
+}
+
+/// All parameters that can be used in callback templates.
+#[derive(Debug, Serialize)]
+struct CallbackParameters<'a> {
+    #[serde(flatten)]
+    task: HashMap<&'a str, String>,
+    /// The environment variables of the daemon, available via `{{env.NAME}}`.
+    env: HashMap<String, String>,
+}
+
 impl TaskHandler {
     /// Users can specify a callback that's fired whenever a task finishes.
     /// Execute the callback by spawning a new subprocess.
+    ///
+    /// If a callback webhook is configured, the task's details are additionally posted to it.
     pub fn spawn_callback(&mut self, task: &Task) {
+        if let Some(url) = self.settings.daemon.callback_webhook_url.clone() {
+            self.send_callback_webhook(task, url);
+        }
+
         // Return early, if there's no callback specified
-        let Some(template_string) = &self.settings.daemon.callback else {
+        let Some(template_string) = self.settings.daemon.callback.clone() else {
             return;
         };
 
+        self.spawn_callback_command(task, &template_string);
+    }
+
+    /// Users can specify a callback that's fired whenever a task starts running.
+    /// Execute the callback by spawning a new subprocess.
+    pub fn spawn_start_callback(&mut self, task: &Task) {
+        // Return early, if there's no start callback specified
+        let Some(template_string) = self.settings.daemon.start_callback.clone() else {
+            return;
+        };
+
+        self.spawn_callback_command(task, &template_string);
+    }
+
+    /// Render the given callback template for a task and spawn it as a new subprocess.
+    fn spawn_callback_command(&mut self, task: &Task, template_string: &str) {
         // Build the command to be called from the template string in the configuration file.
         let callback_command = match self.build_callback_command(task, template_string) {
             Ok(callback_command) => callback_command,
@@ -36,8 +88,65 @@ impl TaskHandler {
         self.callbacks.push(child);
     }
 
+    /// Post the details of a finished task as JSON to the callback webhook.
+    ///
+    /// The request is sent from a separate thread, so a slow or unreachable server never blocks
+    /// the TaskHandler. Any errors are only logged.
+    fn send_callback_webhook(&self, task: &Task, url: String) {
+        let (result, exit_code) = match &task.status {
+            TaskStatus::Done(result) => {
+                let exit_code = match result {
+                    TaskResult::Success => Some(0),
+                    TaskResult::Failed(code) => Some(*code),
+                    _ => None,
+                };
+                (result.to_string(), exit_code)
+            }
+            _ => ("None".to_string(), None),
+        };
+
+        let output = read_last_log_file_lines(
+            task.id,
+            &self.pueue_directory,
+            self.settings.daemon.callback_log_lines,
+        )
+        .unwrap_or_default();
+
+        let payload = CallbackPayload {
+            id: task.id,
+            command: task.command.clone(),
+            path: task.path.to_string_lossy().to_string(),
+            group: task.group.clone(),
+            result,
+            exit_code,
+            start: task.start,
+            end: task.end,
+            output,
+        };
+
+        std::thread::spawn(move || {
+            let response = reqwest::blocking::Client::new()
+                .post(&url)
+                .timeout(WEBHOOK_TIMEOUT)
+                .json(&payload)
+                .send()
+                .and_then(|response| response.error_for_status());
+
+            match response {
+                Ok(_) => debug!("Sent callback webhook for task {}", payload.id),
+                Err(err) => error!(
+                    "Failed to send callback webhook for task {} with error: {err}",
+                    payload.id
+                ),
+            }
+        });
+    }
+
     /// Take the callback template string from the configuration and insert all parameters from the
-    /// finished task.
+    /// task. Parameters that're only known for finished tasks are set to their default values.
+    ///
+    /// Environment variables of the daemon can be used via `{{env.NAME}}`.
+    /// Unknown variables expand to an empty string, just like in a shell.
     pub fn build_callback_command(
         &self,
         task: &Task,
@@ -64,7 +173,7 @@ impl TaskHandler {
         // Format and insert start and end times.
         let print_time = |time: Option<DateTime<Local>>| {
             time.map(|time| time.timestamp().to_string())
-                .unwrap_or_else(String::new)
+                .unwrap_or_default()
         };
         parameters.insert("start", print_time(task.start));
         parameters.insert("end", print_time(task.end));
@@ -96,6 +205,11 @@ impl TaskHandler {
             parameters.insert("exit_code", "None".into());
         }
 
+        let parameters = CallbackParameters {
+            task: parameters,
+            env: referenced_env_variables(template_string),
+        };
+
         handlebars.render_template(template_string, &parameters)
     }
 
@@ -121,7 +235,29 @@ impl TaskHandler {
 
         finished.reverse();
         for id in finished.iter() {
+            // The child has already been reaped by `try_wait` above.
+            #[allow(clippy::zombie_processes)]
             self.callbacks.remove(*id);
         }
     }
 }
+
+/// Get the values of all environment variables that're referenced via `env.NAME` in the template.
+/// Variables that aren't set in the daemon's environment are set to an empty string.
+/// Otherwise, handlebars' strict mode would fail to render the template.
+fn referenced_env_variables(template_string: &str) -> HashMap<String, String> {
+    template_string
+        .match_indices("env.")
+        .map(|(index, prefix)| {
+            template_string[index + prefix.len()..]
+                .chars()
+                .take_while(|char| char.is_ascii_alphanumeric() || *char == '_')
+                .collect::<String>()
+        })
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            let value = std::env::var(&name).unwrap_or_default();
+            (name, value)
+        })
+        .collect()
+}
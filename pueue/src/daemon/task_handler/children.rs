@@ -14,6 +14,11 @@ impl Children {
         self.0.iter().any(|(_, pool)| !pool.is_empty())
     }
 
+    /// Returns whether there are still running tasks in the given group.
+    pub fn has_active_tasks_in_group(&self, group: &str) -> bool {
+        self.0.get(group).map_or(false, |pool| !pool.is_empty())
+    }
+
     /// A convenience function to check whether there's child with a given task_id.
     /// We have to do a nested linear search, as these datastructure aren't indexed via task_ids.
     pub fn has_child(&self, task_id: usize) -> bool {
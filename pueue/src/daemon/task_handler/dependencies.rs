@@ -1,9 +1,19 @@
+use log::warn;
+
 use super::*;
 
 use pueue_lib::state::Group;
 
+use crate::daemon::state_helper::{touch_done_marker, LockedState};
+use crate::ok_or_shutdown;
+
+/// Only the last bytes of a dependency's log are searched for the text a task is waiting for.
+/// This prevents us from reading huge log files into memory.
+const OUTPUT_CONDITION_MAX_BYTES: u64 = 1024 * 1024;
+
 impl TaskHandler {
-    /// Ensure that no `Queued` tasks have any failed dependencies.
+    /// Ensure that no `Queued` tasks, which should only run if their dependencies succeeded,
+    /// have any failed dependencies.
     /// Otherwise set their status to `Done` and result to `DependencyFailed`.
     pub fn check_failed_dependencies(&mut self) {
         // Clone the state ref, so we don't have two mutable borrows later on.
@@ -14,7 +24,11 @@ impl TaskHandler {
         let has_failed_deps: Vec<_> = state
             .tasks
             .iter()
-            .filter(|(_, task)| task.status == TaskStatus::Queued && !task.dependencies.is_empty())
+            .filter(|(_, task)| {
+                task.status == TaskStatus::Queued
+                    && task.only_if_success
+                    && !task.dependencies.is_empty()
+            })
             .filter_map(|(id, task)| {
                 // At this point we got all queued tasks with dependencies.
                 // Go through all dependencies and ensure they didn't fail.
@@ -55,6 +69,51 @@ impl TaskHandler {
             task.start = Some(Local::now());
             task.end = Some(Local::now());
             self.spawn_callback(task);
+            touch_done_marker(task);
         }
     }
+
+    /// Check whether the output of all dependencies of a task contains the text the task is
+    /// waiting for. Tasks without such a condition always pass.
+    ///
+    /// Only the last [OUTPUT_CONDITION_MAX_BYTES] of each dependency's log are searched.
+    pub fn output_condition_met(&self, state: &LockedState, task_id: usize) -> bool {
+        let Some(task) = state.tasks.get(&task_id) else {
+            return true;
+        };
+        let Some(text) = &task.when_output_contains else {
+            return true;
+        };
+
+        task.dependencies.iter().all(|dependency_id| {
+            match read_log_tail(
+                *dependency_id,
+                &self.pueue_directory,
+                OUTPUT_CONDITION_MAX_BYTES,
+            ) {
+                Ok(output) => String::from_utf8_lossy(&output).contains(text.as_str()),
+                Err(err) => {
+                    warn!("Failed to read output of dependency {dependency_id}: {err:?}");
+                    false
+                }
+            }
+        })
+    }
+
+    /// Mark a task that hasn't been started as skipped.
+    /// This happens if its output condition hasn't been met or if a task it depends on
+    /// has been killed via `kill --with-dependents`.
+    pub fn skip_task(&mut self, state: &mut LockedState, task_id: usize) {
+        let Some(task) = state.tasks.get_mut(&task_id) else {
+            return;
+        };
+        info!("Skipping task {task_id}");
+        task.status = TaskStatus::Done(TaskResult::Skipped);
+        task.start = Some(Local::now());
+        task.end = Some(Local::now());
+        self.spawn_callback(task);
+        touch_done_marker(task);
+
+        ok_or_shutdown!(self, save_state(state, &self.settings));
+    }
 }
@@ -0,0 +1,55 @@
+use pueue_lib::settings::FailurePolicy;
+
+use super::*;
+
+use crate::daemon::state_helper::{pause_on_failure, LockedState};
+
+impl TaskHandler {
+    /// Handle the failure of a task of the given group.
+    ///
+    /// The failure policy of the group takes precedence.
+    /// Groups without a policy fall back to the global `pause_*_on_failure` settings.
+    ///
+    /// If the group has a `pause_after_failures` threshold, nothing happens until that many tasks
+    /// failed in a row. The counter starts over, once the threshold has been reached.
+    pub fn handle_task_failure(&mut self, state: &mut LockedState, group: &str) {
+        let group_settings = self.settings.daemon.group_settings.get(group);
+        let policy = group_settings.and_then(|settings| settings.failure_policy);
+
+        if let Some(threshold) = group_settings.and_then(|settings| settings.pause_after_failures) {
+            let failures = self
+                .consecutive_failures
+                .entry(group.to_string())
+                .or_default();
+            *failures += 1;
+            if *failures < threshold {
+                info!("Task of group {group} failed ({failures}/{threshold} consecutive failures)");
+                return;
+            }
+            self.consecutive_failures.remove(group);
+        }
+
+        match policy {
+            Some(FailurePolicy::Continue) => (),
+            Some(FailurePolicy::Pause) => {
+                if let Some(group) = state.groups.get_mut(group) {
+                    group.status = GroupStatus::Paused;
+                }
+            }
+            Some(FailurePolicy::AbortRemaining) => {
+                info!("Skipping all queued tasks of group {group} due to a failed task");
+                let (queued, _) =
+                    state.filter_tasks_of_group(|task| task.status == TaskStatus::Queued, group);
+                for task_id in queued {
+                    self.skip_task(state, task_id);
+                }
+            }
+            None => pause_on_failure(state, &self.settings, group),
+        }
+    }
+
+    /// A task of the given group succeeded, which resets its consecutive failure counter.
+    pub fn reset_consecutive_failures(&mut self, group: &str) {
+        self.consecutive_failures.remove(group);
+    }
+}
@@ -2,9 +2,12 @@ use anyhow::Context;
 
 use super::*;
 
-use crate::daemon::state_helper::{pause_on_failure, save_state};
+use crate::daemon::state_helper::{save_state, touch_done_marker};
 use crate::ok_or_shutdown;
 
+/// How long we wait for the output of a finished task to be written to its log files.
+const OUTPUT_PIPE_GRACE_PERIOD: Duration = Duration::from_millis(100);
+
 impl TaskHandler {
     /// Check whether there are any finished processes
     /// In case there are, handle them and update the shared state
@@ -16,6 +19,14 @@ impl TaskHandler {
             return;
         }
 
+        // Give the output threads a moment to write the remaining output to the log files.
+        // This must not happen while holding the state lock, as it might take a while.
+        let task_ids: Vec<usize> = finished
+            .iter()
+            .map(|((task_id, _, _), _)| *task_id)
+            .collect();
+        self.wait_for_output_pipes(&task_ids);
+
         // Clone the state ref, so we don't have two mutable borrows later on.
         let state_ref = self.state.clone();
         let mut state = state_ref.lock().unwrap();
@@ -31,18 +42,22 @@ impl TaskHandler {
                     .expect("Worker group must exist when handling finished tasks.")
                     .remove(worker_id)
                     .expect("Errored child went missing while handling finished task.");
+                self.truncate_log(*task_id);
 
                 let group = {
-                    let mut task = state.tasks.get_mut(task_id).unwrap();
+                    let task = state.tasks.get_mut(task_id).unwrap();
                     task.status = TaskStatus::Done(TaskResult::Errored);
                     task.end = Some(Local::now());
+                    task.resources = None;
                     self.spawn_callback(task);
+                    self.spawn_post_task_hook(task);
+                    touch_done_marker(task);
 
                     task.group.clone()
                 };
                 error!("Child {} failed with io::Error: {:?}", task_id, error);
 
-                pause_on_failure(&mut state, &self.settings, &group);
+                self.handle_task_failure(&mut state, &group);
                 continue;
             }
 
@@ -55,41 +70,98 @@ impl TaskHandler {
                 .remove(worker_id)
                 .expect("Child of task {} went away while handling finished task.");
 
+            self.truncate_log(*task_id);
+
             // Get the exit code of the child.
             // Errors really shouldn't happen in here, since we already checked if it's finished
             // with try_wait() before.
-            let exit_code_result = child.wait();
-            let exit_code = exit_code_result
+            let exit_status = child
+                .wait()
                 .context(format!(
                     "Failed on wait() for finished task {task_id} with error: {error:?}"
                 ))
-                .unwrap()
-                .code();
+                .unwrap();
 
             // Processes with exit code 0 exited successfully
             // Processes with `None` have been killed by a Signal
-            let result = match exit_code {
-                Some(0) => TaskResult::Success,
-                Some(exit_code) => TaskResult::Failed(exit_code),
-                None => TaskResult::Killed,
+            // Tasks that have been killed due to their timeout are marked as such,
+            // regardless of how they exited.
+            let result = if self.timed_out_tasks.remove(task_id) {
+                TaskResult::TimedOut
+            } else {
+                match exit_status.code() {
+                    Some(0) => TaskResult::Success,
+                    Some(exit_code) => TaskResult::Failed(exit_code),
+                    None => match get_exit_signal(&exit_status) {
+                        Some(signal) => TaskResult::KilledBySignal(signal),
+                        None => TaskResult::Killed,
+                    },
+                }
             };
 
+            // Tasks that have been killed via `pueue kill --retry` are re-queued,
+            // as long as they didn't hit the retry limit yet.
+            if self.retry_tasks.remove(task_id)
+                && !self.is_resetting(group)
+                && self.shutdown.is_none()
+            {
+                let task = state
+                    .tasks
+                    .get_mut(task_id)
+                    .expect("Task was removed before child process has finished!");
+
+                if task.retries < self.settings.daemon.max_kill_retries {
+                    task.retries += 1;
+                    info!("Re-queuing task {task_id} (retry {})", task.retries);
+
+                    task.status = TaskStatus::Queued;
+                    task.enqueued_at = Some(Local::now());
+                    task.start = None;
+                    task.end = None;
+                    task.resources = None;
+                    task.immediate = false;
+                    continue;
+                }
+                info!("Task {task_id} hit the retry limit and won't be re-queued");
+            }
+
+            // Failed tasks are automatically re-enqueued, if they have any retries left.
+            if let TaskResult::Failed(_) = result {
+                let task = state
+                    .tasks
+                    .get_mut(task_id)
+                    .expect("Task was removed before child process has finished!");
+
+                if task.retries_remaining > 0
+                    && !self.is_resetting(group)
+                    && self.shutdown.is_none()
+                {
+                    schedule_retry(task);
+                    continue;
+                }
+            }
+
             // Update all properties on the task and get the group for later
             let group = {
-                let mut task = state
+                let task = state
                     .tasks
                     .get_mut(task_id)
                     .expect("Task was removed before child process has finished!");
 
                 task.status = TaskStatus::Done(result.clone());
                 task.end = Some(Local::now());
+                task.resources = None;
                 self.spawn_callback(task);
+                self.spawn_post_task_hook(task);
+                touch_done_marker(task);
 
                 task.group.clone()
             };
 
-            if let TaskResult::Failed(_) = result {
-                pause_on_failure(&mut state, &self.settings, &group);
+            if matches!(result, TaskResult::Failed(_) | TaskResult::TimedOut) {
+                self.handle_task_failure(&mut state, &group);
+            } else if matches!(result, TaskResult::Success) {
+                self.reset_consecutive_failures(&group);
             }
 
             // Already remove the output files, if the daemon is being reset anyway
@@ -101,6 +173,41 @@ impl TaskHandler {
         ok_or_shutdown!(self, save_state(&state, &self.settings));
     }
 
+    /// Wait a short time for the threads that copy the piped output of the given tasks into
+    /// their log files. They exit as soon as the tasks' output pipes are closed.
+    ///
+    /// Background processes of a task inherit its pipes and might keep them open for a long
+    /// time, which is why we don't wait any longer than [OUTPUT_PIPE_GRACE_PERIOD].
+    fn wait_for_output_pipes(&self, task_ids: &[usize]) {
+        let deadline = Instant::now() + OUTPUT_PIPE_GRACE_PERIOD;
+        let is_piping = |task_id: &usize| {
+            self.output_pipes
+                .get(task_id)
+                .map(|handles| handles.iter().any(|handle| !handle.is_finished()))
+                .unwrap_or(false)
+        };
+
+        while task_ids.iter().any(is_piping) && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    /// Join all output threads, whose pipes have been closed in the meantime.
+    pub fn reap_output_pipes(&mut self) {
+        self.output_pipes.retain(|task_id, handles| {
+            if handles.iter().any(|handle| !handle.is_finished()) {
+                return true;
+            }
+
+            for handle in handles.drain(..) {
+                if handle.join().is_err() {
+                    error!("The output thread of task {task_id} panicked");
+                }
+            }
+            false
+        });
+    }
+
     /// Gather all finished tasks and sort them by finished and errored.
     /// Returns a list of finished task ids and whether they errored or not.
     fn get_finished(&mut self) -> Vec<((usize, String, usize), Option<std::io::Error>)> {
@@ -125,3 +232,34 @@ impl TaskHandler {
         finished
     }
 }
+
+/// The maximum delay in seconds before a failed task is re-enqueued.
+/// Larger delays don't make much sense and would overflow chrono's date arithmetic.
+const MAX_RETRY_DELAY: u64 = 60 * 60 * 24 * 365;
+
+/// Re-enqueue a failed task with an exponential backoff.
+/// The delay doubles with every failed attempt, starting at the task's `retry_delay`.
+fn schedule_retry(task: &mut Task) {
+    let factor = 2u64.saturating_pow(task.failed_attempts as u32);
+    let delay = task.retry_delay.saturating_mul(factor).min(MAX_RETRY_DELAY);
+
+    task.retries_remaining -= 1;
+    task.failed_attempts += 1;
+    task.start = None;
+    task.end = None;
+    task.resources = None;
+    task.immediate = false;
+
+    if delay == 0 {
+        info!("Re-enqueuing failed task {}", task.id);
+        task.status = TaskStatus::Queued;
+        task.enqueued_at = Some(Local::now());
+    } else {
+        info!("Re-enqueuing failed task {} in {delay} seconds", task.id);
+        let delay = chrono::Duration::seconds(delay as i64);
+        task.status = TaskStatus::Stashed {
+            enqueue_at: Some(Local::now() + delay),
+        };
+        task.enqueued_at = None;
+    }
+}
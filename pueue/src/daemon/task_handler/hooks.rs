@@ -0,0 +1,115 @@
+use std::io;
+
+use super::*;
+
+impl TaskHandler {
+    /// Spawn the `pre_task_hook` of the task's group, if there's one.
+    /// The hook's output is discarded, so it doesn't end up in the daemon's output.
+    ///
+    /// Returns `true`, if the task has to wait for the hook to finish before it can be started.
+    /// The hook is then handled in [TaskHandler::check_pre_task_hooks].
+    pub fn spawn_pre_task_hook(&mut self, task_id: usize, group: &str) -> bool {
+        let Some(hook) = self
+            .settings
+            .daemon
+            .group_settings
+            .get(group)
+            .and_then(|settings| settings.pre_task_hook.as_ref())
+        else {
+            return false;
+        };
+
+        let spawn_result = compile_shell_command(hook)
+            .env("PUEUE_TASK_ID", task_id.to_string())
+            .env("PUEUE_GROUP", group)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        match spawn_result {
+            Ok(child) => {
+                debug!("Spawned pre_task_hook for task {task_id}");
+                self.pre_task_hooks.insert(task_id, child);
+                true
+            }
+            Err(err) => {
+                // Let the task fail to spawn with the hook's error.
+                self.finished_pre_task_hooks.insert(task_id, Err(err));
+                false
+            }
+        }
+    }
+
+    /// Check whether any `pre_task_hook`s finished.
+    /// The respective tasks are then either started or fail to spawn, if their hook failed.
+    pub fn check_pre_task_hooks(&mut self) {
+        let mut finished = Vec::new();
+        for (task_id, child) in self.pre_task_hooks.iter_mut() {
+            let result = match child.try_wait() {
+                Ok(None) => continue,
+                Ok(Some(status)) if status.success() => Ok(()),
+                Ok(Some(status)) => Err(io::Error::new(
+                    io::ErrorKind::Other,
+                    format!("The pre_task_hook failed with {status}"),
+                )),
+                Err(err) => Err(err),
+            };
+            finished.push((*task_id, result));
+        }
+
+        if finished.is_empty() {
+            return;
+        }
+
+        let cloned_state_mutex = self.state.clone();
+        let mut state = cloned_state_mutex.lock().unwrap();
+        for (task_id, result) in finished {
+            self.pre_task_hooks.remove(&task_id);
+
+            // The task might have been removed or stashed in the meantime.
+            let startable = state.tasks.get(&task_id).map_or(false, |task| {
+                matches!(task.status, TaskStatus::Queued | TaskStatus::Stashed { .. })
+            });
+            if !startable {
+                continue;
+            }
+
+            self.finished_pre_task_hooks.insert(task_id, result);
+            self.start_process(task_id, &mut state);
+        }
+    }
+
+    /// Spawn the `post_task_hook` of the task's group, once the task finished.
+    /// The hook isn't waited for. It's handled just like a callback.
+    pub fn spawn_post_task_hook(&mut self, task: &Task) {
+        let Some(hook) = self
+            .settings
+            .daemon
+            .group_settings
+            .get(&task.group)
+            .and_then(|settings| settings.post_task_hook.clone())
+        else {
+            return;
+        };
+
+        let result = match &task.status {
+            TaskStatus::Done(result) => result.to_string(),
+            _ => "None".to_string(),
+        };
+
+        let spawn_result = compile_shell_command(&hook)
+            .env("PUEUE_TASK_ID", task.id.to_string())
+            .env("PUEUE_GROUP", &task.group)
+            .env("PUEUE_TASK_RESULT", result)
+            .spawn();
+
+        match spawn_result {
+            Ok(child) => {
+                debug!("Spawned post_task_hook for task {}", task.id);
+                self.callbacks.push(child);
+            }
+            Err(err) => error!("Failed to spawn post_task_hook with error: {err}"),
+        }
+    }
+}
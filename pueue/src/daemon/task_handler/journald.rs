@@ -0,0 +1,61 @@
+use std::io;
+
+use log::debug;
+
+/// The syslog priority of lines that are written to stdout (`LOG_INFO`).
+pub const STDOUT_PRIORITY: &str = "6";
+/// The syslog priority of lines that are written to stderr (`LOG_ERR`).
+pub const STDERR_PRIORITY: &str = "3";
+
+/// Send a single line of a task's output to journald.
+pub fn send_line(task_id: usize, identifier: &str, priority: &str, line: &[u8]) {
+    let task_id_field = task_id.to_string();
+    let fields: [(&str, &[u8]); 4] = [
+        ("MESSAGE", line),
+        ("PRIORITY", priority.as_bytes()),
+        ("SYSLOG_IDENTIFIER", identifier.as_bytes()),
+        ("PUEUE_TASK_ID", task_id_field.as_bytes()),
+    ];
+
+    // Journald might not be running. Don't spam the daemon's log in that case.
+    if let Err(err) = send_entry(&fields) {
+        debug!("Failed to send output of task {task_id} to journald: {err}");
+    }
+}
+
+/// Send a single entry to journald via its native protocol.
+/// Every field is encoded as `KEY=VALUE\n`, unless the value contains a newline.
+/// Such values are encoded as `KEY\n`, followed by the little-endian 64 bit length of the value,
+/// the value itself and a final newline.
+#[cfg(target_os = "linux")]
+fn send_entry(fields: &[(&str, &[u8])]) -> io::Result<()> {
+    use std::os::unix::net::UnixDatagram;
+
+    let mut payload = Vec::new();
+    for (key, value) in fields {
+        payload.extend_from_slice(key.as_bytes());
+        if value.contains(&b'\n') {
+            payload.push(b'\n');
+            payload.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        } else {
+            payload.push(b'=');
+        }
+        payload.extend_from_slice(value);
+        payload.push(b'\n');
+    }
+
+    let socket = UnixDatagram::unbound()?;
+    socket.send_to(&payload, "/run/systemd/journal/socket")?;
+
+    Ok(())
+}
+
+/// Journald only exists on Linux.
+/// The daemon refuses to start with `log_to_journald` on all other platforms.
+#[cfg(not(target_os = "linux"))]
+fn send_entry(_fields: &[(&str, &[u8])]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "journald is only supported on Linux",
+    ))
+}
@@ -0,0 +1,36 @@
+use super::*;
+
+impl TaskHandler {
+    /// Truncate the log files of all running tasks that exceed `daemon.max_log_size_bytes`.
+    pub fn check_log_sizes(&mut self) {
+        if self.settings.daemon.max_log_size_bytes.is_none() {
+            return;
+        }
+
+        let task_ids: Vec<usize> = self
+            .children
+            .0
+            .values()
+            .flat_map(|pool| pool.values().map(|(task_id, _)| *task_id))
+            .collect();
+
+        for task_id in task_ids {
+            self.truncate_log(task_id);
+        }
+    }
+
+    /// Truncate the log files of a single task, if they exceed `daemon.max_log_size_bytes`.
+    ///
+    /// This is also called once a task finished, as the periodic check might miss the last output.
+    pub fn truncate_log(&self, task_id: usize) {
+        let Some(max_size) = self.settings.daemon.max_log_size_bytes else {
+            return;
+        };
+
+        match truncate_log_file(task_id, &self.pueue_directory, max_size) {
+            Ok(true) => debug!("Truncated log file of task {task_id}"),
+            Ok(false) => (),
+            Err(err) => error!("Failed to truncate log file of task {task_id}: {err}"),
+        }
+    }
+}
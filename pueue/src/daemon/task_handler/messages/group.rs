@@ -9,7 +9,7 @@ use crate::daemon::task_handler::{Shutdown, TaskHandler};
 use crate::ok_or_shutdown;
 
 impl TaskHandler {
-    /// Handle the addition and the removal of groups.
+    /// Handle the addition, removal and renaming of groups.
     ///
     /// This is done in the TaskHandler, as we also have to create/remove worker pools.
     /// I.e. we have to touch three things:
@@ -30,7 +30,7 @@ impl TaskHandler {
                     error!("Group \"{name}\" already exists");
                     return;
                 }
-                let mut group = state.create_group(&name);
+                let group = state.create_group(&name);
                 if let Some(parallel_tasks) = parallel_tasks {
                     group.parallel_tasks = parallel_tasks;
                 }
@@ -82,6 +82,31 @@ impl TaskHandler {
 
                 info!("Group \"{group}\" has been removed");
             }
+            GroupMessage::Rename { name, new_name } => {
+                // This moves the group and all of its tasks in one go, while we hold the lock.
+                if let Err(error) = state.rename_group(&name, &new_name) {
+                    error!("Error while renaming group: \"{error}\"");
+                    return;
+                }
+
+                // Move the worker pool, including the children of any running tasks.
+                let pool = self.children.0.remove(&name).unwrap_or_default();
+                self.children.0.insert(new_name.clone(), pool);
+
+                // Keep counting the failures of the group under its new name.
+                if let Some(failures) = self.consecutive_failures.remove(&name) {
+                    self.consecutive_failures.insert(new_name.clone(), failures);
+                }
+
+                if self.group_reset.as_deref() == Some(name.as_str()) {
+                    self.group_reset = Some(new_name.clone());
+                }
+
+                // Persist the state.
+                ok_or_shutdown!(self, save_state(&state, &self.settings));
+
+                info!("Group \"{name}\" has been renamed to \"{new_name}\"");
+            }
         }
     }
 }
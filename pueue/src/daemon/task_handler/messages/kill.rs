@@ -18,7 +18,14 @@ impl TaskHandler {
     /// `pause_groups` If `group` or `all` is given, the groups should be paused under some
     ///     circumstances. This is mostly to prevent any further task execution during an emergency
     /// `signal` Don't kill the task as usual, but rather send a unix process signal.
-    pub fn kill(&mut self, tasks: TaskSelection, pause_groups: bool, signal: Option<Signal>) {
+    /// `retry` Re-queue the tasks once they're finished, instead of marking them as `Killed`.
+    pub fn kill(
+        &mut self,
+        tasks: TaskSelection,
+        pause_groups: bool,
+        signal: Option<Signal>,
+        retry: bool,
+    ) {
         let cloned_state_mutex = self.state.clone();
         let mut state = cloned_state_mutex.lock().unwrap();
         // Get the keys of all tasks that should be resumed
@@ -55,6 +62,12 @@ impl TaskHandler {
         };
 
         for task_id in task_ids {
+            // Only tasks that are actually terminated are re-queued.
+            let terminating = signal.as_ref().map_or(true, Signal::is_terminating);
+            if retry && terminating && self.children.has_child(task_id) {
+                self.retry_tasks.insert(task_id);
+            }
+
             if let Some(signal) = signal.clone() {
                 self.send_internal_signal(task_id, signal);
             } else {
@@ -65,6 +78,25 @@ impl TaskHandler {
         ok_or_shutdown!(self, save_state(&state, &self.settings));
     }
 
+    /// Mark all selected tasks that haven't been started yet as skipped.
+    /// This is used to stop tasks that depend on killed tasks.
+    pub fn skip_unstarted(&mut self, tasks: &TaskSelection) {
+        let TaskSelection::TaskIds(task_ids) = tasks else {
+            return;
+        };
+
+        let cloned_state_mutex = self.state.clone();
+        let mut state = cloned_state_mutex.lock().unwrap();
+        for task_id in task_ids {
+            let unstarted = state.tasks.get(task_id).map_or(false, |task| {
+                matches!(task.status, TaskStatus::Queued | TaskStatus::Stashed { .. })
+            });
+            if unstarted {
+                self.skip_task(&mut state, *task_id);
+            }
+        }
+    }
+
     /// Send a signal to a specific child process.
     /// This is a wrapper around [send_signal_to_child], which does a little bit of
     /// additional error handling.
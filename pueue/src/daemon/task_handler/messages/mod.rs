@@ -27,9 +27,19 @@ impl TaskHandler {
         match message {
             Message::Pause(message) => self.pause(message.tasks, message.wait),
             Message::Start(message) => self.start(message.tasks),
-            Message::Kill(message) => self.kill(message.tasks, true, message.signal),
-            Message::Send(message) => self.send(message.task_id, message.input),
-            Message::Reset(_) => self.reset(),
+            Message::Kill(message) => {
+                if message.skip_unstarted {
+                    self.skip_unstarted(&message.tasks);
+                }
+                // Groups aren't paused, if the killed tasks should be retried.
+                self.kill(message.tasks, !message.retry, message.signal, message.retry)
+            }
+            Message::Send(message) => {
+                for task_id in message.task_ids {
+                    self.send(task_id, message.input.clone());
+                }
+            }
+            Message::Reset(message) => self.reset(message.group),
             Message::Group(message) => self.handle_group_message(message),
             Message::DaemonShutdown(shutdown) => {
                 self.initiate_shutdown(shutdown);
@@ -1,14 +1,18 @@
-use std::collections::{BTreeMap, HashMap};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use std::process::Child;
 use std::process::Stdio;
 use std::sync::mpsc::{Receiver, SendError, Sender};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 use anyhow::Result;
 use chrono::prelude::*;
 use command_group::CommandGroup;
 use handlebars::Handlebars;
 use log::{debug, error, info};
+use sysinfo::{System, SystemExt};
 
 use pueue_lib::log::*;
 use pueue_lib::network::message::*;
@@ -19,21 +23,39 @@ use pueue_lib::state::{GroupStatus, SharedState};
 use pueue_lib::task::{Task, TaskResult, TaskStatus};
 
 use crate::daemon::pid::cleanup_pid_file;
-use crate::daemon::state_helper::{reset_state, save_state};
+use crate::daemon::state_helper::{reset_group_state, reset_state, save_state};
 
+/// Raising the scheduling priority of tasks that other tasks depend on.
+mod blockers;
 mod callback;
 /// A helper newtype struct, which implements convenience methods for our child process management
 /// datastructure.
 mod children;
 /// Logic for handling dependencies
 mod dependencies;
+/// Applying the failure policy of a group, once one of its tasks fails.
+mod failure_policy;
 /// Logic for finishing and cleaning up completed tasks.
 mod finish_task;
+/// The pre- and post-task hooks of groups.
+mod hooks;
+/// Forwarding the output of tasks to journald.
+mod journald;
+/// Truncation of log files that exceed the maximum log size.
+mod log_size;
 /// This module contains all logic that's triggered by messages received via the mpsc channel.
 /// These messages are sent by the threads that handle the client messages.
 mod messages;
+/// Piping the output of tasks through the daemon into their log files.
+mod pipe_output;
+/// Stashing of tasks that have been queued for too long.
+mod queue_age;
+/// Sampling of the resource usage of running tasks.
+mod resources;
 /// Everything regarding actually spawning task processes.
 mod spawn_task;
+/// Logic for killing tasks that exceeded their timeout.
+mod timeout;
 
 use self::children::Children;
 
@@ -85,14 +107,43 @@ pub struct TaskHandler {
     /// A mapping with this structure:
     /// BTreeMap<group, BTreeMap<group_worker_id, (task_id, Subprocess handle)>
     children: Children,
+    /// The threads that copy the piped output of tasks into their log files.
+    /// They're kept until their pipes are closed, which may happen after the task finished.
+    output_pipes: HashMap<usize, Vec<JoinHandle<()>>>,
     /// These are the currently running callbacks. They're usually very short-lived.
     callbacks: Vec<Child>,
+    /// The `pre_task_hook`s that are currently running, keyed by the id of their task.
+    /// Their tasks are started once the hook finished successfully.
+    pre_task_hooks: HashMap<usize, Child>,
+    /// The results of finished `pre_task_hook`s, whose tasks are about to be started.
+    finished_pre_task_hooks: HashMap<usize, std::io::Result<()>>,
     /// A simple flag which is used to signal that we're currently doing a full reset of the daemon.
     /// This flag prevents new tasks from being spawned.
     full_reset: bool,
+    /// The group that's currently being reset, if only a single group is reset.
+    /// Tasks of this group aren't re-queued while their children are being killed.
+    group_reset: Option<String>,
     /// Whether we're currently in the process of a graceful shutdown.
     /// Depending on the shutdown type, we're exiting with different exitcodes.
     shutdown: Option<Shutdown>,
+    /// The point in time at which remaining tasks are killed, while the daemon is being drained.
+    drain_deadline: Option<Instant>,
+    /// The ids of tasks that have been killed, as they exceeded their timeout.
+    /// This is used to mark them as [TaskResult::TimedOut] once they're finished.
+    timed_out_tasks: HashSet<usize>,
+    /// The ids of tasks that have been killed via `pueue kill --retry`.
+    /// These tasks will be re-queued once they're finished.
+    retry_tasks: HashSet<usize>,
+    /// The ids of running tasks, whose niceness has already been lowered, as other tasks depend
+    /// on them.
+    boosted_blockers: HashSet<usize>,
+    /// The amount of tasks that failed in a row for each group.
+    /// This is used for the `pause_after_failures` group setting.
+    consecutive_failures: HashMap<String, usize>,
+    /// Used to sample the resource usage of running tasks.
+    system: System,
+    /// The last time the resource usage of running tasks has been sampled.
+    last_resource_update: Instant,
     /// The settings that are passed at program start.
     settings: Settings,
 
@@ -116,9 +167,20 @@ impl TaskHandler {
             state: shared_state,
             receiver,
             children: Children(pools),
+            output_pipes: HashMap::new(),
             callbacks: Vec::new(),
+            pre_task_hooks: HashMap::new(),
+            finished_pre_task_hooks: HashMap::new(),
             full_reset: false,
+            group_reset: None,
             shutdown: None,
+            drain_deadline: None,
+            timed_out_tasks: HashSet::new(),
+            retry_tasks: HashSet::new(),
+            boosted_blockers: HashSet::new(),
+            consecutive_failures: HashMap::new(),
+            system: System::new(),
+            last_resource_update: Instant::now(),
             pueue_directory: settings.shared.pueue_directory(),
             settings,
         }
@@ -129,9 +191,15 @@ impl TaskHandler {
     ///
     /// - Receive and handle instructions from the client.
     /// - Handle finished tasks, i.e. cleanup processes, update statuses.
+    /// - Kill tasks that exceeded their timeout.
+    /// - Sample the resource usage of running tasks.
+    /// - Truncate log files of running tasks that exceed the maximum log size.
     /// - Callback handling logic. This is rather uncritical.
+    /// - Start tasks whose pre-task hook finished.
     /// - Enqueue any stashed processes which are ready for being queued.
+    /// - Stash queued tasks that exceeded the maximum queue age.
     /// - Ensure tasks with dependencies have no failed ancestors
+    /// - Raise the priority of running tasks that block other tasks.
     /// - Whether whe should perform a shutdown.
     /// - If the client requested a reset: reset the state if all children have been killed and handled.
     /// - Check whether we can spawn new tasks.
@@ -143,9 +211,16 @@ impl TaskHandler {
         loop {
             self.receive_messages();
             self.handle_finished_tasks();
+            self.reap_output_pipes();
+            self.check_timeouts();
+            self.update_resource_usage();
+            self.check_log_sizes();
             self.check_callbacks();
+            self.check_pre_task_hooks();
             self.enqueue_delayed_tasks();
+            self.stash_stale_tasks();
             self.check_failed_dependencies();
+            self.boost_blockers();
 
             if self.shutdown.is_some() {
                 // Check if we're in shutdown.
@@ -156,6 +231,11 @@ impl TaskHandler {
                 // Once they are, reset everything and go back to normal
                 self.handle_reset();
             } else {
+                // Wait until all tasks of a group that's being reset are killed.
+                // The group is paused in the meantime, so no new tasks are started in it.
+                if self.group_reset.is_some() {
+                    self.handle_group_reset();
+                }
                 // Only start new tasks, if we aren't in the middle of a reset or shutdown.
                 self.spawn_new();
             }
@@ -165,16 +245,40 @@ impl TaskHandler {
     /// Initiate shutdown, which includes killing all children and pausing all groups.
     /// We don't have to pause any groups, as no new tasks will be spawned during shutdown anyway.
     /// Any groups with queued tasks, will be automatically paused on state-restoration.
+    ///
+    /// When draining, running tasks aren't killed and are allowed to finish instead.
     fn initiate_shutdown(&mut self, shutdown: Shutdown) {
+        if let Shutdown::Drain { timeout } = shutdown {
+            // Don't downgrade a shutdown that's already in progress.
+            if self.shutdown.is_some() {
+                return;
+            }
+            info!("Draining daemon. Waiting for running tasks to finish.");
+            self.drain_deadline =
+                timeout.map(|timeout| Instant::now() + Duration::from_secs(timeout));
+            self.shutdown = Some(shutdown);
+            return;
+        }
+
+        self.drain_deadline = None;
         self.shutdown = Some(shutdown);
 
-        self.kill(TaskSelection::All, false, None);
+        self.kill(TaskSelection::All, false, None, false);
     }
 
     /// Check if all tasks are killed.
     /// If they aren't, we'll wait a little longer.
     /// Once they're, we do some cleanup and exit.
     fn handle_shutdown(&mut self) {
+        // Kill all remaining tasks, once the drain timeout has been exceeded.
+        if let Some(deadline) = self.drain_deadline {
+            if Instant::now() >= deadline {
+                info!("Drain timeout exceeded. Killing all remaining tasks.");
+                self.drain_deadline = None;
+                self.kill(TaskSelection::All, false, None, false);
+            }
+        }
+
         // There are still active tasks. Continue waiting until they're killed and cleaned up.
         if self.children.has_active_tasks() {
             return;
@@ -225,11 +329,49 @@ impl TaskHandler {
         self.full_reset = false;
     }
 
+    /// Once all children of the group that's being reset have been handled, remove all tasks
+    /// of that group and resume it.
+    fn handle_group_reset(&mut self) {
+        let Some(group) = self.group_reset.clone() else {
+            return;
+        };
+        if self.children.has_active_tasks_in_group(&group) {
+            return;
+        }
+
+        let mut state = self.state.lock().unwrap();
+        match reset_group_state(&mut state, &self.settings, &group) {
+            Ok(task_ids) => {
+                for task_id in task_ids {
+                    clean_log_handles(task_id, &self.pueue_directory);
+                }
+            }
+            Err(error) => error!("Failed to reset group {group} with error: {error:?}"),
+        }
+        self.group_reset = None;
+    }
+
     /// Kill all children by using the `kill` function.
     /// Set the respective group's statuses to `Reset`. This will prevent new tasks from being spawned.
-    fn reset(&mut self) {
-        self.full_reset = true;
-        self.kill(TaskSelection::All, false, None);
+    ///
+    /// If a group is given, only the children of that group are killed and the group is paused
+    /// until all of them have been handled.
+    fn reset(&mut self, group: Option<String>) {
+        match group {
+            Some(group) => {
+                self.group_reset = Some(group.clone());
+                self.kill(TaskSelection::Group(group), true, None, false);
+            }
+            None => {
+                self.full_reset = true;
+                self.kill(TaskSelection::All, false, None, false);
+            }
+        }
+    }
+
+    /// Returns whether the given group is currently being reset.
+    fn is_resetting(&self, group: &str) -> bool {
+        self.full_reset || self.group_reset.as_deref() == Some(group)
     }
 
     /// As time passes, some delayed tasks may need to be enqueued.
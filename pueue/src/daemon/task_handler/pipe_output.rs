@@ -0,0 +1,130 @@
+use std::fs::File;
+use std::io::{ErrorKind, Read, Write};
+use std::thread::JoinHandle;
+
+use chrono::Local;
+use command_group::GroupChild;
+use log::error;
+
+use super::journald::{send_line, STDERR_PRIORITY, STDOUT_PRIORITY};
+
+/// Determines what happens to the output of tasks, which is piped through the daemon.
+#[derive(Clone, Copy, Debug)]
+pub struct PipeOptions {
+    /// Forward each line to journald.
+    pub journald: bool,
+    /// Prepend each line in the log file with the current time, e.g. `[12:34:56] `.
+    pub timestamps: bool,
+}
+
+/// Take the piped output of a freshly spawned task and write it to the task's log files.
+/// Returns the handles of the threads that copy the output.
+pub fn pipe_output(
+    task_id: usize,
+    child: &mut GroupChild,
+    stdout_log: File,
+    stderr_log: File,
+    options: PipeOptions,
+) -> Vec<JoinHandle<()>> {
+    let child = child.inner();
+    let mut handles = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        handles.push(copy_output(
+            task_id,
+            stdout,
+            stdout_log,
+            STDOUT_PRIORITY,
+            options,
+        ));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        handles.push(copy_output(
+            task_id,
+            stderr,
+            stderr_log,
+            STDERR_PRIORITY,
+            options,
+        ));
+    }
+
+    handles
+}
+
+/// Copy everything from the given pipe into the log file.
+/// This happens in a separate thread, which exits as soon as the pipe is closed.
+fn copy_output<R: Read + Send + 'static>(
+    task_id: usize,
+    mut pipe: R,
+    mut log_file: File,
+    priority: &'static str,
+    options: PipeOptions,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let identifier = format!("pueue-task-{task_id}");
+        let mut buffer = [0; 4096];
+        let mut line = Vec::new();
+        let mut at_line_start = true;
+
+        loop {
+            let read = match pipe.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    error!("Failed to read output of task {task_id}: {err}");
+                    break;
+                }
+            };
+
+            // Write the output to the log file right away, so partial lines are visible as well.
+            let chunk = &buffer[..read];
+            let result = if options.timestamps {
+                write_with_timestamps(&mut log_file, chunk, &mut at_line_start)
+            } else {
+                log_file.write_all(chunk)
+            };
+            if let Err(err) = result {
+                error!("Failed to write output of task {task_id} to its log file: {err}");
+            }
+
+            if !options.journald {
+                continue;
+            }
+
+            // Only complete lines are sent to journald.
+            line.extend_from_slice(chunk);
+            while let Some(position) = line.iter().position(|byte| *byte == b'\n') {
+                let rest = line.split_off(position + 1);
+                line.truncate(position);
+                send_line(task_id, &identifier, priority, &line);
+                line = rest;
+            }
+        }
+
+        if !line.is_empty() {
+            send_line(task_id, &identifier, priority, &line);
+        }
+    })
+}
+
+/// Write a chunk of output to the log file and prepend each new line with the current time.
+/// `at_line_start` tracks whether the previous chunk ended with a newline.
+///
+/// The whole chunk is written at once, so it doesn't interleave with the output of the task's
+/// other stream, if both are written to the same log file.
+fn write_with_timestamps(
+    log_file: &mut File,
+    chunk: &[u8],
+    at_line_start: &mut bool,
+) -> std::io::Result<()> {
+    let mut output = Vec::with_capacity(chunk.len());
+    for segment in chunk.split_inclusive(|byte| *byte == b'\n') {
+        if *at_line_start {
+            write!(output, "[{}] ", Local::now().format("%H:%M:%S"))?;
+        }
+        output.extend_from_slice(segment);
+        *at_line_start = segment.ends_with(b"\n");
+    }
+
+    log_file.write_all(&output)
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+use crate::ok_or_shutdown;
+
+impl TaskHandler {
+    /// Stash all queued tasks that have been waiting for longer than the
+    /// `max_queue_age_seconds` setting allows.
+    ///
+    /// Those tasks are marked via [Task::stashed_by_age], so users can see why they didn't run.
+    pub fn stash_stale_tasks(&mut self) {
+        let Some(max_age) = self.settings.daemon.max_queue_age_seconds else {
+            return;
+        };
+        let Ok(max_age) = i64::try_from(max_age) else {
+            return;
+        };
+        let max_age = chrono::Duration::seconds(max_age);
+
+        let state_clone = self.state.clone();
+        let mut state = state_clone.lock().unwrap();
+
+        let mut changed = false;
+        for (_, task) in state.tasks.iter_mut() {
+            if task.status != TaskStatus::Queued {
+                continue;
+            }
+            let Some(enqueued_at) = task.enqueued_at else {
+                continue;
+            };
+
+            if enqueued_at + max_age <= Local::now() {
+                info!(
+                    "Task {} has been queued for longer than {} seconds. Stashing it.",
+                    task.id,
+                    max_age.num_seconds()
+                );
+
+                task.status = TaskStatus::Stashed { enqueue_at: None };
+                task.enqueued_at = None;
+                task.stashed_by_age = true;
+                changed = true;
+            }
+        }
+
+        // Save the state if a task has been stashed
+        if changed {
+            ok_or_shutdown!(self, save_state(&state, &self.settings));
+        }
+    }
+}
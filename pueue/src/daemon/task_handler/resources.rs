@@ -0,0 +1,52 @@
+use std::time::{Duration, Instant};
+
+use sysinfo::{Pid, PidExt, ProcessExt, SystemExt};
+
+use pueue_lib::process_helper::get_process_group_pids;
+use pueue_lib::task::ResourceUsage;
+
+use super::*;
+
+/// The interval in which the resource usage of running tasks is refreshed.
+/// The CPU usage is computed over this interval, so it shouldn't be too short.
+const RESOURCE_UPDATE_INTERVAL: Duration = Duration::from_secs(1);
+
+impl TaskHandler {
+    /// Refresh the memory and CPU usage of all running tasks in the state.
+    ///
+    /// The children of the TaskHandler are only known in this thread, which is why the resource
+    /// usage is sampled in here and made available to clients via the state.
+    pub fn update_resource_usage(&mut self) {
+        if self.last_resource_update.elapsed() < RESOURCE_UPDATE_INTERVAL {
+            return;
+        }
+        self.last_resource_update = Instant::now();
+
+        let mut usages = Vec::new();
+        for pool in self.children.0.values() {
+            for (task_id, child) in pool.values() {
+                // Tasks are spawned in their own process group, which is led by the child.
+                // Sum up the usage of all processes in that group, e.g. those spawned by a shell.
+                let mut usage = ResourceUsage::default();
+                for pid in get_process_group_pids(child.id()) {
+                    let pid = Pid::from_u32(pid);
+                    if !self.system.refresh_process(pid) {
+                        continue;
+                    }
+                    if let Some(process) = self.system.process(pid) {
+                        usage.memory += process.memory();
+                        usage.cpu_usage_permille += (process.cpu_usage() * 10.0).round() as u32;
+                    }
+                }
+                usages.push((*task_id, usage));
+            }
+        }
+
+        let mut state = self.state.lock().unwrap();
+        for (task_id, usage) in usages {
+            if let Some(task) = state.tasks.get_mut(&task_id) {
+                task.resources = Some(usage);
+            }
+        }
+    }
+}
@@ -1,6 +1,7 @@
+use super::pipe_output::{pipe_output, PipeOptions};
 use super::*;
 
-use crate::daemon::state_helper::{pause_on_failure, save_state, LockedState};
+use crate::daemon::state_helper::{save_state, touch_done_marker, LockedState};
 use crate::ok_or_shutdown;
 
 impl TaskHandler {
@@ -8,9 +9,24 @@ impl TaskHandler {
     pub fn spawn_new(&mut self) {
         let cloned_state_mutex = self.state.clone();
         let mut state = cloned_state_mutex.lock().unwrap();
+
+        // Groups that are created outside of the TaskHandler, e.g. by `pueue import`,
+        // don't have a worker pool yet.
+        for group in state.groups.keys() {
+            if !self.children.0.contains_key(group) {
+                self.children.0.insert(group.clone(), BTreeMap::new());
+            }
+        }
+
         // Check whether a new task can be started.
         // Spawn tasks until we no longer have free slots available.
         while let Some(id) = self.get_next_task_id(&state) {
+            // Skip tasks whose dependencies didn't produce the output the task is waiting for.
+            if !self.output_condition_met(&state, id) {
+                self.skip_task(&mut state, id);
+                continue;
+            }
+
             self.start_process(id, &mut state);
         }
     }
@@ -18,14 +34,28 @@ impl TaskHandler {
     /// Search and return the next task that can be started.
     /// Precondition for a task to be started:
     /// - is in Queued state
-    /// - There are free slots in the task's group
+    /// - There are enough free slots in the task's group for the task's weight.
+    ///   Tasks that are heavier than the group's limit are started, once the group is empty.
     /// - The group is running
     /// - has all its dependencies in `Done` state
+    ///   (with a successful result, if the task should only run if its dependencies succeeded)
+    ///
+    /// If multiple tasks can be started, the one with the highest priority is picked.
+    /// Tasks with the same priority are started by their position in the queue.
     pub fn get_next_task_id(&mut self, state: &LockedState) -> Option<usize> {
         state
             .tasks
             .iter()
             .filter(|(_, task)| task.status == TaskStatus::Queued)
+            // Tasks that have been added with `--paused` are only started manually.
+            .filter(|(_, task)| !task.manual_start)
+            // Held tasks keep their position, but aren't started until they're released.
+            .filter(|(_, task)| !task.held)
+            // Tasks that have been added with `--immediate` are started via their start message.
+            // Otherwise, the scheduler might start them first and let them occupy a slot.
+            .filter(|(_, task)| !task.immediate)
+            // Tasks whose pre-task hook is still running are already being started.
+            .filter(|(id, _)| !self.pre_task_hooks.contains_key(id))
             .filter(|(_, task)| {
                 // Make sure the task is assigned to an existing group.
                 let group = match state.groups.get(&task.group) {
@@ -44,10 +74,17 @@ impl TaskHandler {
                     return false;
                 }
 
-                // Get the currently running tasks by looking at the actually running processes.
+                // Get the occupied slots by looking at the actually running processes.
                 // They're sorted by group, which makes this quite convenient.
-                let running_tasks = match self.children.0.get(&task.group) {
-                    Some(children) => children.len(),
+                // Each task occupies as many slots as its weight.
+                // Tasks that have been started immediately don't occupy a slot.
+                let occupied_slots: usize = match self.children.0.get(&task.group) {
+                    Some(children) => children
+                        .values()
+                        .filter_map(|(id, _)| state.tasks.get(id))
+                        .filter(|child_task| !child_task.immediate)
+                        .map(|child_task| child_task.weight)
+                        .sum(),
                     None => {
                         error!(
                             "Got valid group {}, but no worker pool has been initialized. This is a bug!",
@@ -57,16 +94,32 @@ impl TaskHandler {
                     }
                 };
 
-                // Make sure there are free slots in the task's group
-                running_tasks < group.parallel_tasks
+                // Tasks whose pre-task hook is still running already occupy their slots.
+                let occupied_slots: usize = occupied_slots
+                    + self
+                        .pre_task_hooks
+                        .keys()
+                        .filter_map(|id| state.tasks.get(id))
+                        .filter(|hook_task| hook_task.group == task.group && !hook_task.immediate)
+                        .map(|hook_task| hook_task.weight)
+                        .sum::<usize>();
+
+                // Make sure there are enough free slots in the task's group.
+                occupied_slots + task.weight <= group.parallel_tasks || occupied_slots == 0
             })
-            .find(|(_, task)| {
+            .filter(|(_, task)| {
                 // Check whether all dependencies for this task are fulfilled.
+                let only_if_success = task.only_if_success;
                 task.dependencies
                     .iter()
                     .flat_map(|id| state.tasks.get(id))
-                    .all(|task| matches!(task.status, TaskStatus::Done(TaskResult::Success)))
+                    .all(|task| match task.status {
+                        TaskStatus::Done(TaskResult::Success) => true,
+                        TaskStatus::Done(_) => !only_if_success,
+                        _ => false,
+                    })
             })
+            .max_by_key(|(id, task)| (task.priority, Reverse(task.order), Reverse(**id)))
             .map(|(id, _)| *id)
     }
 
@@ -74,7 +127,7 @@ impl TaskHandler {
     /// The output of subprocesses is piped into a seperate file for easier access
     pub fn start_process(&mut self, task_id: usize, state: &mut LockedState) {
         // Check if the task exists and can actually be spawned. Otherwise do an early return.
-        match state.tasks.get(&task_id) {
+        let group = match state.tasks.get(&task_id) {
             Some(task) => {
                 if !matches!(
                     &task.status,
@@ -83,6 +136,7 @@ impl TaskHandler {
                     info!("Tried to start task with status: {}", task.status);
                     return;
                 }
+                task.group.clone()
             }
             None => {
                 info!("Tried to start non-existing task: {task_id}");
@@ -90,82 +144,176 @@ impl TaskHandler {
             }
         };
 
+        // No new processes are spawned, once the daemon is shutting down.
+        if self.shutdown.is_some() {
+            info!("Won't start task {task_id}, as the daemon is shutting down");
+            return;
+        }
+
+        // The task is already waiting for its pre-task hook.
+        if self.pre_task_hooks.contains_key(&task_id) {
+            return;
+        }
+
+        // Run the group's pre-task hook first, if there's one.
+        // The task is started once the hook finished, without blocking the daemon in the meantime.
+        let hook_result = match self.finished_pre_task_hooks.remove(&task_id) {
+            Some(result) => result,
+            None if self.spawn_pre_task_hook(task_id, &group) => return,
+            None => self
+                .finished_pre_task_hooks
+                .remove(&task_id)
+                .unwrap_or(Ok(())),
+        };
+
         // Try to get the log file to which the output of the process will be written to.
         // Panic if this doesn't work! This is unrecoverable.
-        let (stdout_log, stderr_log) = match create_log_file_handles(task_id, &self.pueue_directory)
-        {
-            Ok((out, err)) => (out, err),
-            Err(err) => {
-                panic!("Failed to create child log files: {err:?}");
-            }
+        let (stdout_log, mut stderr_log) =
+            match create_log_file_handles(task_id, &self.pueue_directory) {
+                Ok((out, err)) => (out, err),
+                Err(err) => {
+                    panic!("Failed to create child log files: {err:?}");
+                }
+            };
+
+        // Write stderr to its own log file, if the user wants to keep both streams separate.
+        if self.settings.daemon.separate_stderr {
+            stderr_log = match create_stderr_log_file_handle(task_id, &self.pueue_directory) {
+                Ok(err) => err,
+                Err(err) => {
+                    panic!("Failed to create child stderr log file: {err:?}");
+                }
+            };
+        }
+
+        // Pipe the output through the daemon, if it should also be forwarded to journald or
+        // be timestamped. Otherwise, the process writes directly to its log files.
+        let pipe_options = PipeOptions {
+            journald: self.settings.daemon.log_to_journald,
+            timestamps: self.settings.daemon.log_timestamps,
+        };
+        let mut piped_logs = None;
+        let (stdout, stderr) = if pipe_options.journald || pipe_options.timestamps {
+            piped_logs = Some((stdout_log, stderr_log));
+            (Stdio::piped(), Stdio::piped())
+        } else {
+            (Stdio::from(stdout_log), Stdio::from(stderr_log))
         };
 
         // Get all necessary info for starting the task
-        let (command, path, group, mut envs) = {
+        let (command, path, mut envs, user, cpu_affinity, nice, shell) = {
             let task = state.tasks.get(&task_id).unwrap();
             (
                 task.command.clone(),
                 task.path.clone(),
-                task.group.clone(),
                 task.envs.clone(),
+                task.user.clone(),
+                task.cpu_affinity.clone(),
+                task.nice,
+                task.shell.clone(),
             )
         };
 
         // Build the shell command that should be executed.
-        let mut command = compile_shell_command(&command);
+        // The task's own shell takes precedence over the configured default shell.
+        let shell = shell.or_else(|| self.settings.daemon.shell_command.clone());
+        let mut command = match shell {
+            Some(shell) if !shell.is_empty() => compile_custom_shell_command(&shell, &command),
+            _ => compile_shell_command(&command),
+        };
 
         // Determine the worker's id depending on the current group.
         // Inject that info into the environment.
         let worker_id = self.children.get_next_group_worker(&group);
         envs.insert("PUEUE_GROUP".into(), group.clone());
         envs.insert("PUEUE_WORKER_ID".into(), worker_id.to_string());
+        command.env_clear().envs(envs.clone());
 
-        // Spawn the actual subprocess
-        let spawned_command = command
-            .current_dir(path)
-            .stdin(Stdio::piped())
-            .env_clear()
-            .envs(envs.clone())
-            .stdout(Stdio::from(stdout_log))
-            .stderr(Stdio::from(stderr_log))
-            .group_spawn();
+        // Drop the daemon's privileges to the task's user, if one has been specified.
+        // If the user cannot be resolved, the task fails to spawn instead of running as the daemon's user.
+        let user_result = match &user {
+            Some(user) => set_process_user(&mut command, user),
+            None => Ok(()),
+        };
+
+        // Pin the task to the requested CPU cores.
+        let affinity_result = match &cpu_affinity {
+            Some(cpus) => set_process_cpu_affinity(&mut command, cpus),
+            None => Ok(()),
+        };
+
+        // Lower (or raise) the scheduling priority of the task.
+        let nice_result = match nice {
+            Some(nice) => set_process_nice(&mut command, nice),
+            None => Ok(()),
+        };
+
+        // Spawn the actual subprocess, if the group's pre-task hook succeeded.
+        let spawned_command = hook_result
+            .and(user_result)
+            .and(affinity_result)
+            .and(nice_result)
+            .and_then(|_| {
+                command
+                    .current_dir(path)
+                    .stdin(Stdio::piped())
+                    .stdout(stdout)
+                    .stderr(stderr)
+                    .group_spawn()
+            });
 
         // Check if the task managed to spawn
-        let child = match spawned_command {
+        let mut child = match spawned_command {
             Ok(child) => child,
             Err(err) => {
-                let error = format!("Failed to spawn child {task_id} with err: {err:?}");
-                error!("{}", error);
+                error!("Failed to spawn child {task_id} with err: {err:?}");
                 clean_log_handles(task_id, &self.pueue_directory);
 
                 // Update all necessary fields on the task.
+                // The human-readable error is stored, so users can see why the task failed.
                 let group = {
                     let task = state.tasks.get_mut(&task_id).unwrap();
-                    task.status = TaskStatus::Done(TaskResult::FailedToSpawn(error));
+                    task.status = TaskStatus::Done(TaskResult::FailedToSpawn(err.to_string()));
                     task.start = Some(Local::now());
                     task.end = Some(Local::now());
                     self.spawn_callback(task);
+                    touch_done_marker(task);
 
                     task.group.clone()
                 };
 
-                pause_on_failure(state, &self.settings, &group);
+                self.handle_task_failure(state, &group);
                 ok_or_shutdown!(self, save_state(state, &self.settings));
                 return;
             }
         };
 
+        if let Some((stdout_log, stderr_log)) = piped_logs {
+            let handles = pipe_output(task_id, &mut child, stdout_log, stderr_log, pipe_options);
+            // The threads of a previous run might still be alive, if a background process of
+            // that run kept the pipes open.
+            self.output_pipes
+                .entry(task_id)
+                .or_default()
+                .extend(handles);
+        }
+
         // Save the process handle in our self.children datastructure.
         self.children.add_child(&group, worker_id, task_id, child);
 
         let task = state.tasks.get_mut(&task_id).unwrap();
         task.start = Some(Local::now());
         task.status = TaskStatus::Running;
+        // The task has been started, so it's no longer waiting for a manual start or release.
+        // Otherwise, it would have to be started manually again after a restart.
+        task.manual_start = false;
+        task.held = false;
         // Overwrite the task's environment variables with the new ones, containing the
         // PUEUE_WORKER_ID and PUEUE_GROUP variables.
         task.envs = envs;
 
         info!("Started task: {}", task.command);
+        self.spawn_start_callback(task);
         ok_or_shutdown!(self, save_state(state, &self.settings));
     }
 }
@@ -0,0 +1,33 @@
+use super::*;
+
+impl TaskHandler {
+    /// Kill all running tasks that exceeded their timeout.
+    ///
+    /// The ids of those tasks are remembered, so they can be marked as
+    /// [TaskResult::TimedOut] once their process has been handled in
+    /// [TaskHandler::handle_finished_tasks].
+    pub fn check_timeouts(&mut self) {
+        let timed_out: Vec<usize> = {
+            let state = self.state.lock().unwrap();
+            state
+                .tasks
+                .iter()
+                .filter(|(id, task)| {
+                    task.status == TaskStatus::Running && !self.timed_out_tasks.contains(id)
+                })
+                .filter_map(|(id, task)| {
+                    let timeout = task.timeout?;
+                    let start = task.start?;
+                    let timeout = chrono::Duration::seconds(timeout.try_into().ok()?);
+                    (start + timeout <= Local::now()).then_some(*id)
+                })
+                .collect()
+        };
+
+        for task_id in timed_out {
+            info!("Task {task_id} exceeded its timeout. Killing it.");
+            self.timed_out_tasks.insert(task_id);
+            self.kill_task(task_id);
+        }
+    }
+}
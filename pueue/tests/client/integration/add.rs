@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use pueue_lib::settings::TaskTemplate;
+use pueue_lib::task::{TaskResult, TaskStatus};
+
+use crate::client::helper::*;
+
+/// `add --after-last` depends on the most recent task of the same group.
+/// The task doesn't get any dependencies, if the group is empty.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn after_last() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+    add_group_with_slots(shared, "testgroup", 1).await?;
+
+    // The group is empty, so there's no dependency.
+    run_client_command(shared, &["add", "--stashed", "--after-last", "ls"])?;
+    // Add a task to a different group, which should be ignored.
+    run_client_command(shared, &["add", "--stashed", "--group", "testgroup", "ls"])?;
+    run_client_command(shared, &["add", "--stashed", "--after-last", "ls"])?;
+
+    let state = get_state(shared).await?;
+    assert!(state.tasks.get(&0).unwrap().dependencies.is_empty());
+    assert_eq!(state.tasks.get(&2).unwrap().dependencies, vec![0]);
+
+    Ok(())
+}
+
+/// `add --template` fills all options that aren't explicitly given from the template.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn template() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+    add_group_with_slots(shared, "testgroup", 1).await?;
+    add_group_with_slots(shared, "othergroup", 1).await?;
+
+    // Add a template to the client's configuration.
+    let mut settings = daemon.settings.clone();
+    settings.client.templates.insert(
+        "build".into(),
+        TaskTemplate {
+            command: "echo building".into(),
+            group: Some("testgroup".into()),
+            label: Some("build".into()),
+            envs: HashMap::from([("TEMPLATE_VAR".into(), "value".into())]),
+        },
+    );
+    settings.save(&Some(daemon.tempdir.path().join("pueue.yml")))?;
+
+    run_client_command(shared, &["add", "--stashed", "--template", "build"])?;
+    // Explicitly passed options take precedence over the template.
+    run_client_command(
+        shared,
+        &[
+            "add",
+            "--stashed",
+            "--template",
+            "build",
+            "--group",
+            "othergroup",
+            "ls",
+        ],
+    )?;
+
+    let state = get_state(shared).await?;
+    let task = state.tasks.get(&0).unwrap();
+    assert_eq!(task.command, "echo building");
+    assert_eq!(task.group, "testgroup");
+    assert_eq!(task.label, Some("build".into()));
+    assert_eq!(task.envs.get("TEMPLATE_VAR"), Some(&"value".to_string()));
+
+    let task = state.tasks.get(&1).unwrap();
+    assert_eq!(task.command, "ls");
+    assert_eq!(task.group, "othergroup");
+    assert_eq!(task.label, Some("build".into()));
+
+    // Unknown templates result in an error.
+    let result = run_client_command(shared, &["add", "--template", "unknown"]);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+/// `add --clone` copies the command, environment, path and group of an existing task.
+/// Explicitly passed options take precedence over the cloned task.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn clone() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+    add_group_with_slots(shared, "testgroup", 1).await?;
+    add_group_with_slots(shared, "othergroup", 1).await?;
+
+    let mut message = create_add_message(shared, "echo cloned");
+    message.stashed = true;
+    message.group = "testgroup".into();
+    message.path = daemon.tempdir.path().to_path_buf();
+    message
+        .envs
+        .insert("CLONED_VAR".into(), "cloned value".into());
+    assert_success(send_message(shared, message).await?);
+
+    run_client_command(shared, &["add", "--stashed", "--clone", "0"])?;
+    run_client_command(
+        shared,
+        &[
+            "add",
+            "--stashed",
+            "--clone",
+            "0",
+            "--group",
+            "othergroup",
+            "ls",
+        ],
+    )?;
+
+    let state = get_state(shared).await?;
+    let task = state.tasks.get(&1).unwrap();
+    assert_eq!(task.command, "echo cloned");
+    assert_eq!(task.group, "testgroup");
+    assert_eq!(task.path, daemon.tempdir.path());
+    assert_eq!(
+        task.envs.get("CLONED_VAR"),
+        Some(&"cloned value".to_string())
+    );
+
+    let task = state.tasks.get(&2).unwrap();
+    assert_eq!(task.command, "ls");
+    assert_eq!(task.group, "othergroup");
+    assert_eq!(
+        task.envs.get("CLONED_VAR"),
+        Some(&"cloned value".to_string())
+    );
+
+    // Unknown tasks can't be cloned.
+    let result = run_client_command(shared, &["add", "--clone", "10"]);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+/// The color of a label is stored on the task. Unknown colors are rejected by the client.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn label_color() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(
+        shared,
+        &["add", "--label", "deploy", "--label-color", "Red", "ls"],
+    )?;
+    let task = get_task(shared, 0).await?;
+    assert_eq!(task.label_color.as_deref(), Some("red"));
+
+    let result = run_client_command(
+        shared,
+        &["add", "--label", "deploy", "--label-color", "purple", "ls"],
+    );
+    assert!(result.is_err(), "Unknown colors should be rejected");
+
+    Ok(())
+}
+
+/// Variables passed via `--env` are added to the task's environment and override inherited ones.
+/// Malformed variables are rejected by the client.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn env() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(
+        shared,
+        &[
+            "add",
+            "--env",
+            "PUEUE_TEST=1",
+            "--env",
+            "HOME=/custom",
+            "ls",
+        ],
+    )?;
+    let task = get_task(shared, 0).await?;
+    assert_eq!(task.envs.get("PUEUE_TEST").map(String::as_str), Some("1"));
+    assert_eq!(task.envs.get("HOME").map(String::as_str), Some("/custom"));
+
+    let result = run_client_command(shared, &["add", "--env", "PUEUE_TEST", "ls"]);
+    assert!(result.is_err(), "Malformed variables should be rejected");
+
+    Ok(())
+}
+
+/// The shared secret is never captured from the client's environment.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn secret_is_not_captured() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let secret = std::fs::read_to_string(shared.shared_secret_path())?;
+    let envs = HashMap::from([("PUEUE_SECRET", secret.as_str())]);
+    run_client_command_with_env(shared, &["add", "ls"], envs)?;
+
+    let task = get_task(shared, 0).await?;
+    assert!(!task.envs.contains_key("PUEUE_SECRET"));
+
+    Ok(())
+}
+
+/// With `--no-inherit-env`, only the explicitly passed variables end up in the task's environment.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn no_inherit_env() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(
+        shared,
+        &["add", "--no-inherit-env", "--env", "PATH=/usr/bin", "ls"],
+    )?;
+    let task = get_task(shared, 0).await?;
+    assert!(task.no_inherit_env);
+    assert_eq!(
+        task.envs,
+        HashMap::from([("PATH".to_string(), "/usr/bin".to_string())])
+    );
+
+    Ok(())
+}
+
+/// Tasks with `--touch-on-done` create a marker file with their result as suffix.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn touch_on_done() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+    let marker = daemon.tempdir.path().join("marker");
+    let marker = marker.to_string_lossy();
+
+    run_client_command(shared, &["add", "--touch-on-done", &marker, "ls"])?;
+    run_client_command(shared, &["add", "--touch-on-done", &marker, "failing"])?;
+    for task_id in [0, 1] {
+        wait_for_task_condition(shared, task_id, |task| task.is_done()).await?;
+    }
+
+    assert!(daemon.tempdir.path().join("marker.success").exists());
+    assert!(daemon.tempdir.path().join("marker.failed").exists());
+
+    Ok(())
+}
+
+/// The marker file is also created for tasks that never ran, e.g. as their dependency failed.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn touch_on_done_dependency_failed() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+    let marker = daemon.tempdir.path().join("marker");
+    let marker = marker.to_string_lossy();
+
+    run_client_command(shared, &["add", "failing"])?;
+    run_client_command(
+        shared,
+        &["add", "--after", "0", "--touch-on-done", &marker, "ls"],
+    )?;
+    let task = wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::DependencyFailed));
+
+    assert!(daemon.tempdir.path().join("marker.failed").exists());
+
+    Ok(())
+}
+
+/// `--auto-label` labels the task with the first word of its command.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn auto_label() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(shared, &["add", "--auto-label", "--", "sleep 0.1 && ls"])?;
+    let task = get_task(shared, 0).await?;
+    assert_eq!(task.label.as_deref(), Some("sleep"));
+
+    Ok(())
+}
+
+/// `add --from-file` adds one task per line, while empty lines and comments are skipped.
+/// If any of the tasks is invalid, none of them are added.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn from_file() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+    add_group_with_slots(shared, "testgroup", 1).await?;
+
+    let file = daemon.tempdir.path().join("jobs.txt");
+    std::fs::write(&file, "# Some comment\necho first\n\n  echo second  \n")?;
+    let output = run_client_command(
+        shared,
+        &[
+            "add",
+            "--stashed",
+            "--group",
+            "testgroup",
+            "--from-file",
+            file.to_str().unwrap(),
+        ],
+    )?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("2 new tasks added (ids 0-1)."));
+
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.len(), 2);
+    assert_eq!(state.tasks.get(&0).unwrap().command, "echo first");
+    assert_eq!(state.tasks.get(&1).unwrap().command, "echo second");
+    assert_eq!(state.tasks.get(&1).unwrap().group, "testgroup");
+
+    // A dependency on a non-existing task fails the whole batch.
+    let result = run_client_command(
+        shared,
+        &[
+            "add",
+            "--after",
+            "10",
+            "--from-file",
+            file.to_str().unwrap(),
+        ],
+    );
+    assert!(result.is_err());
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.len(), 2);
+
+    Ok(())
+}
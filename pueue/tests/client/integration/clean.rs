@@ -0,0 +1,29 @@
+use anyhow::Result;
+
+use crate::client::helper::*;
+
+/// Test that a dry-run of `clean` only prints the finished tasks and doesn't remove them.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn clean_dry_run() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Add a task that succeeds and one that fails.
+    assert_success(add_task(shared, "ls", false).await?);
+    assert_success(add_task(shared, "failing", false).await?);
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+
+    let output = run_client_command(shared, &["clean", "--dry-run"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "The following tasks would be removed: 0, 1");
+
+    let output = run_client_command(shared, &["clean", "--dry-run", "--successful-only"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "The following tasks would be removed: 0");
+
+    // Nothing has actually been removed.
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.len(), 2);
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+
+use crate::client::helper::*;
+
+/// The hidden `complete` subcommand prints all task ids and groups that match the current word.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn complete_ids_and_groups() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    add_group_with_slots(shared, "completion", 1).await?;
+    for _ in 0..12 {
+        assert_success(add_task(shared, "ls", false).await?);
+    }
+
+    // All ids starting with `1` are returned.
+    let output = run_client_command(shared, &["complete", "bash", "1"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout, "1\n10\n11\n");
+
+    // Groups are completed as well.
+    let output = run_client_command(shared, &["complete", "bash", "compl"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout, "completion\n");
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use std::process::{Command, Stdio};
+
+use anyhow::{Context, Result};
+use assert_cmd::prelude::*;
+
+/// Make sure that the generated config schema is valid json and describes all config sections.
+#[test]
+fn config_schema_generation() -> Result<()> {
+    let output = Command::cargo_bin("pueue")?
+        .arg("generate-config-schema")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .context("Failed to run config schema generation")?;
+
+    assert!(
+        output.status.success(),
+        "Config schema generation didn't finish successfully."
+    );
+
+    let schema: serde_json::Value =
+        serde_json::from_slice(&output.stdout).context("Schema isn't valid json")?;
+    let properties = &schema["properties"];
+    for section in ["client", "daemon", "shared", "profiles"] {
+        assert!(
+            properties.get(section).is_some(),
+            "Section {section} is missing in schema: {schema}"
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anyhow::Result;
+
+use crate::client::helper::*;
+
+/// `pueue doctor` passes all checks for a running daemon.
+/// If the daemon cannot be reached, it fails with a non-zero exit code.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn doctor() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let output = run_client_command(shared, &["doctor"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("[pass] Configuration"));
+    assert!(stdout.contains("[pass] Pueue directory"));
+    assert!(stdout.contains("[pass] Daemon"));
+    assert!(!stdout.contains("[fail]"));
+
+    // Remove the socket, which makes the daemon unreachable.
+    std::fs::remove_file(shared.unix_socket_path())?;
+    let result = run_client_command(shared, &["doctor"]);
+    let error = result.expect_err("Doctor should fail without a reachable daemon");
+    assert!(error.to_string().contains("[fail] Daemon"));
+
+    Ok(())
+}
@@ -125,3 +125,32 @@ async fn fail_to_edit_task() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that the `--editor` flag takes precedence over the `$EDITOR` environment variable.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn edit_with_editor_flag() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Create a stashed message which we'll edit later on.
+    let mut message = create_add_message(shared, "this is a test");
+    message.stashed = true;
+    send_message(shared, message)
+        .await
+        .context("Failed to to add stashed task.")?;
+
+    // The editor of the environment variable shouldn't be used.
+    let mut envs = HashMap::new();
+    envs.insert("EDITOR", "non_existing_test_binary");
+    run_client_command_with_env(
+        shared,
+        &["edit", "--editor", "echo 'expected command string' > ", "0"],
+        envs,
+    )?;
+
+    let state = get_state(shared).await?;
+    let task = state.tasks.get(&0).unwrap();
+    assert_eq!(task.command, "expected command string");
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anyhow::{Context, Result};
+use rstest::rstest;
 
 use crate::client::helper::*;
 
@@ -75,3 +76,96 @@ async fn remote_with_last_lines() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that `follow --json` prints the output as JSON Lines, both for local and remote logs.
+#[rstest]
+#[case(true)]
+#[case(false)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn json(#[case] read_local_logs: bool) -> Result<()> {
+    let mut daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    daemon.settings.client.read_local_logs = read_local_logs;
+    daemon
+        .settings
+        .save(&Some(daemon.tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+
+    // Add a task and wait until it started.
+    assert_success(add_task(shared, "sleep 1 && echo test", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    let output = run_client_command(shared, &["follow", "--json"])?;
+
+    // Every line must be a valid JSON object and the chunks must add up to the full output.
+    let mut text = String::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let chunk: serde_json::Value = serde_json::from_str(line).context("Invalid JSON line")?;
+        assert_eq!(chunk["task_id"], 0);
+        assert_eq!(chunk["offset"], text.len());
+        text.push_str(chunk["chunk"].as_str().unwrap());
+    }
+    assert_eq!(text, "test\n");
+
+    Ok(())
+}
+
+/// Test that `follow --all` prints the output of all running tasks with their id as prefix,
+/// both for local and remote logs.
+#[rstest]
+#[case(true)]
+#[case(false)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn all(#[case] read_local_logs: bool) -> Result<()> {
+    let mut daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    daemon.settings.client.read_local_logs = read_local_logs;
+    daemon
+        .settings
+        .save(&Some(daemon.tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+
+    // Start two tasks, which finish at different times.
+    assert_success(add_task(shared, "sleep 1 && echo first", true).await?);
+    assert_success(add_task(shared, "sleep 2 && echo second", true).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+    wait_for_task_condition(shared, 1, |task| task.is_running()).await?;
+
+    let output = run_client_command(shared, &["follow", "--all"])?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, vec!["[0] first", "[1] second"]);
+
+    Ok(())
+}
+
+/// `log --follow-from-start` prints the existing output and then follows the task.
+/// No output may be duplicated or lost between both parts.
+#[rstest]
+#[case(true)]
+#[case(false)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn follow_from_start(#[case] read_local_logs: bool) -> Result<()> {
+    let mut daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    daemon.settings.client.read_local_logs = read_local_logs;
+    daemon
+        .settings
+        .save(&Some(daemon.tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+
+    // Add a task and wait until it printed its first line.
+    assert_success(add_task(shared, "echo first && sleep 1 && echo second", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+    sleep_ms(500).await;
+
+    let output = run_client_command(shared, &["log", "--follow-from-start", "0"])?;
+
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "first\nsecond\n");
+
+    Ok(())
+}
@@ -50,3 +50,18 @@ async fn colored() -> Result<()> {
 
     Ok(())
 }
+
+/// The `--color` flag can also be passed after the subcommand.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn colored_after_subcommand() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let output = run_client_command(shared, &["group", "--color", "always"])?;
+    assert!(output.stdout.contains(&0x1b), "Expected styled output");
+
+    let output = run_client_command(shared, &["group", "--color", "never"])?;
+    assert!(!output.stdout.contains(&0x1b), "Expected unstyled output");
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anyhow::Result;
+use pueue_lib::task::{TaskResult, TaskStatus};
+
+use crate::client::helper::*;
+
+/// Tasks that are added with `--job` can be inspected and killed together.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn job_status_and_kill() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // The first task of the job is started, the second one stays queued.
+    run_client_command(shared, &["add", "--job", "deploy", "sleep 60"])?;
+    run_client_command(shared, &["add", "--job", "deploy", "sleep 61"])?;
+    run_client_command(shared, &["add", "--stashed", "echo unrelated"])?;
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    let output = run_client_command(shared, &["job", "status", "deploy"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("sleep 60"), "Got output: {stdout}");
+    assert!(stdout.contains("sleep 61"), "Got output: {stdout}");
+    assert!(!stdout.contains("echo unrelated"), "Got output: {stdout}");
+
+    run_client_command(shared, &["job", "kill", "deploy"])?;
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert!(
+        matches!(task.status, TaskStatus::Done(TaskResult::KilledBySignal(_))),
+        "Got status: {:?}",
+        task.status
+    );
+    let task = wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::Skipped));
+
+    // Tasks outside of the job aren't touched.
+    let task = get_task(shared, 2).await?;
+    assert!(matches!(task.status, TaskStatus::Stashed { .. }));
+
+    // Unknown jobs result in an error.
+    let result = run_client_command(shared, &["job", "status", "unknown"]);
+    assert!(result.is_err());
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anyhow::Result;
+use pueue_lib::task::{TaskResult, TaskStatus};
+
+use crate::client::helper::*;
+
+/// Killing a task with `--with-dependents` also skips all tasks that (transitively) depend on it.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn kill_with_dependents() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    // Task 1 depends on task 0, task 2 depends on task 1.
+    for dependency in [0, 1] {
+        let mut message = create_add_message(shared, "ls");
+        message.dependencies = vec![dependency];
+        assert_success(send_message(shared, message).await?);
+    }
+    // This task doesn't depend on anything.
+    assert_success(add_task(shared, "ls", false).await?);
+
+    run_client_command(shared, &["kill", "--with-dependents", "0"])?;
+
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert!(task.failed());
+    for task_id in [1, 2] {
+        let task = wait_for_task_condition(shared, task_id, |task| task.is_done()).await?;
+        assert_eq!(task.status, TaskStatus::Done(TaskResult::Skipped));
+    }
+
+    // The unrelated task is started as usual.
+    let task = wait_for_task_condition(shared, 3, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::Success));
+
+    Ok(())
+}
+
+/// Signals can be specified by their upper-case name, e.g. `--signal SIGINT`.
+/// Unknown signals are rejected by the client.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn kill_with_signal() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    let result = run_client_command(shared, &["kill", "--signal", "SIGFOO", "0"]);
+    assert!(result.is_err(), "Unknown signals should be rejected");
+
+    run_client_command(shared, &["kill", "--signal", "SIGINT", "0"])?;
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert!(
+        matches!(
+            task.status,
+            TaskStatus::Done(TaskResult::KilledBySignal(2))
+                | TaskStatus::Done(TaskResult::Failed(130))
+        ),
+        "Task should have been interrupted, got {:?}",
+        task.status
+    );
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+
+use crate::client::helper::*;
+
+/// Test that tasks can be selected by a regular expression on their command.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remove_matching_tasks() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    for command in ["ls", "echo test", "ls -al"] {
+        assert_success(add_task(shared, command, false).await?);
+    }
+    wait_for_task_condition(shared, 2, |task| task.is_done()).await?;
+
+    run_client_command(shared, &["remove", "--matching", "^ls"])?;
+
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.keys().copied().collect::<Vec<_>>(), vec![1]);
+
+    Ok(())
+}
+
+/// Invalid regular expressions and patterns without matches result in an error.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn fail_on_invalid_or_unmatched_pattern() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "sleep 60", false).await?);
+
+    assert!(run_client_command(shared, &["kill", "--matching", "sleep ("]).is_err());
+    assert!(run_client_command(shared, &["kill", "--matching", "^ls"]).is_err());
+
+    // The task hasn't been touched.
+    let state = get_state(shared).await?;
+    assert!(!state.tasks.get(&0).unwrap().is_done());
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anyhow::Result;
+
+use crate::client::helper::*;
+
+/// `metrics` prints the amount of tasks and the group usage in the Prometheus text format.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn metrics() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "ls", false).await?);
+    assert_success(add_task(shared, "failing", false).await?);
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    wait_for_task_condition(shared, 2, |task| task.is_running()).await?;
+
+    let output = run_client_command(shared, &["metrics"])?;
+    let output = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = output.lines().collect();
+
+    for expected in [
+        "# TYPE pueue_tasks gauge",
+        "# TYPE pueue_tasks_completed gauge",
+        "# TYPE pueue_tasks_failed gauge",
+        r#"pueue_tasks{group="default",status="done"} 2"#,
+        r#"pueue_tasks{group="default",status="running"} 1"#,
+        r#"pueue_tasks{group="default",status="queued"} 0"#,
+        r#"pueue_tasks_completed{group="default"} 2"#,
+        r#"pueue_tasks_failed{group="default"} 1"#,
+        r#"pueue_group_parallel_tasks{group="default"} 1"#,
+        r#"pueue_group_running_tasks{group="default"} 1"#,
+    ] {
+        assert!(
+            lines.contains(&expected),
+            "Missing line {expected} in:\n{output}"
+        );
+    }
+
+    Ok(())
+}
@@ -1,8 +1,23 @@
+mod add;
+mod clean;
+mod complete;
 mod completions;
+mod config_schema;
+mod doctor;
 mod edit;
 mod follow;
 mod group;
+mod job;
+mod kill;
 mod log;
+mod matching;
+mod metrics;
+mod note;
+mod parallel;
+mod remove;
 mod restart;
+mod send;
+mod socket;
+mod stash;
 mod status;
 mod wait;
@@ -0,0 +1,35 @@
+use anyhow::Result;
+
+use crate::client::helper::*;
+
+/// Notes can be set and removed at any time and are shown in the status.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn set_and_remove_note() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    run_client_command(shared, &["note", "0", "flaky, investigate"])?;
+    let task = get_task(shared, 0).await?;
+    assert_eq!(task.note, Some("flaky, investigate".to_string()));
+
+    let output = run_client_command(shared, &["status"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Note"), "Got output: {stdout}");
+    assert!(
+        stdout.contains("flaky, investigate"),
+        "Got output: {stdout}"
+    );
+
+    run_client_command(shared, &["note", "0"])?;
+    let task = get_task(shared, 0).await?;
+    assert_eq!(task.note, None);
+
+    // Notes can't be set on unknown tasks.
+    let result = run_client_command(shared, &["note", "1", "missing"]);
+    assert!(result.is_err());
+
+    Ok(())
+}
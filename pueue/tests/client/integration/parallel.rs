@@ -0,0 +1,27 @@
+use anyhow::Result;
+
+use crate::client::helper::*;
+
+/// Setting the parallel tasks of a group echoes the old and new limit.
+/// Calling `parallel` without a number prints the current limit of the group.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn set_and_query_parallel_tasks() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(shared, &["group", "add", "testgroup", "--parallel=2"])?;
+    wait_for_group(shared, "testgroup").await?;
+
+    let output = run_client_command(shared, &["parallel", "--group", "testgroup", "4"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("adjusted from 2 to 4"),
+        "Expected old and new limit in output, got: {stdout}"
+    );
+
+    let output = run_client_command(shared, &["parallel", "--group", "testgroup"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), "Group \"testgroup\" allows 4 parallel tasks");
+
+    Ok(())
+}
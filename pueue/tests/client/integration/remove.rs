@@ -0,0 +1,38 @@
+use anyhow::Result;
+
+use crate::client::helper::*;
+
+/// `remove --before` and `remove --older-than` only remove finished tasks.
+/// Both can be limited to a single group.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn remove_finished_tasks() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+    add_group_with_slots(shared, "testgroup", 1).await?;
+
+    assert_success(add_task(shared, "ls", false).await?);
+    assert_success(add_task_to_group(shared, "ls", "testgroup").await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    wait_for_task_condition(shared, 2, |task| task.is_running()).await?;
+
+    // None of the tasks finished more than an hour ago.
+    let result = run_client_command(shared, &["remove", "--older-than", "1h"]);
+    assert!(result.is_err());
+
+    // Only the finished task of the given group is removed.
+    run_client_command(
+        shared,
+        &["remove", "--older-than", "0", "--group", "testgroup"],
+    )?;
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.keys().copied().collect::<Vec<_>>(), vec![0, 2]);
+
+    // The running task is never removed.
+    run_client_command(shared, &["remove", "--before", "3"])?;
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.keys().copied().collect::<Vec<_>>(), vec![2]);
+
+    Ok(())
+}
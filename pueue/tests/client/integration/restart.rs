@@ -147,3 +147,63 @@ async fn normal_restart_with_edit() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that restarting a task with a different working directory works as expected.
+/// Non-existing directories are rejected by the daemon.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn restart_with_working_directory() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Create a task and wait for it to finish.
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    // Restart the task in place in a different directory.
+    run_client_command(shared, &["restart", "--in-place", "-w", "/tmp", "0"])?;
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.get(&0).unwrap().path.to_string_lossy(), "/tmp");
+
+    // Restart a copy of the task in yet another directory.
+    let path = shared.pueue_directory();
+    let path = path.to_str().unwrap();
+    run_client_command(shared, &["restart", "-w", path, "0"])?;
+    let task = wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    assert_eq!(task.path.to_string_lossy(), path);
+
+    // Restarts with a non-existing directory fail for both modes.
+    let result = run_client_command(shared, &["restart", "-i", "-w", "/does/not/exist", "0"]);
+    assert!(result.is_err(), "In-place restart should be rejected");
+    let result = run_client_command(shared, &["restart", "-w", "/does/not/exist", "0"]);
+    assert!(result.is_err(), "Restart should be rejected");
+
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.len(), 2, "No new task should have been added");
+    assert_eq!(state.tasks.get(&0).unwrap().path.to_string_lossy(), "/tmp");
+
+    Ok(())
+}
+
+/// `--all-failed --group` only restarts the failed tasks of the given group.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn restart_all_failed_of_group() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(shared, &["group", "add", "build"])?;
+    run_client_command(shared, &["add", "failing"])?;
+    run_client_command(shared, &["add", "--group", "build", "failing"])?;
+    for task_id in [0, 1] {
+        wait_for_task_condition(shared, task_id, |task| task.is_done()).await?;
+    }
+
+    run_client_command(shared, &["restart", "--all-failed", "--group", "build"])?;
+
+    // Only the failed task of the `build` group has been restarted as a new task.
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.len(), 3);
+    assert_eq!(state.tasks.get(&2).unwrap().group, "build");
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::fs::File;
+use std::io::Write;
+
+use anyhow::Result;
+
+use crate::client::helper::*;
+
+/// Test that the content of a file can be sent to a task's stdin.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn send_stdin_file() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let input_path = daemon.tempdir.path().join("input.txt");
+    let mut input_file = File::create(&input_path)?;
+    input_file.write_all(b"first\nsecond\n")?;
+
+    // The task reads two lines from its stdin and prints them.
+    assert_success(add_task(shared, "head -n 2", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    run_client_command(
+        shared,
+        &["send", "0", "--stdin-file", input_path.to_str().unwrap()],
+    )?;
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    let log = get_task_log(shared, 0, None).await?;
+    assert_eq!(log, "first\nsecond\n");
+
+    Ok(())
+}
+
+/// Test that the same input can be sent to multiple tasks at once.
+/// Tasks that aren't running are skipped.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn send_to_multiple_tasks() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // The first task finishes right away, so it can't receive any input.
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    // Both receiving tasks have to run at the same time.
+    run_client_command(shared, &["parallel", "2"])?;
+    for id in 1..3 {
+        assert_success(add_task(shared, "head -n 1", false).await?);
+        wait_for_task_condition(shared, id, |task| task.is_running()).await?;
+    }
+
+    let output = run_client_command(shared, &["send", "0,1,2", "hello\n"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("The command failed for tasks: 0"),
+        "The finished task should be reported as skipped: {stdout}"
+    );
+
+    for id in 1..3 {
+        wait_for_task_condition(shared, id, |task| task.is_done()).await?;
+        let log = get_task_log(shared, id, None).await?;
+        assert_eq!(log, "hello\n");
+    }
+
+    Ok(())
+}
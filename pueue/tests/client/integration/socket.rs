@@ -0,0 +1,27 @@
+use anyhow::{Context, Result};
+
+use crate::client::helper::*;
+
+/// An explicitly specified socket takes precedence over the socket in the config file.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn socket_override() -> Result<()> {
+    let mut daemon = daemon().await?;
+    let socket_path = daemon.settings.shared.unix_socket_path();
+
+    // Point the config to a socket that doesn't exist.
+    daemon.settings.shared.unix_socket_path = Some(daemon.tempdir.path().join("missing.socket"));
+    daemon
+        .settings
+        .save(&Some(daemon.tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+    let shared = &daemon.settings.shared;
+
+    assert!(run_client_command(shared, &["status"]).is_err());
+
+    run_client_command(
+        shared,
+        &["--socket", socket_path.to_str().unwrap(), "status"],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+use chrono::{Duration, Local};
+use pueue_lib::task::TaskStatus;
+
+use crate::client::helper::*;
+
+/// `stash --until` stashes a task and schedules it to be enqueued again.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn stash_until() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // The first task occupies the only slot, so the second task stays queued.
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    run_client_command(shared, &["stash", "1", "--until", "in 2 hours"])?;
+
+    let task = get_task(shared, 1).await?;
+    let TaskStatus::Stashed {
+        enqueue_at: Some(enqueue_at),
+    } = task.status
+    else {
+        panic!(
+            "Expected the task to be stashed with an enqueue time, got {:?}",
+            task.status
+        );
+    };
+    assert!(enqueue_at > Local::now() + Duration::minutes(119));
+    assert!(enqueue_at <= Local::now() + Duration::hours(2));
+
+    Ok(())
+}
+
+/// `start --stashed` enqueues all stashed tasks of a group, including delayed tasks.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn start_stashed() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(shared, &["add", "--stashed", "ls"])?;
+    run_client_command(shared, &["add", "--delay", "2 hours", "ls"])?;
+
+    run_client_command(shared, &["start", "--stashed"])?;
+
+    for task_id in [0, 1] {
+        wait_for_task_condition(shared, task_id, |task| task.is_done()).await?;
+    }
+
+    Ok(())
+}
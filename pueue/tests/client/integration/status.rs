@@ -1,6 +1,7 @@
 use anyhow::Context;
 use anyhow::Result;
 use pueue_lib::state::State;
+use pueue_lib::task::Task;
 
 use crate::client::helper::*;
 
@@ -129,3 +130,300 @@ async fn json() -> Result<()> {
 
     Ok(())
 }
+
+/// Calling `pueue status --format json-v1` prints the state in the stable api representation.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn json_v1() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Add a task and wait until it finishes.
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    let output = run_client_command(shared, &["status", "--format", "json-v1"])?;
+
+    let json = String::from_utf8_lossy(&output.stdout);
+    let status: serde_json::Value =
+        serde_json::from_str(&json).context("Failed to deserialize json status")?;
+
+    assert_eq!(status["schema_version"], 1);
+    assert_eq!(status["groups"]["default"]["status"], "running");
+    assert_eq!(status["tasks"][0]["id"], 0);
+    assert_eq!(status["tasks"][0]["status"], "done");
+    assert_eq!(status["tasks"][0]["result"], "success");
+    assert_eq!(status["tasks"][0]["exit_code"], 0);
+
+    Ok(())
+}
+
+/// Calling `pueue status <id> --single` only requests and prints that single task.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn single() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "echo first", false).await?);
+    assert_success(add_task(shared, "echo second", false).await?);
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+
+    let output = run_client_command(shared, &["status", "1", "--single", "--json"])?;
+    let json = String::from_utf8_lossy(&output.stdout);
+    let task: Task = serde_json::from_str(&json).context("Failed to deserialize json task")?;
+    assert_eq!(task, get_task(shared, 1).await?);
+
+    // The table only contains the requested task.
+    let output = run_client_command(shared, &["status", "1", "--single"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("echo second"));
+    assert!(!stdout.contains("echo first"));
+
+    // Unknown tasks result in an error.
+    let result = run_client_command(shared, &["status", "10", "--single"]);
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+/// `pueue status --since/--until` only shows tasks that were running during that time window.
+/// Running tasks are included, while tasks that never started are not.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn time_range() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    wait_for_task_condition(shared, 1, |task| task.is_running()).await?;
+    let mut message = create_add_message(shared, "ls");
+    message.stashed = true;
+    assert_success(send_message(shared, message).await?);
+
+    let output = run_client_command(
+        shared,
+        &["status", "--since", "1h", "--until", "now", "--json"],
+    )?;
+    let state: State = serde_json::from_str(&String::from_utf8_lossy(&output.stdout))
+        .context("Failed to deserialize json state")?;
+    assert_eq!(state.tasks.keys().copied().collect::<Vec<_>>(), vec![0, 1]);
+
+    // No task ran before an hour ago.
+    let output = run_client_command(shared, &["status", "--until", "1h", "--json"])?;
+    let state: State = serde_json::from_str(&String::from_utf8_lossy(&output.stdout))
+        .context("Failed to deserialize json state")?;
+    assert!(state.tasks.is_empty());
+
+    Ok(())
+}
+
+/// Calling `pueue status --preset` will only show the columns defined in the preset.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn preset() -> Result<()> {
+    let mut daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Define a preset and persist it, so it can be seen by the client.
+    daemon.settings.client.column_presets.insert(
+        "minimal".to_string(),
+        vec!["id".to_string(), "status".to_string()],
+    );
+    daemon
+        .settings
+        .save(&Some(daemon.tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    let output = run_client_command(shared, &["status", "--preset", "minimal"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Status"), "Status column should be shown");
+    assert!(
+        !stdout.contains("Command"),
+        "Command column should be hidden"
+    );
+
+    // Unknown presets result in an error.
+    let result = run_client_command(shared, &["status", "--preset", "unknown"]);
+    assert!(result.is_err(), "Unknown presets should fail");
+
+    Ok(())
+}
+
+/// `pueue status --group-summary` prints the amount of tasks per status for each group.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn group_summary() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    add_group_with_slots(shared, "testgroup", 2).await?;
+
+    // A successful and a failed task in the default group.
+    run_client_command(shared, &["add", "ls"])?;
+    run_client_command(shared, &["add", "false"])?;
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    // A stashed task in the testgroup.
+    run_client_command(shared, &["add", "--stashed", "--group", "testgroup", "ls"])?;
+
+    let output = run_client_command(shared, &["status", "--group-summary"])?;
+    assert_snapshot_matches_stdout("status__group_summary", output.stdout)?;
+
+    Ok(())
+}
+
+/// `pueue status --group-by-label` shows a table for each label.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn group_by_label() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(shared, &["add", "--stashed", "--label", "build", "ls"])?;
+    run_client_command(shared, &["add", "--stashed", "ls"])?;
+    run_client_command(shared, &["add", "--stashed", "--label", "alpha", "ls"])?;
+
+    let output = run_client_command(shared, &["status", "--group-by-label"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Labels are sorted alphabetically, unlabeled tasks come last.
+    let alpha = stdout
+        .find("Label \"alpha\"")
+        .context("Missing alpha label")?;
+    let build = stdout
+        .find("Label \"build\"")
+        .context("Missing build label")?;
+    let unlabeled = stdout
+        .find("Unlabeled")
+        .context("Missing unlabeled tasks")?;
+    assert!(alpha < build && build < unlabeled);
+    assert!(!stdout.contains("Group \"default\""));
+
+    Ok(())
+}
+
+/// With `client.status_relative_time`, recent start and end times are shown relative to now.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn relative_time() -> Result<()> {
+    let mut daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    daemon.settings.client.status_relative_time = true;
+    daemon
+        .settings
+        .save(&Some(daemon.tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    let output = run_client_command(shared, &["status"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("s ago"), "Got output: {stdout}");
+
+    Ok(())
+}
+
+/// The border style of the status table can be changed via `client.table_style`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn ascii_table_style() -> Result<()> {
+    let mut daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    daemon.settings.client.table_style = "ascii".to_string();
+    daemon
+        .settings
+        .save(&Some(daemon.tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    let output = run_client_command(shared, &["status"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("+---"), "Got output: {stdout}");
+    assert!(!stdout.contains('─'), "Got output: {stdout}");
+
+    Ok(())
+}
+
+/// Calling `pueue status --format line` prints one tab-separated line per task.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn line_format() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+    add_group_with_slots(shared, "testgroup", 1).await?;
+
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert_success(add_task_to_group(shared, "sleep 60", "testgroup").await?);
+    wait_for_task_condition(shared, 1, |task| task.is_running()).await?;
+
+    let output = run_client_command(shared, &["status", "--format", "line"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        lines,
+        vec!["0\tSuccess\tdefault\tls", "1\tRunning\ttestgroup\tsleep 60"]
+    );
+
+    // The group filter is respected.
+    let output = run_client_command(
+        shared,
+        &["status", "--format", "line", "--group", "testgroup"],
+    )?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim_end(), "1\tRunning\ttestgroup\tsleep 60");
+
+    Ok(())
+}
+
+/// Task ids are rendered with the `shared.id_prefix` of the daemon.
+/// The client's local configuration doesn't matter.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn id_prefix() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.shared.id_prefix = Some("work".to_string());
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let mut daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    daemon.settings.shared.id_prefix = Some("local".to_string());
+    daemon
+        .settings
+        .save(&Some(daemon.tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+    let shared = &daemon.settings.shared;
+
+    let output = run_client_command(shared, &["status", "--format", "line"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim_end(), "work-0\tSuccess\tdefault\tls");
+
+    let output = run_client_command(shared, &["log", "0"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Task work-0: "), "Got output: {stdout}");
+
+    Ok(())
+}
+
+/// Held tasks and tasks that wait for a manual start are marked differently.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn held_and_paused() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Pause the group, so the task cannot be started before it's held.
+    run_client_command(shared, &["pause"])?;
+    run_client_command(shared, &["add", "ls"])?;
+    run_client_command(shared, &["hold", "0"])?;
+    run_client_command(shared, &["add", "--paused", "ls"])?;
+
+    let output = run_client_command(shared, &["status"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Queued (held)"), "Got output: {stdout}");
+    assert!(stdout.contains("Queued (paused)"), "Got output: {stdout}");
+
+    Ok(())
+}
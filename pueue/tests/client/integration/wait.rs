@@ -1,9 +1,10 @@
 use std::{
-    process::Output,
+    process::{Command, Output, Stdio},
     thread::{self, JoinHandle},
 };
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use assert_cmd::prelude::*;
 use tokio::time::sleep;
 
 use crate::client::helper::*;
@@ -111,3 +112,65 @@ async fn single_task_target_status() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that `wait --status failed` exits with a non-zero code as soon as a task fails.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn failed_target_status() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(shared, &["add", "sleep 1 && failing_command"])?;
+    run_client_command(shared, &["add", "sleep 20"])?;
+
+    let wait_handle = spawn_wait_client(shared, vec!["wait", "--status", "failed"]).await;
+
+    // The client has to exit early, even though task 1 hasn't finished yet.
+    let result = wait_handle.join().unwrap();
+    assert!(result.is_err());
+
+    Ok(())
+}
+
+/// Test that `wait --status success` exits normally, if all tasks finish successfully.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn success_target_status() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(shared, &["add", "sleep 1"])?;
+
+    let wait_handle = spawn_wait_client(shared, vec!["wait", "--status", "success"]).await;
+
+    wait_handle.join().unwrap()?;
+
+    Ok(())
+}
+
+/// Test that `wait --timeout` stops waiting, if the tasks don't finish in time.
+/// Timeouts are signaled with exit code 2, to distinguish them from failed tasks.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn timeout() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    run_client_command(shared, &["add", "sleep 20"])?;
+
+    let output = Command::cargo_bin("pueue")?
+        .arg("--config")
+        .arg(shared.pueue_directory().join("pueue.yml"))
+        .args(["wait", "--timeout", "1"])
+        .current_dir(shared.pueue_directory())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .output()
+        .context("Failed to run the wait command")?;
+
+    assert_eq!(output.status.code(), Some(2), "Wait should have timed out");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Timed out while waiting for tasks"),
+        "Got stderr: {stderr}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use chrono::{Duration, Local, NaiveTime};
+
+use pueue::client::cli::parse_delay_until;
+
+#[test]
+fn relative_expression() {
+    let before = Local::now();
+    let delay_until = parse_delay_until("in 2 hours").expect("Failed to parse expression");
+
+    assert!(delay_until >= before + Duration::hours(2));
+    assert!(delay_until <= Local::now() + Duration::hours(2));
+}
+
+/// A plain time of the day refers to its next occurrence.
+#[test]
+fn time_of_day() {
+    let now = Local::now();
+    let delay_until = parse_delay_until("18:00").expect("Failed to parse expression");
+
+    assert_eq!(
+        delay_until.time(),
+        NaiveTime::from_hms_opt(18, 0, 0).unwrap()
+    );
+    assert!(delay_until > now);
+    assert!(delay_until <= now + Duration::days(1));
+}
+
+#[test]
+fn tomorrow_with_time() {
+    let tomorrow = Local::now().naive_local().date() + Duration::days(1);
+    let delay_until = parse_delay_until("tomorrow 9am").expect("Failed to parse expression");
+
+    assert_eq!(delay_until.naive_local().date(), tomorrow);
+    assert_eq!(
+        delay_until.time(),
+        NaiveTime::from_hms_opt(9, 0, 0).unwrap()
+    );
+}
+
+#[test]
+fn invalid_expression() {
+    let error = parse_delay_until("when pigs fly").expect_err("Expression should be invalid");
+
+    assert!(
+        error.contains("Accepted formats"),
+        "Unexpected error: {error}"
+    );
+}
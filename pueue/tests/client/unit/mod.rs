@@ -1 +1,2 @@
+mod delay;
 mod status_query;
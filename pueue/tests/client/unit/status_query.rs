@@ -102,6 +102,18 @@ async fn column_selection() -> Result<()> {
     Ok(())
 }
 
+/// The resource usage column can be selected explicitly.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn column_selection_resources() -> Result<()> {
+    let result = apply_query("columns=id,resources")?;
+    assert_eq!(
+        result.selected_columns,
+        [Rule::column_id, Rule::column_resources]
+    );
+
+    Ok(())
+}
+
 /// Select the first few entries of the list
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn limit_first() -> Result<()> {
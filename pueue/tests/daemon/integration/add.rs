@@ -1,7 +1,10 @@
-use anyhow::Result;
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
 
 use chrono::Local;
-use pueue_lib::network::message::TaskSelection;
+use pueue_lib::network::message::{create_success_message, Message, TaskSelection};
+use pueue_lib::settings::GroupSettings;
 use pueue_lib::task::*;
 
 use crate::helper::*;
@@ -84,3 +87,211 @@ async fn test_add_with_immediate_start() -> Result<()> {
 
     Ok(())
 }
+
+/// Tasks that should run as a non-existing user fail to spawn instead of running as the daemon's user.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_add_with_unknown_user() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "sleep 0.01");
+    message.user = Some("pueue_user_that_does_not_exist".into());
+    assert_success(send_message(shared, message).await?);
+
+    // The reason is stored on the task, so it can be shown to the user.
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    let TaskStatus::Done(TaskResult::FailedToSpawn(err)) = task.status else {
+        bail!("Expected the task to fail to spawn, got {:?}", task.status);
+    };
+    assert!(err.contains("doesn't exist"), "Got error: {err}");
+
+    Ok(())
+}
+
+/// Tasks with a CPU affinity are pinned to the requested CPU cores.
+#[cfg(target_os = "linux")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_add_with_cpu_affinity() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "grep Cpus_allowed_list /proc/self/status");
+    message.cpu_affinity = Some(vec![0]);
+    assert_success(send_message(shared, message).await?);
+
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::Success));
+
+    let log = get_task_log(shared, 0, None).await?;
+    assert_eq!(log.trim(), "Cpus_allowed_list:\t0");
+
+    Ok(())
+}
+
+/// Tasks with a nice value are run with that scheduling priority.
+/// Values that are out of range are clamped.
+#[cfg(target_os = "linux")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_add_with_nice() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "nice");
+    message.nice = Some(10);
+    assert_success(send_message(shared, message).await?);
+
+    let mut message = create_add_message(shared, "nice");
+    message.nice = Some(100);
+    assert_success(send_message(shared, message).await?);
+
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+
+    assert_eq!(get_task_log(shared, 0, None).await?.trim(), "10");
+    assert_eq!(get_task_log(shared, 1, None).await?.trim(), "19");
+
+    Ok(())
+}
+
+/// Tasks are executed with the configured shell, unless they specify their own shell.
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_add_with_shell() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.shell_command = Some(vec!["bash".into(), "-c".into()]);
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    // `$0` is the name of the shell that executes the command.
+    assert_success(add_task(shared, "echo $0", false).await?);
+
+    let mut message = create_add_message(shared, "echo $0");
+    message.shell = Some(vec!["sh".into(), "-c".into()]);
+    assert_success(send_message(shared, message).await?);
+
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+
+    assert_eq!(get_task_log(shared, 0, None).await?.trim(), "bash");
+    assert_eq!(get_task_log(shared, 1, None).await?.trim(), "sh");
+
+    Ok(())
+}
+
+/// Tasks without an explicit working directory use the working directory of their group.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_add_with_group_working_directory() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    let group_directory = tempdir.path().join("group_directory");
+    std::fs::create_dir(&group_directory)?;
+    settings.daemon.group_settings.insert(
+        "test_2".into(),
+        GroupSettings {
+            working_directory: Some(group_directory.clone()),
+            ..Default::default()
+        },
+    );
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    // The group's directory is only used, if the path is the client's default.
+    let mut message = create_add_message(shared, "sleep 60");
+    message.group = "test_2".into();
+    message.stashed = true;
+    message.path_is_default = true;
+    assert_success(send_message(shared, message.clone()).await?);
+
+    message.path_is_default = false;
+    assert_success(send_message(shared, message).await?);
+
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.get(&0).unwrap().path, group_directory);
+    assert_eq!(state.tasks.get(&1).unwrap().path, shared.pueue_directory());
+
+    Ok(())
+}
+
+/// The environment variables of a group are injected into its tasks.
+/// Variables that are set on the task itself take precedence.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_add_with_group_env() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.group_settings.insert(
+        "test_2".into(),
+        GroupSettings {
+            env: HashMap::from([
+                ("CARGO_TERM_COLOR".to_string(), "always".to_string()),
+                ("OVERRIDDEN".to_string(), "group".to_string()),
+            ]),
+            ..Default::default()
+        },
+    );
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "sleep 60");
+    message.group = "test_2".into();
+    message.stashed = true;
+    message.envs.insert("OVERRIDDEN".into(), "task".into());
+    assert_success(send_message(shared, message).await?);
+
+    let task = get_task(shared, 0).await?;
+    assert_eq!(
+        task.envs.get("CARGO_TERM_COLOR"),
+        Some(&"always".to_string())
+    );
+    assert_eq!(task.envs.get("OVERRIDDEN"), Some(&"task".to_string()));
+
+    Ok(())
+}
+
+/// Adding a task with an already existing idempotency key returns the id of the existing task.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_add_with_idempotency_key() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "sleep 60");
+    message.stashed = true;
+    message.print_task_id = true;
+    message.idempotency_key = Some("abc".into());
+    let response = send_message(shared, message.clone()).await?;
+    assert_eq!(response, create_success_message("0"));
+
+    // The same key doesn't create a new task.
+    let response = send_message(shared, message.clone()).await?;
+    assert_eq!(response, create_success_message("0"));
+
+    // A different key does.
+    message.idempotency_key = Some("def".into());
+    let response = send_message(shared, message.clone()).await?;
+    assert_eq!(response, create_success_message("1"));
+
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.len(), 2);
+    assert_eq!(
+        state.tasks.get(&0).unwrap().idempotency_key,
+        Some("abc".to_string())
+    );
+
+    // Batches skip tasks whose key already exists.
+    let mut new_message = message.clone();
+    new_message.idempotency_key = Some("ghi".into());
+    message.idempotency_key = Some("abc".into());
+    let batch = Message::AddBatch(vec![message.clone(), new_message.clone()]);
+    let response = send_message(shared, batch).await?;
+    assert_eq!(response, create_success_message("0\n2"));
+
+    // Keys have to be unique within a batch.
+    new_message.idempotency_key = Some("jkl".into());
+    let batch = Message::AddBatch(vec![new_message.clone(), new_message]);
+    assert_failure(send_message(shared, batch).await?);
+
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.len(), 3);
+
+    Ok(())
+}
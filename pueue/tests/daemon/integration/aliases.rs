@@ -68,6 +68,7 @@ async fn test_restart_with_alias() -> Result<()> {
         }],
         start_immediately: true,
         stashed: false,
+        working_directory: None,
     };
     send_message(shared, message).await?;
     let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
@@ -84,3 +85,31 @@ async fn test_restart_with_alias() -> Result<()> {
 
     Ok(())
 }
+
+/// Placeholders in aliases are replaced by the respective arguments.
+/// Missing arguments result in an error.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_add_with_alias_placeholders() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let mut aliases = HashMap::new();
+    aliases.insert("deploy".into(), "echo {{1}} --env {{2}} {1..2}".into());
+    create_test_alias_file(daemon.tempdir.path(), aliases)?;
+
+    // Unused arguments are appended and shell brace expansions are left untouched.
+    assert_success(add_task(shared, "deploy app prod --force", false).await?);
+    let task = get_task(shared, 0).await?;
+    assert_eq!(task.command, "echo app --env prod {1..2} --force");
+    assert_eq!(task.original_command, "deploy app prod --force");
+
+    // Quoted arguments are kept as a single argument.
+    assert_success(add_task(shared, "deploy 'my app' prod", false).await?);
+    let task = get_task(shared, 1).await?;
+    assert_eq!(task.command, "echo 'my app' --env prod {1..2}");
+
+    // The second argument is missing.
+    assert_failure(add_task(shared, "deploy app", false).await?);
+
+    Ok(())
+}
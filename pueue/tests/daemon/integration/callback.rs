@@ -0,0 +1,117 @@
+use std::fs::read_to_string;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+
+use anyhow::{Context, Result};
+
+use crate::helper::*;
+
+/// Make sure that the start callback is executed with the correct parameters,
+/// as soon as a task starts running.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_start_callback() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    let callback_file = tempdir.path().join("start_callback");
+    settings.daemon.start_callback = Some(format!(
+        "echo '{{{{id}}}} {{{{group}}}} {{{{command}}}}' > {}",
+        callback_file.to_string_lossy()
+    ));
+    settings
+        .save(&Some(tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "sleep 60", true).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    // The callback runs asynchronously, so give it some time to finish.
+    let mut tries = 0;
+    while !callback_file.exists() && tries < 20 {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        tries += 1;
+    }
+
+    let content = read_to_string(&callback_file).context("Start callback didn't run")?;
+    assert_eq!(content, "0 default sleep 60\n");
+
+    Ok(())
+}
+
+/// Make sure that the details of a finished task are posted to the callback webhook.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_callback_webhook() -> Result<()> {
+    // A minimal HTTP server, which accepts a single request and returns its content.
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let address = listener.local_addr()?;
+    let server = std::thread::spawn(move || -> Result<String> {
+        let (mut stream, _) = listener.accept()?;
+        let mut request = Vec::new();
+        let mut buffer = [0; 4096];
+        // Read until the JSON body has been fully received.
+        while !request.ends_with(b"}") {
+            let read = stream.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&buffer[..read]);
+        }
+        stream.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")?;
+
+        Ok(String::from_utf8_lossy(&request).to_string())
+    });
+
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.callback_webhook_url = Some(format!("http://{address}/callback"));
+    settings
+        .save(&Some(tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "echo test", true).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    let request = server.join().unwrap()?;
+    assert!(request.starts_with("POST /callback"));
+    assert!(request.contains(r#""id":0"#));
+    assert!(request.contains(r#""command":"echo test""#));
+    assert!(request.contains(r#""result":"Success""#));
+    assert!(request.contains(r#""exit_code":0"#));
+
+    Ok(())
+}
+
+/// Environment variables of the daemon can be used in callback templates.
+/// Unknown variables expand to an empty string.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_callback_env_variables() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    let callback_file = tempdir.path().join("callback");
+    settings.daemon.callback = Some(format!(
+        "echo '{{{{id}}}}:{{{{env.CARGO_PKG_NAME}}}}:{{{{env.PUEUE_UNKNOWN_TEST_VARIABLE}}}}' > {}",
+        callback_file.to_string_lossy()
+    ));
+    settings
+        .save(&Some(tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "ls", true).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    // The callback runs asynchronously, so give it some time to finish.
+    let mut tries = 0;
+    while !callback_file.exists() && tries < 20 {
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        tries += 1;
+    }
+
+    // The daemon runs inside the test process, so it shares its environment.
+    let package_name = std::env::var("CARGO_PKG_NAME").unwrap_or_default();
+    let content = read_to_string(&callback_file).context("Callback didn't run")?;
+    assert_eq!(content, format!("0:{package_name}:\n"));
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
 use pueue_lib::network::message::*;
 
 use crate::helper::*;
@@ -138,3 +138,47 @@ async fn test_clean_successful_only_in_selected_group() -> Result<()> {
 
     Ok(())
 }
+
+/// Ensure that cleaned tasks are archived and can be queried via the history.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_clean_archives_tasks() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    for command in &["failing", "ls"] {
+        assert_success(add_task(shared, command, false).await?);
+    }
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+
+    let clean_message = CleanMessage {
+        successful_only: false,
+        group: None,
+    };
+    assert_success(send_message(shared, clean_message).await?);
+
+    // Both tasks are in the archive.
+    let history_message = HistoryMessage {
+        since: None,
+        group: None,
+        status: None,
+    };
+    let Message::HistoryResponse(tasks) = send_message(shared, history_message).await? else {
+        bail!("Didn't receive a history response");
+    };
+    let commands: Vec<&str> = tasks.iter().map(|task| task.command.as_str()).collect();
+    assert_eq!(commands, vec!["failing", "ls"]);
+
+    // Only the failed task is returned, if we filter for failed tasks.
+    let history_message = HistoryMessage {
+        since: None,
+        group: None,
+        status: Some(HistoryStatus::Failed),
+    };
+    let Message::HistoryResponse(tasks) = send_message(shared, history_message).await? else {
+        bail!("Didn't receive a history response");
+    };
+    assert_eq!(tasks.len(), 1);
+    assert_eq!(tasks[0].command, "failing");
+
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use anyhow::Result;
+use pueue_lib::task::*;
+
+use crate::helper::*;
+
+/// Tasks that depend on a failed task fail with `DependencyFailed`, unless they should run
+/// regardless of the result of their dependencies.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_only_if_success() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Add a task that fails and wait until it finished.
+    assert_success(add_task(shared, "failing", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    // This task only waits for the dependency to finish.
+    let mut message = create_add_message(shared, "ls");
+    message.dependencies = vec![0];
+    message.only_if_success = false;
+    assert_success(send_message(shared, message.clone()).await?);
+
+    // This task requires the dependency to succeed.
+    message.only_if_success = true;
+    assert_success(send_message(shared, message).await?);
+
+    let task = wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::Success));
+
+    let task = wait_for_task_condition(shared, 2, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::DependencyFailed));
+
+    Ok(())
+}
+
+/// Tasks that wait for a specific output of their dependencies are only started, if the output
+/// contains the text. Otherwise, they're skipped.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_when_output_contains() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "echo OK", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    // This task's condition is met by the output of the dependency.
+    let mut message = create_add_message(shared, "ls");
+    message.dependencies = vec![0];
+    message.when_output_contains = Some("OK".to_string());
+    assert_success(send_message(shared, message.clone()).await?);
+
+    // This task's condition isn't met.
+    message.when_output_contains = Some("MISSING".to_string());
+    assert_success(send_message(shared, message).await?);
+
+    let task = wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::Success));
+
+    let task = wait_for_task_condition(shared, 2, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::Skipped));
+
+    Ok(())
+}
+
+/// Running tasks that block other tasks are boosted, if `daemon.boost_blockers` is set.
+#[cfg(target_os = "linux")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_boost_blockers() -> Result<()> {
+    // Lowering the niceness of a process requires elevated privileges.
+    if whoami::username() != "root" {
+        return Ok(());
+    }
+
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.boost_blockers = true;
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    // The blocker waits until it has been boosted and prints its niceness afterwards.
+    let command = "for i in $(seq 20); do [ \"$(nice)\" = 10 ] || break; sleep 0.1; done; nice";
+    let mut message = create_add_message(shared, command);
+    message.nice = Some(10);
+    assert_success(send_message(shared, message).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    let mut message = create_add_message(shared, "ls");
+    message.dependencies = vec![0];
+    assert_success(send_message(shared, message).await?);
+
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert_eq!(get_task_log(shared, 0, None).await?.trim(), "5");
+
+    Ok(())
+}
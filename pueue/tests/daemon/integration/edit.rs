@@ -57,6 +57,7 @@ async fn test_edit_flow() -> Result<()> {
             path: Some("/tmp".into()),
             label: Some("test".to_string()),
             delete_label: false,
+            dependencies: None,
         },
     )
     .await?;
@@ -71,3 +72,57 @@ async fn test_edit_flow() -> Result<()> {
 
     Ok(())
 }
+
+/// Test that the dependencies of a task can be edited and are validated.
+#[test(tokio::test(flavor = "multi_thread", worker_threads = 2))]
+async fn test_edit_dependencies() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Pause the daemon. That way the command won't be started.
+    pause_tasks(shared, TaskSelection::All).await?;
+    wait_for_group_status(shared, PUEUE_DEFAULT_GROUP, GroupStatus::Paused).await?;
+
+    assert_success(add_task(shared, "ls", false).await?);
+    assert_success(add_task(shared, "ls", false).await?);
+
+    let mut message = EditMessage {
+        task_id: 1,
+        command: None,
+        path: None,
+        label: None,
+        delete_label: false,
+        dependencies: Some(vec![0, 5]),
+    };
+
+    // Non-existing dependencies are rejected and the task is restored to its previous state.
+    send_message(shared, Message::EditRequest(1)).await?;
+    assert_failure(send_message(shared, message.clone()).await?);
+    let task = get_task(shared, 1).await?;
+    assert_eq!(task.status, TaskStatus::Queued);
+    assert!(task.dependencies.is_empty());
+
+    // Valid dependencies are applied.
+    message.dependencies = Some(vec![0]);
+    send_message(shared, Message::EditRequest(1)).await?;
+    assert_success(send_message(shared, message).await?);
+    let task = get_task(shared, 1).await?;
+    assert_eq!(task.dependencies, vec![0]);
+    assert_eq!(task.status, TaskStatus::Queued);
+
+    // Dependencies that would create a cycle are rejected.
+    let message = EditMessage {
+        task_id: 0,
+        command: None,
+        path: None,
+        label: None,
+        delete_label: false,
+        dependencies: Some(vec![1]),
+    };
+    send_message(shared, Message::EditRequest(0)).await?;
+    assert_failure(send_message(shared, message).await?);
+    let task = get_task(shared, 0).await?;
+    assert!(task.dependencies.is_empty());
+
+    Ok(())
+}
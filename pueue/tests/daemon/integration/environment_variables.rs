@@ -1,4 +1,6 @@
-use anyhow::Result;
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
 
 use crate::helper::*;
 
@@ -24,3 +26,30 @@ async fn test_isolated_task_environment() -> Result<()> {
     child.kill()?;
     Ok(())
 }
+
+/// If an allowlist is configured, only the listed environment variables are passed to tasks.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_env_allowlist() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.env_allowlist = vec!["ALLOWED".to_string()];
+    settings
+        .save(&Some(tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "echo $ALLOWED $FORBIDDEN");
+    message.envs = HashMap::from([
+        ("ALLOWED".to_string(), "allowed".to_string()),
+        ("FORBIDDEN".to_string(), "forbidden".to_string()),
+    ]);
+    assert_success(send_message(shared, message).await?);
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    assert!(task.envs.contains_key("ALLOWED"));
+    assert!(!task.envs.contains_key("FORBIDDEN"));
+    let log = get_task_log(shared, 0, None).await?;
+    assert_eq!(log, "allowed\n");
+
+    Ok(())
+}
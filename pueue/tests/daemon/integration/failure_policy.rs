@@ -0,0 +1,101 @@
+use anyhow::Result;
+use pueue_lib::settings::{FailurePolicy, GroupSettings};
+use pueue_lib::state::{GroupStatus, PUEUE_DEFAULT_GROUP};
+use pueue_lib::task::*;
+
+use crate::helper::*;
+
+/// Create a daemon, whose default group uses the given failure policy.
+/// The global setting would pause the group, so we can check that the policy takes precedence.
+async fn daemon_with_failure_policy(policy: FailurePolicy) -> Result<PueueDaemon> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.pause_group_on_failure = true;
+    settings.daemon.group_settings.insert(
+        PUEUE_DEFAULT_GROUP.into(),
+        GroupSettings {
+            failure_policy: Some(policy),
+            ..Default::default()
+        },
+    );
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+
+    daemon_with_settings(settings, tempdir).await
+}
+
+/// With the `continue` policy, the group keeps starting tasks after a failure.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_continue() -> Result<()> {
+    let daemon = daemon_with_failure_policy(FailurePolicy::Continue).await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "sleep 1 && failing", false).await?);
+    assert_success(add_task(shared, "ls", false).await?);
+
+    let task = wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::Success));
+
+    let state = get_state(shared).await?;
+    assert_eq!(
+        state.groups[PUEUE_DEFAULT_GROUP].status,
+        GroupStatus::Running
+    );
+
+    Ok(())
+}
+
+/// With the `abort-remaining` policy, all queued tasks of the group are skipped after a failure.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_abort_remaining() -> Result<()> {
+    let daemon = daemon_with_failure_policy(FailurePolicy::AbortRemaining).await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "sleep 1 && failing", false).await?);
+    assert_success(add_task(shared, "ls", false).await?);
+    assert_success(add_task(shared, "ls", false).await?);
+
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert!(task.failed());
+
+    for task_id in [1, 2] {
+        let task = wait_for_task_condition(shared, task_id, |task| task.is_done()).await?;
+        assert_eq!(task.status, TaskStatus::Done(TaskResult::Skipped));
+    }
+
+    Ok(())
+}
+
+/// With `pause_after_failures`, the group is only paused after that many failures in a row.
+/// A successful task resets the counter.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_pause_after_failures() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.group_settings.insert(
+        PUEUE_DEFAULT_GROUP.into(),
+        GroupSettings {
+            failure_policy: Some(FailurePolicy::Pause),
+            pause_after_failures: Some(2),
+            ..Default::default()
+        },
+    );
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    for command in ["failing", "ls", "failing", "failing", "ls"] {
+        assert_success(add_task(shared, command, false).await?);
+    }
+
+    // The success of task 1 resets the counter, so only task 3 pauses the group.
+    let task = wait_for_task_condition(shared, 3, |task| task.is_done()).await?;
+    assert!(task.failed());
+    sleep_ms(500).await;
+
+    let state = get_state(shared).await?;
+    assert_eq!(
+        state.groups[PUEUE_DEFAULT_GROUP].status,
+        GroupStatus::Paused
+    );
+    assert_eq!(state.tasks[&4].status, TaskStatus::Queued);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anyhow::Result;
 
 use pueue_lib::network::message::*;
+use pueue_lib::settings::GroupSettings;
 
 use crate::helper::*;
 
@@ -32,6 +33,26 @@ async fn test_add_and_remove() -> Result<()> {
     Ok(())
 }
 
+/// Adding an existing group with a parallel limit updates the limit of that group.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_add_existing_with_parallel() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    add_group_with_slots(shared, "testgroup", 1).await?;
+
+    let add_message = GroupMessage::Add {
+        name: "testgroup".to_string(),
+        parallel_tasks: Some(4),
+    };
+    assert_success(send_message(shared, add_message).await?);
+
+    let state = get_state(shared).await?;
+    assert_eq!(state.groups.get("testgroup").unwrap().parallel_tasks, 4);
+
+    Ok(())
+}
+
 /// Users cannot delete the default group.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_cannot_delete_default() -> Result<()> {
@@ -81,3 +102,78 @@ async fn test_cannot_delete_group_with_tasks() -> Result<()> {
 
     Ok(())
 }
+
+/// Renaming a group moves its tasks and its parallel setting to the new name.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_rename() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    add_group_with_slots(shared, "testgroup", 3).await?;
+    let mut message = create_add_message(shared, "sleep 60");
+    message.group = "testgroup".to_string();
+    assert_success(send_message(shared, message).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    // Renaming to an existing group or renaming the default group isn't allowed.
+    let rename_message = GroupMessage::Rename {
+        name: "testgroup".to_string(),
+        new_name: PUEUE_DEFAULT_GROUP.to_string(),
+    };
+    assert_failure(send_message(shared, rename_message).await?);
+    let rename_message = GroupMessage::Rename {
+        name: PUEUE_DEFAULT_GROUP.to_string(),
+        new_name: "renamed".to_string(),
+    };
+    assert_failure(send_message(shared, rename_message).await?);
+
+    let rename_message = GroupMessage::Rename {
+        name: "testgroup".to_string(),
+        new_name: "renamed".to_string(),
+    };
+    assert_success(send_message(shared, rename_message).await?);
+    wait_for_group_absence(shared, "testgroup").await?;
+
+    let state = get_state(shared).await?;
+    assert_eq!(state.groups.get("renamed").unwrap().parallel_tasks, 3);
+    assert_eq!(state.tasks.get(&0).unwrap().group, "renamed");
+
+    // The running task is still managed in its new group and can be killed.
+    let kill_message = KillMessage {
+        tasks: TaskSelection::Group("renamed".to_string()),
+        signal: None,
+        retry: false,
+        skip_unstarted: false,
+    };
+    assert_success(send_message(shared, kill_message).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    Ok(())
+}
+
+/// Groups that are configured via the `group_settings` cannot be renamed,
+/// as their settings would otherwise be lost.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_rename_configured_group() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings
+        .daemon
+        .group_settings
+        .insert("configured".into(), GroupSettings::default());
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    add_group_with_slots(shared, "configured", 1).await?;
+
+    let rename_message = GroupMessage::Rename {
+        name: "configured".to_string(),
+        new_name: "renamed".to_string(),
+    };
+    assert_failure(send_message(shared, rename_message).await?);
+
+    let state = get_state(shared).await?;
+    assert!(state.groups.contains_key("configured"));
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+
+use pueue_lib::network::message::*;
+use pueue_lib::state::{GroupStatus, PUEUE_DEFAULT_GROUP};
+use pueue_lib::task::*;
+
+use crate::helper::*;
+
+/// Held tasks aren't started, while the following tasks are.
+/// Once released, they're started like any other queued task.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_hold_and_release() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Pause the group, so the task cannot be started before it's held.
+    pause_tasks(shared, TaskSelection::Group(PUEUE_DEFAULT_GROUP.into())).await?;
+    wait_for_group_status(shared, PUEUE_DEFAULT_GROUP, GroupStatus::Paused).await?;
+    assert_success(add_task(shared, "ls", false).await?);
+    assert_success(add_task(shared, "ls", false).await?);
+
+    assert_success(send_message(shared, Message::Hold(vec![0])).await?);
+    start_tasks(shared, TaskSelection::Group(PUEUE_DEFAULT_GROUP.into())).await?;
+
+    // The task after the held one is started, the held one keeps waiting.
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    let task = get_task(shared, 0).await?;
+    assert_eq!(task.status, TaskStatus::Queued);
+    assert!(task.held);
+
+    // Only held tasks can be released.
+    assert_failure(send_message(shared, Message::Release(vec![1])).await?);
+
+    assert_success(send_message(shared, Message::Release(vec![0])).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    Ok(())
+}
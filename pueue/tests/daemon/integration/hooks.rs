@@ -0,0 +1,57 @@
+use anyhow::{bail, Result};
+use pueue_lib::settings::GroupSettings;
+use pueue_lib::task::*;
+
+use crate::helper::*;
+
+/// The hooks of a group are executed before and after each of its tasks.
+/// If the pre-task hook fails, the task fails without being started.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_pre_and_post_task_hooks() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    let path = tempdir.path().to_path_buf();
+    settings.daemon.group_settings.insert(
+        "hooked".into(),
+        GroupSettings {
+            pre_task_hook: Some(format!("touch {}/pre_$PUEUE_TASK_ID", path.display())),
+            post_task_hook: Some(format!(
+                "echo $PUEUE_TASK_RESULT > {}/post_$PUEUE_TASK_ID",
+                path.display()
+            )),
+            ..Default::default()
+        },
+    );
+    settings.daemon.group_settings.insert(
+        "failing".into(),
+        GroupSettings {
+            pre_task_hook: Some("exit 1".into()),
+            ..Default::default()
+        },
+    );
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    add_group_with_slots(shared, "hooked", 1).await?;
+    add_group_with_slots(shared, "failing", 1).await?;
+
+    // The hooks run around the task.
+    assert_success(add_task_to_group(shared, "ls", "hooked").await?);
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::Success));
+    assert!(path.join("pre_0").exists(), "pre_task_hook should have run");
+    // The post-task hook isn't waited for, so give it a moment.
+    sleep_ms(500).await;
+    let post = std::fs::read_to_string(path.join("post_0"))?;
+    assert_eq!(post.trim(), "Success");
+
+    // A failing pre-task hook prevents the task from being started.
+    assert_success(add_task_to_group(shared, "ls", "failing").await?);
+    let task = wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    let TaskStatus::Done(TaskResult::FailedToSpawn(err)) = task.status else {
+        bail!("Expected the task to fail to spawn, got {:?}", task.status);
+    };
+    assert!(err.contains("pre_task_hook"), "Got error: {err}");
+
+    Ok(())
+}
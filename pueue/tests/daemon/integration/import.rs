@@ -0,0 +1,57 @@
+use anyhow::{bail, Result};
+use pueue_lib::network::message::*;
+use pueue_lib::state::{Group, GroupStatus};
+use pueue_lib::task::*;
+
+use crate::helper::*;
+
+/// An exported state can be imported again.
+/// Tasks get new ids, their dependencies are remapped and running tasks are queued.
+/// Groups that don't exist yet are created.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_export_import() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Add a running task and a task that depends on it.
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+    let mut message = create_add_message(shared, "ls");
+    message.dependencies = vec![0];
+    assert_success(send_message(shared, message).await?);
+
+    let mut exported = match send_message(shared, Message::Export).await? {
+        Message::ExportResponse(state) => state,
+        response => bail!("Received unexpected response: {response:?}"),
+    };
+    assert_eq!(exported.tasks.len(), 2);
+
+    // Move the dependant task into a group that doesn't exist yet.
+    exported.groups.insert(
+        "imported".to_string(),
+        Group {
+            status: GroupStatus::Running,
+            parallel_tasks: 2,
+        },
+    );
+    exported.tasks.get_mut(&1).unwrap().group = "imported".to_string();
+
+    assert_success(send_message(shared, Message::Import(exported)).await?);
+
+    // The group is created right away, together with the imported tasks.
+    let state = get_state(shared).await?;
+    assert_eq!(state.groups["imported"].parallel_tasks, 2);
+
+    // The running task has been queued again, as the default group is still occupied.
+    let task = get_task(shared, 2).await?;
+    assert_eq!(task.status, TaskStatus::Queued);
+    assert_eq!(task.command, "sleep 60");
+
+    // The dependency points to the newly imported task.
+    let task = get_task(shared, 3).await?;
+    assert_eq!(task.dependencies, vec![2]);
+    assert_eq!(task.group, "imported");
+    assert_eq!(task.status, TaskStatus::Queued);
+
+    Ok(())
+}
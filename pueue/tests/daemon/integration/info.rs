@@ -0,0 +1,30 @@
+use anyhow::{bail, Result};
+use pueue_lib::network::message::*;
+
+use crate::helper::*;
+
+/// The daemon reports information about itself.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_info() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    let info = match send_message(shared, Message::Info).await? {
+        Message::InfoResponse(info) => info,
+        response => bail!("Received unexpected response: {response:?}"),
+    };
+
+    // The test daemon runs inside of the test process and wrote its pid file.
+    assert_eq!(info.pid, std::process::id());
+    assert_eq!(info.pid, daemon.pid as u32);
+    assert_eq!(info.running_tasks, 1);
+    assert_eq!(
+        info.config_path,
+        Some(daemon.tempdir.path().join("pueue.yml"))
+    );
+
+    Ok(())
+}
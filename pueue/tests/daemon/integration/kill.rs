@@ -22,18 +22,24 @@ use crate::helper::*;
     KillMessage {
         tasks: TaskSelection::All,
         signal: None,
+        retry: false,
+        skip_unstarted: false,
     }, true
 )]
 #[case(
     KillMessage {
         tasks: TaskSelection::Group(PUEUE_DEFAULT_GROUP.into()),
         signal: None,
+        retry: false,
+        skip_unstarted: false,
     }, true
 )]
 #[case(
     KillMessage {
         tasks: TaskSelection::TaskIds(vec![0, 1, 2]),
         signal: None,
+        retry: false,
+        skip_unstarted: false,
     }, false
 )]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -59,7 +65,7 @@ async fn test_kill_tasks(
     // Make sure all tasks get killed
     for id in 0..3 {
         wait_for_task_condition(shared, id, |task| {
-            matches!(task.status, TaskStatus::Done(TaskResult::Killed))
+            matches!(task.status, TaskStatus::Done(TaskResult::KilledBySignal(9)))
         })
         .await?;
     }
@@ -75,3 +81,78 @@ async fn test_kill_tasks(
 
     Ok(())
 }
+
+/// Tasks that are killed with the `retry` flag are re-queued, until they hit the retry limit.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_kill_with_retry() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.max_kill_retries = 1;
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "sleep 60", true).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    let message = KillMessage {
+        tasks: TaskSelection::TaskIds(vec![0]),
+        signal: None,
+        retry: true,
+        skip_unstarted: false,
+    };
+
+    // The task is re-queued and started again, as its group is still running.
+    assert_success(send_message(shared, message.clone()).await?);
+    wait_for_task_condition(shared, 0, |task| task.retries == 1 && task.is_running()).await?;
+
+    // The retry limit is reached, so the task is killed for good.
+    assert_success(send_message(shared, message).await?);
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::KilledBySignal(9)));
+    assert_eq!(task.retries, 1);
+
+    Ok(())
+}
+
+/// Tasks cannot be retried, if the signal that's sent doesn't terminate them.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_kill_with_retry_non_terminating_signal() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "sleep 60", true).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    let message = KillMessage {
+        tasks: TaskSelection::TaskIds(vec![0]),
+        signal: Some(Signal::SigStop),
+        retry: true,
+        skip_unstarted: false,
+    };
+    assert_failure(send_message(shared, message).await?);
+
+    // The task is left untouched.
+    sleep_ms(500).await;
+    let task = get_task(shared, 0).await?;
+    assert_eq!(task.status, TaskStatus::Running);
+    assert_eq!(task.retries, 0);
+
+    Ok(())
+}
+
+/// Tasks that are terminated by a signal remember the signal that killed them.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_killed_by_signal() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // The shell sends a SIGTERM to itself.
+    assert_success(add_task(shared, "kill -TERM $$", false).await?);
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert_eq!(
+        task.status,
+        TaskStatus::Done(TaskResult::KilledBySignal(15))
+    );
+
+    Ok(())
+}
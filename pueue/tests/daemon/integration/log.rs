@@ -1,6 +1,7 @@
 use std::fs::read_to_string;
 use std::fs::File;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 use anyhow::{bail, Context, Result};
 use pueue_lib::network::message::*;
@@ -98,6 +99,9 @@ async fn test_partial_log() -> Result<()> {
         task_ids: vec![0],
         send_logs: true,
         lines: Some(5),
+        offset: None,
+        stderr: false,
+        archived: false,
     };
     let response = send_message(shared, Message::Log(log_message)).await?;
     let logs = match response {
@@ -135,6 +139,9 @@ async fn test_correct_log_order() -> Result<()> {
         task_ids: vec![0],
         send_logs: true,
         lines: None,
+        offset: None,
+        stderr: false,
+        archived: false,
     };
     let response = send_message(shared, Message::Log(log_message)).await?;
     let logs = match response {
@@ -155,3 +162,184 @@ async fn test_correct_log_order() -> Result<()> {
 
     Ok(())
 }
+
+/// Ensure that only the requested range of lines is returned, if an offset is given.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_log_line_range() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "seq 0 9", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    // Request the lines 3 to 5.
+    let log_message = LogRequestMessage {
+        task_ids: vec![0],
+        send_logs: true,
+        lines: Some(3),
+        offset: Some(3),
+        stderr: false,
+        archived: false,
+    };
+    let response = send_message(shared, Message::Log(log_message)).await?;
+    let logs = match response {
+        Message::LogResponse(logs) => logs,
+        _ => bail!("Received non LogResponse: {:#?}", response),
+    };
+
+    let logs = logs.get(&0).unwrap();
+    let output = logs
+        .output
+        .clone()
+        .context("Didn't find output on TaskLogMessage")?;
+    let output = decompress_log(output)?;
+
+    assert_eq!(output, "3\n4\n5\n");
+
+    Ok(())
+}
+
+/// Ensure that the log of a task is truncated to its most recent output,
+/// once it exceeds the maximum log size.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_max_log_size() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.max_log_size_bytes = Some(1000);
+    settings
+        .save(&Some(tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    // The log is also truncated, if the task finishes before the periodic check.
+    assert_success(add_task(shared, "seq 0 9999", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    let output = get_task_log(shared, 0, None).await?;
+    assert!(
+        output.len() <= 1000,
+        "Log wasn't truncated: {}",
+        output.len()
+    );
+    assert!(output.ends_with("9998\n9999\n"));
+    // The log has to start at the beginning of a line.
+    assert!(output.lines().next().unwrap().parse::<usize>().is_ok());
+
+    Ok(())
+}
+
+/// Ensure that stderr is written to a separate log, if `separate_stderr` is enabled.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_separate_stderr() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.separate_stderr = true;
+    settings
+        .save(&Some(tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "echo out && echo err >&2", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    // The default log only contains stdout.
+    let output = get_task_log(shared, 0, None).await?;
+    assert_eq!(output, "out\n");
+
+    // The stderr log only contains stderr.
+    let log_message = LogRequestMessage {
+        task_ids: vec![0],
+        send_logs: true,
+        lines: None,
+        offset: None,
+        stderr: true,
+        archived: false,
+    };
+    let response = send_message(shared, Message::Log(log_message)).await?;
+    let logs = match response {
+        Message::LogResponse(logs) => logs,
+        _ => bail!("Received non LogResponse: {:#?}", response),
+    };
+    let output = logs
+        .get(&0)
+        .unwrap()
+        .output
+        .clone()
+        .context("Didn't find output on TaskLogMessage")?;
+    assert_eq!(decompress_log(output)?, "err\n");
+
+    Ok(())
+}
+
+/// Output that's forwarded to journald is still written to the task's log file.
+/// Journald doesn't need to be available for this, as failures to reach it are ignored.
+#[cfg(target_os = "linux")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_log_to_journald() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.log_to_journald = true;
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "echo hello && echo world >&2", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    let log = get_task_log(shared, 0, None).await?;
+    assert!(log.contains("hello"), "Got log: {log}");
+    assert!(log.contains("world"), "Got log: {log}");
+
+    Ok(())
+}
+
+/// With `log_timestamps`, each line of the task's log is prepended with the time it was written.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_log_timestamps() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.log_timestamps = true;
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "echo hello && echo world", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    let log = get_task_log(shared, 0, None).await?;
+    let lines: Vec<&str> = log.lines().collect();
+    assert_eq!(lines.len(), 2, "Got log: {log}");
+    for (line, expected) in lines.iter().zip(["hello", "world"]) {
+        // Each line looks like `[12:34:56] hello`.
+        assert!(line.starts_with('['), "Got line: {line}");
+        assert_eq!(&line[9..], format!("] {expected}"), "Got line: {line}");
+    }
+
+    Ok(())
+}
+
+/// Background processes inherit the output pipes of their task and keep them open.
+/// Neither the task nor the daemon may wait for them, once the task itself finished.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_log_timestamps_with_background_process() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.log_timestamps = true;
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    let start = Instant::now();
+    assert_success(add_task(shared, "sleep 10 & echo background", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+
+    // The daemon still answers and starts new tasks.
+    assert_success(add_task(shared, "echo hello", false).await?);
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+    assert!(
+        start.elapsed() < Duration::from_secs(5),
+        "The daemon waited for the background process"
+    );
+
+    let log = get_task_log(shared, 0, None).await?;
+    assert!(log.ends_with("] background\n"), "Got log: {log}");
+
+    Ok(())
+}
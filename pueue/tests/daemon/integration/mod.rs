@@ -1,21 +1,45 @@
 mod add;
 mod aliases;
+/// Tests for the callbacks that are executed on task state changes.
+mod callback;
 mod clean;
+/// Tests for tasks that depend on other tasks.
+mod dependencies;
 mod edit;
 mod environment_variables;
+/// Tests for the failure policies of groups.
+mod failure_policy;
 mod group;
+/// Tests for holding and releasing queued tasks.
+mod hold;
+/// Tests for the pre- and post-task hooks of groups.
+mod hooks;
+/// Tests for exporting and importing the state.
+mod import;
+/// Tests for information about the daemon itself.
+mod info;
 mod kill;
 mod log;
 mod parallel_tasks;
 mod pause;
+/// Tests for starting queued tasks by their priority.
+mod priority;
+/// Tests for clients with read-only access.
+mod read_only;
 mod remove;
 mod reset;
+mod resources;
 mod restart;
 /// Tests regarding state restoration from a previous run.
 mod restore;
+/// Tests for automatically re-enqueuing failed tasks.
+mod retry;
 /// Tests for shutting down the daemon.
 mod shutdown;
 mod start;
 mod stashed;
+/// Tests for serving the state from the status cache.
+mod status_cache;
+mod timeout;
 /// Test that the worker pool environment variables are properly injected.
 mod worker_environment_variables;
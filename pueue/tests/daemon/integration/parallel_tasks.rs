@@ -56,3 +56,71 @@ async fn test_parallel_tasks() -> Result<()> {
     }
     Ok(())
 }
+
+/// Test that tasks which are started immediately don't occupy a slot of their group.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_immediate_tasks_dont_occupy_slots() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // The default group only has a single slot.
+    assert_success(add_task(shared, "sleep 60", true).await?);
+    let task = wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+    assert!(task.immediate);
+
+    // The next task is started by the scheduler, as the immediate task doesn't occupy the slot.
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    let task = wait_for_task_condition(shared, 1, |task| task.is_running()).await?;
+    assert!(!task.immediate);
+
+    // The slot is now occupied, so this task stays queued.
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    sleep_ms(500).await;
+    let task = get_task(shared, 2).await?;
+    assert_eq!(task.status, TaskStatus::Queued);
+
+    Ok(())
+}
+
+/// Tasks occupy as many slots as their weight.
+/// Tasks that are heavier than the group's limit are started, once the group is empty.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_weighted_tasks() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    add_group_with_slots(shared, "testgroup", 3).await?;
+
+    // A light and a heavy task fill all three slots.
+    assert_success(add_task_to_group(shared, "sleep 0.5", "testgroup").await?);
+    let mut message = create_add_message(shared, "sleep 60");
+    message.group = "testgroup".to_string();
+    message.weight = 2;
+    assert_success(send_message(shared, message).await?);
+    wait_for_task_condition(shared, 1, |task| task.is_running()).await?;
+
+    // Another heavy task doesn't fit, not even once the light task finished.
+    let mut message = create_add_message(shared, "sleep 60");
+    message.group = "testgroup".to_string();
+    message.weight = 2;
+    assert_success(send_message(shared, message).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    sleep_ms(500).await;
+    let task = get_task(shared, 2).await?;
+    assert_eq!(task.status, TaskStatus::Queued);
+
+    // A task that's heavier than the whole group is started in an empty group.
+    add_group_with_slots(shared, "small", 1).await?;
+    let mut message = create_add_message(shared, "sleep 60");
+    message.group = "small".to_string();
+    message.weight = 3;
+    assert_success(send_message(shared, message).await?);
+    wait_for_task_condition(shared, 3, |task| task.is_running()).await?;
+
+    // Tasks without any weight are rejected.
+    let mut message = create_add_message(shared, "sleep 60");
+    message.weight = 0;
+    assert_failure(send_message(shared, message).await?);
+
+    Ok(())
+}
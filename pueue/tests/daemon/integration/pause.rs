@@ -76,3 +76,40 @@ async fn test_pause_with_wait() -> Result<()> {
 
     Ok(())
 }
+
+/// Pausing and resuming specific tasks doesn't affect other running tasks of the same group.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_pause_single_task() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let message = ParallelMessage {
+        parallel_tasks: 2,
+        group: PUEUE_DEFAULT_GROUP.into(),
+    };
+    assert_success(send_message(shared, message).await?);
+
+    // Start two long running tasks in the same group.
+    add_task(shared, "sleep 60", false).await?;
+    add_task(shared, "sleep 60", false).await?;
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+    wait_for_task_condition(shared, 1, |task| task.is_running()).await?;
+
+    // Only pause the first task.
+    pause_tasks(shared, TaskSelection::TaskIds(vec![0])).await?;
+    wait_for_task_condition(shared, 0, |task| matches!(task.status, TaskStatus::Paused)).await?;
+
+    // The sibling and the group keep running.
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.get(&1).unwrap().status, TaskStatus::Running);
+    assert_eq!(
+        state.groups.get(PUEUE_DEFAULT_GROUP).unwrap().status,
+        GroupStatus::Running
+    );
+
+    // Resume the task again.
+    start_tasks(shared, TaskSelection::TaskIds(vec![0])).await?;
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use anyhow::Result;
+
+use pueue_lib::network::message::*;
+use pueue_lib::state::{GroupStatus, PUEUE_DEFAULT_GROUP};
+
+use crate::helper::*;
+
+/// Queued tasks with a higher priority are started first.
+/// Tasks with the same priority are started in the order of their ids.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_start_by_priority() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    // Pause the default group, so the tasks can be queued without being started.
+    pause_tasks(shared, TaskSelection::Group(PUEUE_DEFAULT_GROUP.into())).await?;
+    wait_for_group_status(shared, PUEUE_DEFAULT_GROUP, GroupStatus::Paused).await?;
+
+    for priority in [0, 5, 5] {
+        let mut message = create_add_message(shared, "sleep 60");
+        message.priority = priority;
+        assert_success(send_message(shared, message).await?);
+    }
+
+    // Only a single task can run at a time in the default group.
+    start_tasks(shared, TaskSelection::Group(PUEUE_DEFAULT_GROUP.into())).await?;
+    wait_for_task_condition(shared, 1, |task| task.is_running()).await?;
+
+    let state = get_state(shared).await?;
+    assert!(state.tasks.get(&0).unwrap().is_queued());
+    assert!(state.tasks.get(&2).unwrap().is_queued());
+
+    Ok(())
+}
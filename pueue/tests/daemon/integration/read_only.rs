@@ -0,0 +1,57 @@
+use anyhow::Result;
+use pretty_assertions::assert_eq;
+
+use pueue_lib::network::message::*;
+
+use crate::helper::*;
+
+/// Clients that authenticate with the read-only secret can read the state,
+/// but all messages that would change the state are rejected.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_read_only_secret() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.shared.read_only_secret_path = Some(tempdir.path().join("read_only_secret"));
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    // Clients use the read-only secret, by pointing their secret path to it.
+    let mut read_only = shared.clone();
+    read_only.shared_secret_path = shared.read_only_secret_path.clone();
+
+    assert_success(add_task(shared, "ls", false).await?);
+
+    // Reading the state works as usual, but the environment of tasks is hidden.
+    let state = get_state(&read_only).await?;
+    assert_eq!(state.tasks.len(), 1);
+    assert!(state.tasks.get(&0).unwrap().envs.is_empty());
+
+    let response = send_message(&read_only, TaskRequestMessage { id: 0 }).await?;
+    let Message::TaskResponse(task) = response else {
+        panic!("Expected a task response, got {response:?}");
+    };
+    assert!(task.envs.is_empty());
+
+    let response = send_message(&read_only, Message::Export).await?;
+    let Message::ExportResponse(state) = response else {
+        panic!("Expected an export response, got {response:?}");
+    };
+    assert!(state.tasks.get(&0).unwrap().envs.is_empty());
+
+    // Groups can be listed.
+    let response = send_message(&read_only, GroupMessage::List).await?;
+    assert!(matches!(response, Message::GroupResponse(_)));
+
+    // Clients with full access still see the environment.
+    let state = get_state(shared).await?;
+    assert!(!state.tasks.get(&0).unwrap().envs.is_empty());
+
+    // Adding or removing tasks isn't allowed.
+    assert_failure(add_task(&read_only, "ls", false).await?);
+    assert_failure(send_message(&read_only, Message::Remove(vec![0])).await?);
+
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.len(), 1);
+
+    Ok(())
+}
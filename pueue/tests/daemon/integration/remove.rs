@@ -1,4 +1,5 @@
-use anyhow::Result;
+use anyhow::{bail, Result};
+use pueue_lib::log::{get_log_archive_path, get_log_path};
 use pueue_lib::network::message::*;
 
 use crate::helper::*;
@@ -47,3 +48,59 @@ async fn test_normal_remove() -> Result<()> {
 
     Ok(())
 }
+
+/// Removing tasks with `keep_logs` moves their logs into the archive.
+/// The archived logs can still be requested afterwards.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_remove_keep_logs() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "echo archived", false).await?);
+    assert_success(add_task(shared, "echo deleted", false).await?);
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+
+    let archived = get_task(shared, 0).await?;
+    let deleted = get_task(shared, 1).await?;
+    let message = Message::RemoveKeepLogs(vec![0]);
+    assert_success(send_message(shared, message).await?);
+    let message = Message::Remove(vec![1]);
+    assert_success(send_message(shared, message).await?);
+
+    let pueue_directory = shared.pueue_directory();
+    let archive_path = get_log_archive_path(&archived, &pueue_directory);
+    assert!(!get_log_path(0, &pueue_directory).exists());
+    assert!(get_log_path(0, &archive_path).exists());
+    let archive_path = get_log_archive_path(&deleted, &pueue_directory);
+    assert!(!get_log_path(1, &archive_path).exists());
+
+    // The id of the archived task is reused by the next task.
+    // Archiving that task as well doesn't replace the logs of the first one.
+    assert_success(add_task(shared, "echo reused", false).await?);
+    let reused = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert_success(send_message(shared, Message::RemoveKeepLogs(vec![0])).await?);
+    let archive_path = get_log_archive_path(&archived, &pueue_directory);
+    let output = std::fs::read_to_string(get_log_path(0, &archive_path))?;
+    assert_eq!(output.trim(), "archived");
+    let archive_path = get_log_archive_path(&reused, &pueue_directory);
+    let output = std::fs::read_to_string(get_log_path(0, &archive_path))?;
+    assert_eq!(output.trim(), "reused");
+
+    let message = LogRequestMessage {
+        task_ids: vec![0, 1],
+        send_logs: true,
+        lines: None,
+        offset: None,
+        stderr: false,
+        archived: true,
+    };
+    let response = send_message(shared, message).await?;
+    let Message::LogResponse(logs) = response else {
+        bail!("Received non-LogResponse: {response:#?}");
+    };
+    // The latest archived task with the requested id is shown.
+    assert_eq!(logs.keys().copied().collect::<Vec<_>>(), vec![0]);
+    assert_eq!(logs.get(&0).unwrap().task.command, "echo reused");
+
+    Ok(())
+}
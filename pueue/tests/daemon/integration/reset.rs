@@ -17,7 +17,7 @@ async fn test_reset() -> Result<()> {
     wait_for_task_condition(shared, 2, |task| task.is_running()).await?;
 
     // Reset the daemon
-    send_message(shared, ResetMessage {})
+    send_message(shared, ResetMessage { group: None })
         .await
         .context("Failed to send Start tasks message")?;
 
@@ -30,3 +30,38 @@ async fn test_reset() -> Result<()> {
 
     Ok(())
 }
+
+/// Resetting a single group only kills and removes the tasks of that group.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_reset_group() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+    add_group_with_slots(shared, "testgroup", 1).await?;
+
+    add_task(shared, "sleep 60", false).await?;
+    add_task_to_group(shared, "sleep 60", "testgroup").await?;
+    add_task_to_group(shared, "ls", "testgroup").await?;
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+    wait_for_task_condition(shared, 1, |task| task.is_running()).await?;
+
+    // Reset the test group
+    let message = ResetMessage {
+        group: Some("testgroup".to_string()),
+    };
+    assert_success(send_message(shared, message).await?);
+
+    // Reseting is asynchronous, wait for the tasks of the group to disappear.
+    wait_for_task_absence(shared, 1).await?;
+    wait_for_task_absence(shared, 2).await?;
+
+    // The task of the default group is still running and the group is running again.
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.len(), 1);
+    assert!(state.tasks.get(&0).unwrap().is_running());
+    assert_eq!(
+        state.groups.get("testgroup").unwrap().status,
+        pueue_lib::state::GroupStatus::Running
+    );
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anyhow::Result;
+
+use pueue_lib::network::message::*;
+
+use crate::helper::*;
+
+/// The daemon samples the resource usage of running tasks and clears it once they finish.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_resource_usage() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    assert_success(add_task(shared, "sleep 60", true).await?);
+    let task = wait_for_task_condition(shared, 0, |task| task.resources.is_some()).await?;
+    assert!(task.resources.unwrap().memory > 0);
+
+    // Kill the task. The resource usage should be gone.
+    send_message(
+        shared,
+        KillMessage {
+            tasks: TaskSelection::TaskIds(vec![0]),
+            signal: None,
+            retry: false,
+            skip_unstarted: false,
+        },
+    )
+    .await?;
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert!(task.resources.is_none());
+
+    Ok(())
+}
@@ -33,6 +33,7 @@ async fn test_restart_in_place() -> Result<()> {
         }],
         start_immediately: false,
         stashed: false,
+        working_directory: None,
     };
     assert_success(send_message(shared, restart_message).await?);
 
@@ -86,6 +87,7 @@ async fn test_cannot_restart_running() -> Result<()> {
         }],
         start_immediately: false,
         stashed: false,
+        working_directory: None,
     };
     assert_failure(send_message(shared, restart_message).await?);
 
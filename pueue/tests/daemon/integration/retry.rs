@@ -0,0 +1,58 @@
+use anyhow::Result;
+use chrono::Local;
+
+use pueue_lib::task::*;
+
+use crate::helper::*;
+
+/// Failed tasks are re-enqueued until they run out of retries.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_retry_failed_task() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "failing");
+    message.retries = 2;
+    assert_success(send_message(shared, message).await?);
+
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert!(matches!(
+        task.status,
+        TaskStatus::Done(TaskResult::Failed(_))
+    ));
+    assert_eq!(task.retries_remaining, 0);
+    assert_eq!(task.failed_attempts, 2);
+    assert_eq!(task.attempt(), 3);
+
+    Ok(())
+}
+
+/// Failed tasks with a retry delay are stashed and scheduled for a later enqueue.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_retry_with_delay() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "failing");
+    message.retries = 3;
+    message.retry_delay = 60;
+    assert_success(send_message(shared, message).await?);
+
+    let task = wait_for_task_condition(shared, 0, |task| {
+        matches!(task.status, TaskStatus::Stashed { .. })
+    })
+    .await?;
+
+    if let TaskStatus::Stashed {
+        enqueue_at: Some(enqueue_at),
+    } = task.status
+    {
+        assert!(enqueue_at > Local::now() + chrono::Duration::seconds(50));
+    } else {
+        panic!("Expected the task to be scheduled for enqueue, got {task:?}");
+    }
+    assert_eq!(task.retries_remaining, 2);
+    assert_eq!(task.attempt(), 2);
+
+    Ok(())
+}
@@ -1,4 +1,6 @@
 use anyhow::{Context, Result};
+use pueue_lib::network::message::*;
+use pueue_lib::task::*;
 
 use crate::helper::*;
 
@@ -47,3 +49,45 @@ async fn test_graceful_shutdown() -> Result<()> {
 
     Ok(())
 }
+
+/// Spin up the daemon and drain it, while a task is running and another one is queued.
+/// The daemon should wait for the running task to finish, exit with a 0 and keep the queued task.
+#[tokio::test]
+async fn test_drain_shutdown() -> Result<()> {
+    let (settings, _tempdir) = daemon_base_setup()?;
+    let shared = &settings.shared;
+    let mut child = standalone_daemon(shared).await?;
+
+    assert_success(add_task(shared, "sleep 1.5", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+    assert_success(add_task(shared, "ls", false).await?);
+
+    assert_success(send_message(shared, Shutdown::Drain { timeout: None }).await?);
+
+    // No new tasks are started while draining.
+    let message = StartMessage {
+        tasks: TaskSelection::TaskIds(vec![1]),
+    };
+    assert_failure(send_message(shared, message).await?);
+    assert_failure(add_task(shared, "ls", true).await?);
+
+    // The daemon is still waiting for the running task.
+    assert!(matches!(child.try_wait(), Ok(None)));
+
+    wait_for_shutdown(&mut child).await?;
+    let code = child.try_wait()?.expect("The daemon should have exited");
+    assert!(matches!(code.code(), Some(0)));
+
+    // Restart the daemon. The running task finished, while the queued task has been kept.
+    let mut child = standalone_daemon(shared).await?;
+    let state = get_state(shared).await?;
+    assert_eq!(
+        state.tasks.get(&0).unwrap().status,
+        TaskStatus::Done(TaskResult::Success)
+    );
+    assert_eq!(state.tasks.get(&1).unwrap().status, TaskStatus::Queued);
+
+    child.kill()?;
+
+    Ok(())
+}
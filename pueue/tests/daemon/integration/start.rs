@@ -65,3 +65,35 @@ async fn test_start_tasks(#[case] start_message: StartMessage) -> Result<()> {
     }
     Ok(())
 }
+
+/// Tasks that have been added with `manual_start` are never started by the scheduler,
+/// but they can be started explicitly.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_start_manual_task() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "sleep 60");
+    message.manual_start = true;
+    assert_success(send_message(shared, message).await?);
+    // A normal task is added afterwards and should be started right away.
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 1, |task| task.is_done()).await?;
+
+    // The first task is still queued.
+    let task = get_task(shared, 0).await?;
+    assert_eq!(task.status, TaskStatus::Queued);
+
+    // Explicitly starting the task works.
+    let message = StartMessage {
+        tasks: TaskSelection::TaskIds(vec![0]),
+    };
+    assert_success(send_message(shared, message).await?);
+    let task = wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+    assert!(
+        !task.manual_start,
+        "Started tasks no longer wait for a manual start"
+    );
+
+    Ok(())
+}
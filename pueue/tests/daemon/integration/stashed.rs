@@ -69,6 +69,7 @@ async fn test_enqueued_tasks(
     let enqueue_message = EnqueueMessage {
         task_ids: vec![0],
         enqueue_at: None,
+        group: None,
     };
     send_message(shared, enqueue_message)
         .await
@@ -141,3 +142,30 @@ async fn test_stash_queued_task() -> Result<()> {
 
     Ok(())
 }
+
+/// Queued tasks that exceed the maximum queue age are automatically stashed.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_max_queue_age() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.max_queue_age_seconds = Some(1);
+    settings
+        .save(&Some(tempdir.path().join("pueue.yml")))
+        .context("Couldn't write pueue config to temporary directory")?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    // The first task occupies the only slot, so the second task stays queued.
+    assert_success(add_task(shared, "sleep 60", false).await?);
+    assert_success(add_task(shared, "ls", false).await?);
+    wait_for_task_condition(shared, 0, |task| task.is_running()).await?;
+
+    sleep_ms(1000).await;
+    let task = wait_for_task_condition(shared, 1, |task| {
+        matches!(task.status, TaskStatus::Stashed { .. })
+    })
+    .await?;
+    assert!(task.stashed_by_age);
+    assert_eq!(task.enqueued_at, None);
+
+    Ok(())
+}
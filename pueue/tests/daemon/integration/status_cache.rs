@@ -0,0 +1,44 @@
+use anyhow::{bail, Result};
+use pueue_lib::network::message::Message;
+use pueue_lib::settings::Shared;
+use pueue_lib::state::State;
+
+use crate::helper::*;
+
+/// Request the status the way `pueue status` does, which is served from the cache.
+async fn get_cached_state(shared: &Shared) -> Result<Box<State>> {
+    match send_message(shared, Message::Status).await? {
+        Message::StatusResponse(state) => Ok(state),
+        response => bail!("Expected a status response, got {response:?}"),
+    }
+}
+
+/// The state is served from the cache, until the cache expires.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_status_cache() -> Result<()> {
+    let (mut settings, tempdir) = daemon_base_setup()?;
+    settings.daemon.status_cache_ms = Some(2000);
+    settings.save(&Some(tempdir.path().join("pueue.yml")))?;
+    let daemon = daemon_with_settings(settings, tempdir).await?;
+    let shared = &daemon.settings.shared;
+
+    // Fill the cache with the empty state.
+    let state = get_cached_state(shared).await?;
+    assert!(state.tasks.is_empty());
+
+    // The new task isn't visible, as the cached state is still valid.
+    assert_success(add_task(shared, "ls", false).await?);
+    let state = get_cached_state(shared).await?;
+    assert!(state.tasks.is_empty(), "The cached state should be served");
+
+    // Commands that act on the state bypass the cache.
+    let state = get_state(shared).await?;
+    assert_eq!(state.tasks.len(), 1, "The cache should be bypassed");
+
+    // Once the cache expires, the current state is served.
+    sleep_ms(2000).await;
+    let state = get_cached_state(shared).await?;
+    assert_eq!(state.tasks.len(), 1, "The cache should have expired");
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anyhow::Result;
+
+use pueue_lib::task::*;
+
+use crate::helper::*;
+
+/// Tasks that run longer than their timeout are killed and marked as `TimedOut`.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_task_timeout() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "sleep 60");
+    message.timeout = Some(1);
+    assert_success(send_message(shared, message).await?);
+
+    wait_for_task_condition(shared, 0, |task| {
+        matches!(task.status, TaskStatus::Done(TaskResult::TimedOut))
+    })
+    .await?;
+
+    Ok(())
+}
+
+/// Tasks that finish before their timeout aren't affected.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_task_finishes_before_timeout() -> Result<()> {
+    let daemon = daemon().await?;
+    let shared = &daemon.settings.shared;
+
+    let mut message = create_add_message(shared, "ls");
+    message.timeout = Some(60);
+    assert_success(send_message(shared, message).await?);
+
+    let task = wait_for_task_condition(shared, 0, |task| task.is_done()).await?;
+    assert_eq!(task.status, TaskStatus::Done(TaskResult::Success));
+
+    Ok(())
+}
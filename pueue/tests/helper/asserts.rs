@@ -52,6 +52,9 @@ pub async fn assert_worker_envs(
             task_ids: vec![task_id],
             send_logs: true,
             lines: None,
+            offset: None,
+            stderr: false,
+            archived: false,
         },
     )
     .await?;
@@ -141,12 +141,20 @@ pub fn daemon_base_setup() -> Result<(Settings, TempDir)> {
         use_unix_socket: true,
         #[cfg(not(target_os = "windows"))]
         unix_socket_path: None,
+        #[cfg(not(target_os = "windows"))]
+        unix_socket_permissions: None,
         pid_path: None,
         host: "localhost".to_string(),
         port: "51230".to_string(),
         daemon_cert: Some(tempdir_path.join("certs").join("daemon.cert")),
         daemon_key: Some(tempdir_path.join("certs").join("daemon.key")),
         shared_secret_path: Some(tempdir_path.join("secret")),
+        read_only_secret_path: None,
+        require_client_cert: false,
+        client_ca_cert: None,
+        client_cert: None,
+        client_key: None,
+        id_prefix: None,
     };
 
     let client = Client {
@@ -158,6 +166,10 @@ pub fn daemon_base_setup() -> Result<(Settings, TempDir)> {
         max_status_lines: Some(15),
         status_time_format: "%H:%M:%S".into(),
         status_datetime_format: "%Y-%m-%d %H:%M:%S".into(),
+        status_relative_time: false,
+        table_style: "horizontal".to_string(),
+        column_presets: HashMap::new(),
+        templates: HashMap::new(),
     };
 
     #[allow(deprecated)]
@@ -165,7 +177,20 @@ pub fn daemon_base_setup() -> Result<(Settings, TempDir)> {
         pause_group_on_failure: false,
         pause_all_on_failure: false,
         callback: None,
+        callback_webhook_url: None,
+        start_callback: None,
         callback_log_lines: 15,
+        max_log_size_bytes: None,
+        max_queue_age_seconds: None,
+        separate_stderr: false,
+        log_to_journald: false,
+        log_timestamps: false,
+        shell_command: None,
+        env_allowlist: Vec::new(),
+        max_kill_retries: 3,
+        boost_blockers: false,
+        status_cache_ms: None,
+        group_settings: HashMap::new(),
         groups: None,
     };
 
@@ -174,6 +199,7 @@ pub fn daemon_base_setup() -> Result<(Settings, TempDir)> {
         daemon,
         shared,
         profiles: HashMap::new(),
+        default_profile: None,
     };
 
     settings
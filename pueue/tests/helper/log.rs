@@ -26,6 +26,9 @@ pub async fn get_task_log(shared: &Shared, task_id: usize, lines: Option<usize>)
         task_ids: vec![task_id],
         send_logs: true,
         lines,
+        offset: None,
+        stderr: false,
+        archived: false,
     };
     let response = send_message(shared, message).await?;
 
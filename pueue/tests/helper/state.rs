@@ -8,7 +8,7 @@ use super::*;
 
 /// Convenience function for getting the current state from the daemon.
 pub async fn get_state(shared: &Shared) -> Result<Box<State>> {
-    let response = send_message(shared, Message::Status).await?;
+    let response = send_message(shared, Message::FreshStatus).await?;
     match response {
         Message::StatusResponse(state) => Ok(state),
         _ => bail!("Didn't get status response in get_state"),
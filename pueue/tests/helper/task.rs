@@ -23,6 +23,24 @@ pub fn create_add_message(shared: &Shared, command: &str) -> AddMessage {
         dependencies: Vec::new(),
         label: None,
         print_task_id: false,
+        timeout: None,
+        retries: 0,
+        retry_delay: 0,
+        user: None,
+        path_is_default: false,
+        priority: 0,
+        cpu_affinity: None,
+        nice: None,
+        when_output_contains: None,
+        shell: None,
+        manual_start: false,
+        only_if_success: true,
+        label_color: None,
+        touch_on_done: None,
+        weight: 1,
+        job_id: None,
+        idempotency_key: None,
+        no_inherit_env: false,
     }
 }
 
@@ -49,7 +67,7 @@ pub async fn get_task(shared: &Shared, task_id: usize) -> Result<Task> {
     let state = get_state(shared).await?;
     let task = state
         .tasks
-        .get(&0)
+        .get(&task_id)
         .ok_or_else(|| anyhow!("Couldn't find task {task_id}"))?;
 
     Ok(task.clone())
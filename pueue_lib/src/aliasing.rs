@@ -34,24 +34,101 @@ pub fn get_aliases(settings: &Settings) -> Result<HashMap<String, String>, Error
 
 /// Check if there exists an alias for a given command.
 /// Only the first word will be replaced.
-pub fn insert_alias(settings: &Settings, command: String) -> String {
+///
+/// Aliases may contain positional placeholders, e.g. `./deploy.sh {{1}} --env {{2}}`.
+/// In that case, the placeholders are replaced by the respective arguments following the alias.
+/// Arguments are split like a shell would, so quoted arguments are passed as a single argument.
+/// Arguments that aren't referenced by any placeholder are appended to the expanded alias.
+/// An error is returned, if a placeholder references an argument that doesn't exist.
+pub fn insert_alias(settings: &Settings, command: String) -> Result<String, Error> {
     // Get the first word of the command.
     let first = match command.split_whitespace().next() {
         Some(first) => first,
-        None => return command,
+        None => return Ok(command),
     };
 
     let aliases = match get_aliases(settings) {
         Err(err) => {
             info!("Couldn't read aliases file: {err}");
-            return command;
+            return Ok(command);
         }
         Ok(aliases) => aliases,
     };
 
-    if let Some(alias) = aliases.get(first) {
-        return command.replacen(first, alias, 1);
+    let Some(alias) = aliases.get(first) else {
+        return Ok(command);
+    };
+
+    // Aliases without placeholders simply replace the first word.
+    if !alias.contains("{{") {
+        return Ok(command.replacen(first, alias, 1));
+    }
+
+    let rest = command.trim_start()[first.len()..].to_string();
+    let arguments = shell_words::split(&rest).map_err(|err| {
+        Error::Generic(format!(
+            "Failed to split the arguments of alias \"{first}\": {err}"
+        ))
+    })?;
+    match expand_placeholders(first, alias, &arguments)? {
+        Some(expanded) => Ok(expanded),
+        None => Ok(command.replacen(first, alias, 1)),
+    }
+}
+
+/// Replace all `{{N}}` placeholders of an alias with the N-th argument (starting at 1).
+/// Arguments are quoted, so they're passed to the shell as they were given.
+/// Arguments that aren't referenced by any placeholder are appended.
+///
+/// Returns `None`, if the alias doesn't contain any placeholders.
+fn expand_placeholders(
+    name: &str,
+    alias: &str,
+    arguments: &[String],
+) -> Result<Option<String>, Error> {
+    let mut used = vec![false; arguments.len()];
+    let mut found_placeholder = false;
+    let mut command = String::new();
+    let mut rest = alias;
+
+    while let Some(start) = rest.find("{{") {
+        command.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let digits = after
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(after.len());
+
+        // Only `{{N}}` is a placeholder, all other braces are kept as they are.
+        if digits == 0 || !after[digits..].starts_with("}}") {
+            command.push_str("{{");
+            rest = after;
+            continue;
+        }
+
+        let index: usize = after[..digits].parse().unwrap_or(0);
+        let Some(argument) = index.checked_sub(1).and_then(|i| arguments.get(i)) else {
+            return Err(Error::Generic(format!(
+                "Alias \"{name}\" requires an argument for placeholder {{{{{index}}}}}"
+            )));
+        };
+        used[index - 1] = true;
+        found_placeholder = true;
+        command.push_str(&shell_words::quote(argument));
+        rest = &after[digits + 2..];
+    }
+    command.push_str(rest);
+
+    if !found_placeholder {
+        return Ok(None);
+    }
+
+    // Append all arguments that haven't been used by any placeholder.
+    for (argument, used) in arguments.iter().zip(used) {
+        if !used {
+            command.push(' ');
+            command.push_str(&shell_words::quote(argument));
+        }
     }
 
-    command
+    Ok(Some(command))
 }
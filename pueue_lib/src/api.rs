@@ -0,0 +1,182 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use chrono::prelude::*;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::state::{Group, GroupStatus, State};
+use crate::task::{Task, TaskResult, TaskStatus};
+
+/// The version of the schema represented by [StatusV1].
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Version 1 of the machine-readable status output.
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct StatusV1 {
+    /// The version of this schema. This is always [SCHEMA_VERSION].
+    pub schema_version: u32,
+    /// All groups by their name.
+    pub groups: BTreeMap<String, GroupV1>,
+    /// All tasks, ordered by their id.
+    pub tasks: Vec<TaskV1>,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct GroupV1 {
+    pub status: GroupStatusV1,
+    pub parallel_tasks: usize,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupStatusV1 {
+    Running,
+    Paused,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct TaskV1 {
+    pub id: usize,
+    /// The command with all aliases applied.
+    pub command: String,
+    /// The command as it has been added by the user.
+    pub original_command: String,
+    pub path: PathBuf,
+    pub group: String,
+    pub label: Option<String>,
+    pub dependencies: Vec<usize>,
+    pub priority: i32,
+    pub status: TaskStatusV1,
+    /// The result of the task. This is only set, once the task is done.
+    pub result: Option<TaskResultV1>,
+    /// The exit code of the task's process, if it exited by itself.
+    pub exit_code: Option<i32>,
+    /// The number of the signal that terminated the task's process.
+    pub signal: Option<i32>,
+    /// The reason why the task's process couldn't be spawned.
+    #[serde(default)]
+    pub spawn_error: Option<String>,
+    /// The point in time a stashed task will be enqueued.
+    pub enqueue_at: Option<DateTime<Local>>,
+    pub created_at: DateTime<Local>,
+    pub start: Option<DateTime<Local>>,
+    pub end: Option<DateTime<Local>>,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskStatusV1 {
+    Queued,
+    Stashed,
+    Running,
+    Paused,
+    Done,
+    Locked,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskResultV1 {
+    Success,
+    Failed,
+    FailedToSpawn,
+    Killed,
+    Errored,
+    DependencyFailed,
+    TimedOut,
+    Skipped,
+}
+
+impl StatusV1 {
+    /// Create the representation of the given state, but only include the given tasks.
+    pub fn new(state: &State, tasks: &[Task]) -> Self {
+        StatusV1 {
+            schema_version: SCHEMA_VERSION,
+            groups: state
+                .groups
+                .iter()
+                .map(|(name, group)| (name.clone(), GroupV1::from(group)))
+                .collect(),
+            tasks: tasks.iter().map(TaskV1::from).collect(),
+        }
+    }
+}
+
+impl From<&State> for StatusV1 {
+    fn from(state: &State) -> Self {
+        let tasks: Vec<Task> = state.tasks.values().cloned().collect();
+        StatusV1::new(state, &tasks)
+    }
+}
+
+impl From<&Group> for GroupV1 {
+    fn from(group: &Group) -> Self {
+        GroupV1 {
+            status: match group.status {
+                GroupStatus::Running => GroupStatusV1::Running,
+                GroupStatus::Paused => GroupStatusV1::Paused,
+            },
+            parallel_tasks: group.parallel_tasks,
+        }
+    }
+}
+
+impl From<&Task> for TaskV1 {
+    fn from(task: &Task) -> Self {
+        let (status, result, enqueue_at) = match &task.status {
+            TaskStatus::Queued => (TaskStatusV1::Queued, None, None),
+            TaskStatus::Stashed { enqueue_at } => (TaskStatusV1::Stashed, None, *enqueue_at),
+            TaskStatus::Running => (TaskStatusV1::Running, None, None),
+            TaskStatus::Paused => (TaskStatusV1::Paused, None, None),
+            TaskStatus::Done(result) => (TaskStatusV1::Done, Some(result), None),
+            TaskStatus::Locked => (TaskStatusV1::Locked, None, None),
+        };
+
+        let signal = match result {
+            Some(TaskResult::KilledBySignal(signal)) => Some(*signal),
+            _ => None,
+        };
+
+        let spawn_error = match result {
+            Some(TaskResult::FailedToSpawn(err)) => Some(err.clone()),
+            _ => None,
+        };
+
+        let exit_code = match result {
+            Some(TaskResult::Success) => Some(0),
+            Some(TaskResult::Failed(code)) => Some(*code),
+            _ => None,
+        };
+
+        let result = result.map(|result| match result {
+            TaskResult::Success => TaskResultV1::Success,
+            TaskResult::Failed(_) => TaskResultV1::Failed,
+            TaskResult::FailedToSpawn(_) => TaskResultV1::FailedToSpawn,
+            TaskResult::Killed | TaskResult::KilledBySignal(_) => TaskResultV1::Killed,
+            TaskResult::Errored => TaskResultV1::Errored,
+            TaskResult::DependencyFailed => TaskResultV1::DependencyFailed,
+            TaskResult::TimedOut => TaskResultV1::TimedOut,
+            TaskResult::Skipped => TaskResultV1::Skipped,
+        });
+
+        TaskV1 {
+            id: task.id,
+            command: task.command.clone(),
+            original_command: task.original_command.clone(),
+            path: task.path.clone(),
+            group: task.group.clone(),
+            label: task.label.clone(),
+            dependencies: task.dependencies.clone(),
+            priority: task.priority,
+            status,
+            result,
+            exit_code,
+            signal,
+            spawn_error,
+            enqueue_at,
+            created_at: task.created_at,
+            start: task.start,
+            end: task.end,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use log::warn;
+
+use crate::error::Error;
+use crate::task::Task;
+
+/// Get the path to the archive file.
+/// The archive contains all tasks that have been removed via `pueue clean` or
+/// `pueue remove --keep-logs`.
+pub fn get_archive_path(path: &Path) -> PathBuf {
+    path.join("archive.jsonl")
+}
+
+/// Append the given tasks to the archive file.
+/// Each task is written as a single line of JSON.
+pub fn append_to_archive(path: &Path, tasks: &[Task]) -> Result<(), Error> {
+    if tasks.is_empty() {
+        return Ok(());
+    }
+
+    let mut content = String::new();
+    for task in tasks {
+        let line = serde_json::to_string(task)
+            .map_err(|err| Error::MessageSerialization(err.to_string()))?;
+        content.push_str(&line);
+        content.push('\n');
+    }
+
+    let archive_path = get_archive_path(path);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&archive_path)
+        .map_err(|err| Error::IoPathError(archive_path.clone(), "opening archive file", err))?;
+    file.write_all(content.as_bytes())
+        .map_err(|err| Error::IoPathError(archive_path, "writing archive file", err))?;
+
+    Ok(())
+}
+
+/// Read all tasks from the archive file.
+/// Lines that cannot be deserialized are skipped, so a single broken entry doesn't
+/// make the whole archive unreadable.
+pub fn read_archive(path: &Path) -> Result<Vec<Task>, Error> {
+    let archive_path = get_archive_path(path);
+    if !archive_path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let file = File::open(&archive_path)
+        .map_err(|err| Error::IoPathError(archive_path.clone(), "opening archive file", err))?;
+
+    let mut tasks = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line
+            .map_err(|err| Error::IoPathError(archive_path.clone(), "reading archive file", err))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str(&line) {
+            Ok(task) => tasks.push(task),
+            Err(err) => warn!("Skipping malformed entry in archive file: {err}"),
+        }
+    }
+
+    Ok(tasks)
+}
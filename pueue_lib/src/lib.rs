@@ -13,6 +13,12 @@
 /// Shared module for internal logic!
 /// Contains helper for command aliasing.
 pub mod aliasing;
+/// Stable and versioned representations of the daemon's state for scripts and other tools.
+/// Unlike [State](state::State) and [Task](task::Task), these types only change together with
+/// their schema version.
+pub mod api;
+/// Helper to read and write the archive of cleaned tasks.
+pub mod archive;
 /// Pueue lib's own Error implementation.
 pub mod error;
 /// Helper classes to read and write log files of Pueue's tasks.
@@ -1,5 +1,5 @@
-use std::fs::{read_dir, remove_file, File};
-use std::io::{self, prelude::*, Read, SeekFrom};
+use std::fs::{create_dir_all, read_dir, remove_file, rename, File, OpenOptions};
+use std::io::{self, prelude::*, BufReader, Read, SeekFrom};
 use std::path::{Path, PathBuf};
 
 use log::error;
@@ -7,6 +7,7 @@ use rev_buf_reader::RevBufReader;
 use snap::write::FrameEncoder;
 
 use crate::error::Error;
+use crate::task::Task;
 
 /// Get the path to the log file of a task.
 pub fn get_log_path(task_id: usize, path: &Path) -> PathBuf {
@@ -14,11 +15,38 @@ pub fn get_log_path(task_id: usize, path: &Path) -> PathBuf {
     task_log_dir.join(format!("{task_id}.log"))
 }
 
+/// Get the path to the separate stderr log file of a task.
+/// This file only exists, if the daemon has been configured with `separate_stderr`.
+pub fn get_stderr_log_path(task_id: usize, path: &Path) -> PathBuf {
+    let task_log_dir = path.join("task_logs");
+    task_log_dir.join(format!("{task_id}.stderr.log"))
+}
+
+/// Get the path to the archived logs of a task that has been removed via
+/// `pueue remove --keep-logs`.
+///
+/// Task ids are reused, which is why each archived task gets its own directory,
+/// keyed by its id and the point in time it finished.
+/// That directory has the same layout as the pueue directory, so all functions of this module can
+/// be used with it, e.g. `get_log_path(task.id, &get_log_archive_path(task, path))`.
+pub fn get_log_archive_path(task: &Task, path: &Path) -> PathBuf {
+    let end = task.end.map_or(0, |end| end.timestamp_millis());
+    path.join("archive").join(format!("{}_{end}", task.id))
+}
+
 /// Create and return the two file handles for the `(stdout, stderr)` log file of a task.
 /// These are two handles to the same file.
+///
+/// The file is opened in append mode, so all writes go to the current end of the file,
+/// even if the file has been truncated in the meantime (see [truncate_log_file]).
 pub fn create_log_file_handles(task_id: usize, path: &Path) -> Result<(File, File), Error> {
     let log_path = get_log_path(task_id, path);
-    let stdout_handle = File::create(&log_path)
+    let stdout_handle = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .truncate(false)
+        .open(&log_path)
+        .and_then(|file| file.set_len(0).map(|_| file))
         .map_err(|err| Error::IoPathError(log_path, "getting stdout handle", err))?;
     let stderr_handle = stdout_handle
         .try_clone()
@@ -27,6 +55,21 @@ pub fn create_log_file_handles(task_id: usize, path: &Path) -> Result<(File, Fil
     Ok((stdout_handle, stderr_handle))
 }
 
+/// Create and return a file handle for the separate stderr log file of a task.
+///
+/// This is used instead of the second handle of [create_log_file_handles], if the daemon is
+/// configured to keep the stderr output of tasks separate.
+pub fn create_stderr_log_file_handle(task_id: usize, path: &Path) -> Result<File, Error> {
+    let log_path = get_stderr_log_path(task_id, path);
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .truncate(false)
+        .open(&log_path)
+        .and_then(|file| file.set_len(0).map(|_| file))
+        .map_err(|err| Error::IoPathError(log_path, "getting stderr handle", err))
+}
+
 /// Return the file handle for the log file of a task.
 pub fn get_log_file_handle(task_id: usize, path: &Path) -> Result<File, Error> {
     let path = get_log_path(task_id, path);
@@ -36,14 +79,171 @@ pub fn get_log_file_handle(task_id: usize, path: &Path) -> Result<File, Error> {
     Ok(handle)
 }
 
+/// Return the file handle for the separate stderr log file of a task.
+pub fn get_stderr_log_file_handle(task_id: usize, path: &Path) -> Result<File, Error> {
+    let path = get_stderr_log_path(task_id, path);
+    let handle = File::open(&path).map_err(|err| {
+        Error::IoPathError(
+            path,
+            "getting stderr log file handle. Is `separate_stderr` enabled?",
+            err,
+        )
+    })?;
+
+    Ok(handle)
+}
+
+/// Return the handle for either the stderr or the default log file of a task.
+pub fn get_stream_log_file_handle(
+    task_id: usize,
+    path: &Path,
+    stderr: bool,
+) -> Result<File, Error> {
+    if stderr {
+        get_stderr_log_file_handle(task_id, path)
+    } else {
+        get_log_file_handle(task_id, path)
+    }
+}
+
+/// Truncate the log files of a task, if they're larger than `max_size` bytes.
+///
+/// Only the last `max_size / 2` bytes of the log are kept, starting at the beginning of a line.
+/// Keeping less than `max_size` ensures that we don't have to truncate again on every check.
+/// Returns whether any log file has been truncated.
+pub fn truncate_log_file(task_id: usize, path: &Path, max_size: u64) -> Result<bool, Error> {
+    let mut truncated = truncate_file(get_log_path(task_id, path), max_size)?;
+
+    let stderr_path = get_stderr_log_path(task_id, path);
+    if stderr_path.exists() {
+        truncated |= truncate_file(stderr_path, max_size)?;
+    }
+
+    Ok(truncated)
+}
+
+/// Truncate a single log file, if it's larger than `max_size` bytes.
+/// See [truncate_log_file].
+fn truncate_file(log_path: PathBuf, max_size: u64) -> Result<bool, Error> {
+    let mut file = OpenOptions::new()
+        .read(true)
+        .append(true)
+        .open(&log_path)
+        .map_err(|err| Error::IoPathError(log_path.clone(), "opening log file", err))?;
+
+    let size = file
+        .metadata()
+        .map_err(|err| Error::IoPathError(log_path.clone(), "reading log file metadata", err))?
+        .len();
+    if size <= max_size {
+        return Ok(false);
+    }
+
+    // Read the tail of the log and drop the first (probably incomplete) line.
+    let mut tail = Vec::new();
+    file.seek(SeekFrom::Start(size - max_size / 2))
+        .and_then(|_| file.read_to_end(&mut tail))
+        .map_err(|err| Error::IoPathError(log_path.clone(), "reading log file", err))?;
+    if let Some(newline) = tail.iter().position(|byte| *byte == b'\n') {
+        tail.drain(..=newline);
+    }
+
+    // Output that's written by the task between reading the tail and truncating the file is lost.
+    // As the task's handles are in append mode, any later output is written after the tail.
+    file.set_len(0)
+        .and_then(|_| file.write_all(&tail))
+        .map_err(|err| Error::IoPathError(log_path, "truncating log file", err))?;
+
+    Ok(true)
+}
+
+/// Read at most the last `max_bytes` bytes of the log of a task.
+///
+/// This is used to inspect the output of tasks, without reading huge log files into memory.
+pub fn read_log_tail(task_id: usize, path: &Path, max_bytes: u64) -> Result<Vec<u8>, Error> {
+    let log_path = get_log_path(task_id, path);
+    let mut file = File::open(&log_path)
+        .map_err(|err| Error::IoPathError(log_path.clone(), "opening log file", err))?;
+
+    let size = file
+        .metadata()
+        .map_err(|err| Error::IoPathError(log_path.clone(), "reading log file metadata", err))?
+        .len();
+
+    let mut tail = Vec::new();
+    file.seek(SeekFrom::Start(size.saturating_sub(max_bytes)))
+        .and_then(|_| file.take(max_bytes).read_to_end(&mut tail))
+        .map_err(|err| Error::IoPathError(log_path, "reading log file", err))?;
+
+    Ok(tail)
+}
+
+/// Move the cursor of a log file handle back to the start of the file, if the log file
+/// has been truncated behind the cursor's position.
+///
+/// This is used by readers that continuously follow a log file.
+pub fn rewind_if_truncated(file: &mut File) -> Result<(), Error> {
+    let position = file
+        .stream_position()
+        .map_err(|err| Error::IoError("getting log file position".to_string(), err))?;
+    let size = file
+        .metadata()
+        .map_err(|err| Error::IoError("reading log file metadata".to_string(), err))?
+        .len();
+
+    if position > size {
+        file.rewind()
+            .map_err(|err| Error::IoError("rewinding log file".to_string(), err))?;
+    }
+
+    Ok(())
+}
+
 /// Remove the the log files of a task.
 pub fn clean_log_handles(task_id: usize, path: &Path) {
-    let path = get_log_path(task_id, path);
-    if path.exists() {
-        if let Err(err) = remove_file(path) {
+    let stdout_path = get_log_path(task_id, path);
+    if stdout_path.exists() {
+        if let Err(err) = remove_file(stdout_path) {
             error!("Failed to remove stdout file for task {task_id} with error {err:?}");
         };
     }
+
+    let stderr_path = get_stderr_log_path(task_id, path);
+    if stderr_path.exists() {
+        if let Err(err) = remove_file(stderr_path) {
+            error!("Failed to remove stderr file for task {task_id} with error {err:?}");
+        };
+    }
+}
+
+/// Move the log files of a task into the log archive instead of removing them.
+/// See [get_log_archive_path].
+pub fn archive_log_files(task: &Task, path: &Path) -> Result<(), Error> {
+    let task_id = task.id;
+    let archive_path = get_log_archive_path(task, path);
+    let archive_log_dir = archive_path.join("task_logs");
+    create_dir_all(&archive_log_dir).map_err(|err| {
+        Error::IoPathError(archive_log_dir, "creating log archive directory", err)
+    })?;
+
+    let files = [
+        (
+            get_log_path(task_id, path),
+            get_log_path(task_id, &archive_path),
+        ),
+        (
+            get_stderr_log_path(task_id, path),
+            get_stderr_log_path(task_id, &archive_path),
+        ),
+    ];
+    for (source, target) in files {
+        if source.exists() {
+            rename(&source, target)
+                .map_err(|err| Error::IoPathError(source, "moving log file to archive", err))?;
+        }
+    }
+
+    Ok(())
 }
 
 /// Return the output of a task. \
@@ -51,16 +251,36 @@ pub fn clean_log_handles(task_id: usize, path: &Path) {
 /// Return type is `(Vec<u8>, bool)`
 /// - `Vec<u8>` the compressed task output.
 /// - `bool` Whether the full task's output has been read.
-///     `false` indicate that the log output has been truncated
+///   `false` indicate that the log output has been truncated
+///
+/// If an `offset` is given, `lines` lines starting at the line with that index are read.
+/// Otherwise, `lines` determines the amount of lines that're read from the end of the file.
+///
+/// If `stderr` is set, the separate stderr log file is read instead.
 pub fn read_and_compress_log_file(
     task_id: usize,
     path: &Path,
     lines: Option<usize>,
+    offset: Option<usize>,
+    stderr: bool,
 ) -> Result<(Vec<u8>, bool), Error> {
-    let mut file = get_log_file_handle(task_id, path)?;
+    let mut file = get_stream_log_file_handle(task_id, path, stderr)?;
 
     let mut content = Vec::new();
 
+    // Only a specific range of lines has been requested.
+    if let Some(offset) = offset {
+        let output = read_line_range(&mut file, offset, lines)?;
+        {
+            let mut compressor = FrameEncoder::new(&mut content);
+            compressor
+                .write_all(&output)
+                .map_err(|err| Error::IoError("compressing log output".to_string(), err))?;
+        }
+
+        return Ok((content, false));
+    }
+
     // Indicates whether the full log output is shown or just the last part of it.
     let mut output_complete = true;
 
@@ -93,6 +313,29 @@ pub fn read_last_log_file_lines(
     Ok(read_last_lines(&mut file, lines))
 }
 
+/// Return the length of the given bytes without a trailing incomplete UTF-8 character.
+/// Invalid byte sequences are counted as complete, they'll be replaced during a lossy conversion.
+///
+/// This is used when streaming log output in chunks, to not split multi-byte characters.
+pub fn complete_utf8_len(bytes: &[u8]) -> usize {
+    // A UTF-8 character is at most 4 bytes long.
+    // Hence, only the last 3 bytes can belong to an incomplete character.
+    let start = bytes.len().saturating_sub(3);
+    for index in (start..bytes.len()).rev() {
+        // Skip continuation bytes until we find the start of the last character.
+        if bytes[index] & 0b1100_0000 == 0b1000_0000 {
+            continue;
+        }
+
+        return match std::str::from_utf8(&bytes[index..]) {
+            Err(err) if err.error_len().is_none() => index,
+            _ => bytes.len(),
+        };
+    }
+
+    bytes.len()
+}
+
 /// Remove all files in the log directory.
 pub fn reset_task_log_directory(path: &Path) -> Result<(), Error> {
     let task_log_dir = path.join("task_logs");
@@ -202,3 +445,104 @@ pub fn seek_to_last_lines(file: &mut File, amount: usize) -> Result<bool, Error>
 
     Ok(false)
 }
+
+/// Seek the cursor of the current file to the beginning of the line with the given (zero-based)
+/// index, starting from the beginning of the file.
+///
+/// If the file has fewer lines, the cursor is placed at the end of the file.
+pub fn seek_to_line(file: &mut File, line: usize) -> Result<(), Error> {
+    file.rewind()
+        .map_err(|err| Error::IoError("seeking to start of file".to_string(), err))?;
+    if line == 0 {
+        return Ok(());
+    }
+
+    let mut total_read_bytes: u64 = 0;
+    let mut found_lines = 0;
+
+    // Read in 4KB chunks until there's either nothing left or we found `line` newline characters.
+    let mut buffer = vec![0; 4096];
+    loop {
+        let read_bytes = file
+            .read(&mut buffer)
+            .map_err(|err| Error::IoError("reading next log chunk".to_string(), err))?;
+
+        // We hit the end of the file. The cursor is already at the end.
+        if read_bytes == 0 {
+            return Ok(());
+        }
+
+        for byte in buffer[0..read_bytes].iter() {
+            total_read_bytes += 1;
+            if *byte != b'\n' {
+                continue;
+            }
+
+            found_lines += 1;
+            if found_lines == line {
+                // The cursor most likely already went past this point.
+                // Set it to the position right after the newline.
+                file.seek(SeekFrom::Start(total_read_bytes))
+                    .map_err(|err| {
+                        Error::IoError("seeking to correct position".to_string(), err)
+                    })?;
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Read `amount` lines, starting at the line with the (zero-based) index `offset`.
+/// If `amount` is `None`, everything after the offset is read.
+///
+/// Only the requested range is read from the file.
+pub fn read_line_range(
+    file: &mut File,
+    offset: usize,
+    amount: Option<usize>,
+) -> Result<Vec<u8>, Error> {
+    seek_to_line(file, offset)?;
+
+    let mut reader = BufReader::new(file);
+    let mut output = Vec::new();
+    match amount {
+        Some(amount) => {
+            for _ in 0..amount {
+                let read_bytes = reader
+                    .read_until(b'\n', &mut output)
+                    .map_err(|err| Error::IoError("reading log line".to_string(), err))?;
+                if read_bytes == 0 {
+                    break;
+                }
+            }
+        }
+        None => {
+            reader
+                .read_to_end(&mut output)
+                .map_err(|err| Error::IoError("reading log output".to_string(), err))?;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complete_utf8_len() {
+        // Complete input isn't touched.
+        assert_eq!(complete_utf8_len(b"test"), 4);
+        assert_eq!(complete_utf8_len("tést".as_bytes()), 5);
+        assert_eq!(complete_utf8_len(b""), 0);
+
+        // The trailing incomplete character is excluded.
+        let bytes = "t€".as_bytes();
+        assert_eq!(complete_utf8_len(&bytes[..2]), 1);
+        assert_eq!(complete_utf8_len(&bytes[..3]), 1);
+
+        // Invalid sequences are considered complete.
+        assert_eq!(complete_utf8_len(&[b't', 0xff]), 2);
+    }
+}
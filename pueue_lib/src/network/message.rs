@@ -5,8 +5,9 @@ use chrono::prelude::*;
 use serde_derive::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
+use crate::setting_defaults::default_true;
 use crate::state::{Group, State};
-use crate::task::Task;
+use crate::task::{default_weight, Task};
 
 /// Macro to simplify creating From implementations for each variant-contained
 /// struct; e.g. `impl_into_message!(AddMessage, Message::Add)` to make it possible
@@ -25,11 +26,22 @@ macro_rules! impl_into_message {
 /// Everything that's communicated in Pueue can be serialized as this enum.
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
 pub enum Message {
-    Add(AddMessage),
+    Add(Box<AddMessage>),
+    /// Add several tasks at once. Either all or none of them are added.
+    AddBatch(Vec<AddMessage>),
     Remove(Vec<usize>),
+    /// Remove tasks, but move their logs into the log archive instead of deleting them.
+    RemoveKeepLogs(Vec<usize>),
     Switch(SwitchMessage),
+    Reorder(ReorderMessage),
     Stash(Vec<usize>),
     Enqueue(EnqueueMessage),
+    /// Prevent queued tasks from being started by the scheduler, while they keep their position.
+    Hold(Vec<usize>),
+    /// Let held tasks be started by the scheduler again.
+    Release(Vec<usize>),
+    /// Set or remove the note of a task.
+    Note(NoteMessage),
 
     Start(StartMessage),
     Restart(RestartMessage),
@@ -54,7 +66,13 @@ pub enum Message {
     GroupResponse(GroupResponseMessage),
 
     Status,
+    /// Request the current state, bypassing the daemon's status cache.
+    /// This is used by commands that need the latest state to act on it.
+    FreshStatus,
     StatusResponse(Box<State>),
+    /// Request a single task, instead of the whole state.
+    TaskRequest(TaskRequestMessage),
+    TaskResponse(Box<Task>),
     Log(LogRequestMessage),
     LogResponse(BTreeMap<usize, TaskLogMessage>),
 
@@ -62,9 +80,22 @@ pub enum Message {
     StreamRequest(StreamRequestMessage),
     /// The next chunk of output, that's send to the client.
     Stream(String),
+    /// The next chunk of output of a specific task, if the output of all tasks is followed.
+    TaskStream(TaskStreamMessage),
 
     Reset(ResetMessage),
     Clean(CleanMessage),
+    /// Request information about the daemon process itself.
+    Info,
+    InfoResponse(InfoResponseMessage),
+    /// Request archived tasks, which have been removed via `pueue clean`.
+    History(HistoryMessage),
+    HistoryResponse(Vec<Task>),
+    /// Request the full state, which can be used as a backup.
+    Export,
+    ExportResponse(Box<State>),
+    /// Add all tasks and groups of a previously exported state.
+    Import(Box<State>),
     DaemonShutdown(Shutdown),
 
     Success(String),
@@ -76,6 +107,27 @@ pub enum Message {
     Parallel(ParallelMessage),
 }
 
+impl Message {
+    /// Whether this message only reads the daemon's state without changing it.
+    /// Only these messages are accepted from clients with read-only access.
+    /// The environment variables of tasks are hidden from those clients,
+    /// as they might contain secrets.
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Message::Status
+                | Message::FreshStatus
+                | Message::Group(GroupMessage::List)
+                | Message::TaskRequest(_)
+                | Message::Log(_)
+                | Message::StreamRequest(_)
+                | Message::Info
+                | Message::History(_)
+                | Message::Export
+        )
+    }
+}
+
 /// This enum is used to express a selection of tasks.
 /// As commands can be executed on various sets of tasks, we need some kind of datastructure to
 /// explicitly and unambiguously specify the selection.
@@ -98,6 +150,62 @@ pub struct AddMessage {
     pub dependencies: Vec<usize>,
     pub label: Option<String>,
     pub print_task_id: bool,
+    /// Kill the task, if it runs longer than this amount of seconds.
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// How often the task should be automatically re-enqueued, if it fails.
+    #[serde(default)]
+    pub retries: usize,
+    /// The initial delay in seconds before a failed task is re-enqueued.
+    #[serde(default)]
+    pub retry_delay: u64,
+    /// Run the task as this user. The daemon drops its privileges before spawning the task.
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Whether `path` is the client's current directory, as no working directory has been
+    /// specified. In that case, the group's working directory is used, if it's configured.
+    #[serde(default)]
+    pub path_is_default: bool,
+    /// Queued tasks with a higher priority are started first.
+    #[serde(default)]
+    pub priority: i32,
+    /// Pin the task to these CPU cores. This is only supported on Linux.
+    #[serde(default)]
+    pub cpu_affinity: Option<Vec<usize>>,
+    /// The scheduling priority (niceness) of the task. This is only supported on Linux.
+    #[serde(default)]
+    pub nice: Option<i32>,
+    /// Only start the task, if the output of all its dependencies contains this text.
+    #[serde(default)]
+    pub when_output_contains: Option<String>,
+    /// Execute the command with this shell instead of the daemon's default shell.
+    #[serde(default)]
+    pub shell: Option<Vec<String>>,
+    /// Don't start the task automatically. It has to be started via `pueue start`.
+    #[serde(default)]
+    pub manual_start: bool,
+    /// Only start the task, if all of its dependencies succeeded.
+    #[serde(default = "default_true")]
+    pub only_if_success: bool,
+    /// The color of the label in the status table.
+    #[serde(default)]
+    pub label_color: Option<String>,
+    /// Create an empty marker file with the task's result as suffix, once the task finished.
+    #[serde(default)]
+    pub touch_on_done: Option<PathBuf>,
+    /// The amount of parallel slots of its group the task occupies while running.
+    #[serde(default = "default_weight")]
+    pub weight: usize,
+    /// The name of the job the task belongs to.
+    #[serde(default)]
+    pub job_id: Option<String>,
+    /// If a task with this key already exists, no new task is created.
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// `envs` only contains explicitly provided variables.
+    /// The environment variables of the task's group aren't merged into them.
+    #[serde(default)]
+    pub no_inherit_env: bool,
 }
 
 /// We use a custom `Debug` implementation for [AddMessage], as the `envs` field just has
@@ -118,11 +226,33 @@ impl std::fmt::Debug for AddMessage {
             .field("dependencies", &self.dependencies)
             .field("label", &self.label)
             .field("print_task_id", &self.print_task_id)
+            .field("timeout", &self.timeout)
+            .field("retries", &self.retries)
+            .field("retry_delay", &self.retry_delay)
+            .field("user", &self.user)
+            .field("path_is_default", &self.path_is_default)
+            .field("priority", &self.priority)
+            .field("cpu_affinity", &self.cpu_affinity)
+            .field("nice", &self.nice)
+            .field("when_output_contains", &self.when_output_contains)
+            .field("shell", &self.shell)
+            .field("manual_start", &self.manual_start)
+            .field("only_if_success", &self.only_if_success)
+            .field("label_color", &self.label_color)
+            .field("touch_on_done", &self.touch_on_done)
+            .field("weight", &self.weight)
+            .field("job_id", &self.job_id)
+            .field("idempotency_key", &self.idempotency_key)
+            .field("no_inherit_env", &self.no_inherit_env)
             .finish()
     }
 }
 
-impl_into_message!(AddMessage, Message::Add);
+impl From<AddMessage> for Message {
+    fn from(message: AddMessage) -> Self {
+        Message::Add(Box::new(message))
+    }
+}
 
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
 pub struct SwitchMessage {
@@ -132,10 +262,23 @@ pub struct SwitchMessage {
 
 impl_into_message!(SwitchMessage, Message::Switch);
 
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct ReorderMessage {
+    /// The task that should be moved.
+    pub task_id: usize,
+    /// The task in front of which the task should be put.
+    pub before: usize,
+}
+
+impl_into_message!(ReorderMessage, Message::Reorder);
+
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
 pub struct EnqueueMessage {
     pub task_ids: Vec<usize>,
     pub enqueue_at: Option<DateTime<Local>>,
+    /// Additionally enqueue all stashed tasks of this group.
+    #[serde(default)]
+    pub group: Option<String>,
 }
 
 impl_into_message!(EnqueueMessage, Message::Enqueue);
@@ -154,6 +297,10 @@ pub struct RestartMessage {
     pub tasks: Vec<TaskToRestart>,
     pub start_immediately: bool,
     pub stashed: bool,
+    /// Restart all tasks in this working directory.
+    /// The daemon rejects the restart, if this directory doesn't exist.
+    #[serde(default)]
+    pub working_directory: Option<PathBuf>,
 }
 
 impl_into_message!(RestartMessage, Message::Restart);
@@ -185,7 +332,10 @@ impl_into_message!(PauseMessage, Message::Pause);
 ///
 /// This is also needed for usage in clap, since nix's Signal doesn't implement [Display] and
 /// [std::str::FromStr].
+///
+/// Signal names are parsed case-insensitively, e.g. `SIGINT`, `sigint` and `2` are all valid.
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize, Display, EnumString)]
+#[strum(ascii_case_insensitive)]
 pub enum Signal {
     #[strum(serialize = "SigInt", serialize = "sigint", serialize = "2")]
     SigInt,
@@ -199,28 +349,53 @@ pub enum Signal {
     SigStop,
 }
 
+impl Signal {
+    /// Whether this signal usually terminates the process it's sent to.
+    pub fn is_terminating(&self) -> bool {
+        !matches!(self, Signal::SigCont | Signal::SigStop)
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
 pub struct KillMessage {
     pub tasks: TaskSelection,
     pub signal: Option<Signal>,
+    /// Re-queue the killed tasks instead of marking them as `Killed`.
+    #[serde(default)]
+    pub retry: bool,
+    /// Selected tasks that haven't been started yet are marked as `Skipped`.
+    #[serde(default)]
+    pub skip_unstarted: bool,
 }
 
 impl_into_message!(KillMessage, Message::Kill);
 
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
 pub struct SendMessage {
-    pub task_id: usize,
+    /// The input is sent to all of these tasks.
+    pub task_ids: Vec<usize>,
     pub input: String,
 }
 
 impl_into_message!(SendMessage, Message::Send);
 
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct NoteMessage {
+    pub task_id: usize,
+    /// The new note of the task. `None` removes the current note.
+    pub note: Option<String>,
+}
+
+impl_into_message!(NoteMessage, Message::Note);
+
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
 pub struct EditResponseMessage {
     pub task_id: usize,
     pub command: String,
     pub path: PathBuf,
     pub label: Option<String>,
+    #[serde(default)]
+    pub dependencies: Vec<usize>,
 }
 
 impl_into_message!(EditResponseMessage, Message::EditResponse);
@@ -234,6 +409,9 @@ pub struct EditMessage {
     /// Cbor cannot represent Option<Option<T>> yet, which is why we have to utilize a
     /// boolean to indicate that the label should be released, rather than an `Some(None)`.
     pub delete_label: bool,
+    /// Replace the task's dependencies. An empty list removes all dependencies.
+    #[serde(default)]
+    pub dependencies: Option<Vec<usize>>,
 }
 
 impl_into_message!(EditMessage, Message::Edit);
@@ -245,6 +423,11 @@ pub enum GroupMessage {
         parallel_tasks: Option<usize>,
     },
     Remove(String),
+    /// Rename a group. All tasks of the group are moved to the new name.
+    Rename {
+        name: String,
+        new_name: String,
+    },
     List,
 }
 
@@ -258,7 +441,11 @@ pub struct GroupResponseMessage {
 impl_into_message!(GroupResponseMessage, Message::GroupResponse);
 
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
-pub struct ResetMessage {}
+pub struct ResetMessage {
+    /// Only reset this group. If this isn't set, everything is reset.
+    #[serde(default)]
+    pub group: Option<String>,
+}
 
 impl_into_message!(ResetMessage, Message::Reset);
 
@@ -273,6 +460,27 @@ pub struct CleanMessage {
 
 impl_into_message!(CleanMessage, Message::Clean);
 
+/// The status of archived tasks that should be returned by a [HistoryMessage].
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize, Display, EnumString)]
+pub enum HistoryStatus {
+    #[strum(serialize = "success", serialize = "Success")]
+    Success,
+    #[strum(serialize = "failed", serialize = "Failed")]
+    Failed,
+}
+
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct HistoryMessage {
+    /// Only return tasks that finished after this point in time.
+    pub since: Option<DateTime<Local>>,
+    /// Only return tasks of this group.
+    pub group: Option<String>,
+    /// Only return tasks with this status.
+    pub status: Option<HistoryStatus>,
+}
+
+impl_into_message!(HistoryMessage, Message::History);
+
 /// Determines which type of shutdown we're dealing with.
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
 pub enum Shutdown {
@@ -280,6 +488,9 @@ pub enum Shutdown {
     Emergency,
     /// Graceful is user initiated and expected.
     Graceful,
+    /// Stop scheduling new tasks and wait for all running tasks to finish before shutting down.
+    /// Tasks that are still running after `timeout` seconds are killed.
+    Drain { timeout: Option<u64> },
 }
 
 impl_into_message!(Shutdown, Message::DaemonShutdown);
@@ -288,20 +499,74 @@ impl_into_message!(Shutdown, Message::DaemonShutdown);
 pub struct StreamRequestMessage {
     pub task_id: Option<usize>,
     pub lines: Option<usize>,
+    /// Follow the output of all running tasks, instead of a single one.
+    /// The output is then sent via [TaskStreamMessage]s.
+    #[serde(default)]
+    pub all: bool,
+    /// Start streaming at this byte offset of the log, instead of its start or its last `lines`.
+    /// This is used to continue streaming right after a previously read log.
+    #[serde(default)]
+    pub offset: Option<u64>,
 }
 
 impl_into_message!(StreamRequestMessage, Message::StreamRequest);
 
+/// A chunk of output of a specific task, which is sent while following the output of all tasks.
+/// `finished` indicates that the task finished and that no further output will be sent for it.
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct TaskStreamMessage {
+    pub task_id: usize,
+    pub text: String,
+    pub finished: bool,
+}
+
+impl_into_message!(TaskStreamMessage, Message::TaskStream);
+
+/// Information about the running daemon, which is shown by `pueue info`.
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct InfoResponseMessage {
+    pub version: String,
+    /// The point in time the daemon has been started.
+    pub start: DateTime<Local>,
+    pub pid: u32,
+    /// The amount of currently running tasks.
+    pub running_tasks: usize,
+    /// The configuration file the daemon has read on startup, if any.
+    pub config_path: Option<PathBuf>,
+    /// The prefix that's used to render task ids, if any.
+    #[serde(default)]
+    pub id_prefix: Option<String>,
+}
+
+impl_into_message!(InfoResponseMessage, Message::InfoResponse);
+
+/// Request a single task, without having to fetch the whole state.
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+pub struct TaskRequestMessage {
+    pub id: usize,
+}
+
+impl_into_message!(TaskRequestMessage, Message::TaskRequest);
+
 /// Request logs for specific tasks.
 ///
 /// `task_ids` specifies the requested tasks. If none are given, all tasks are selected.
 /// `send_logs` Determines whether logs should be sent at all.
 /// `lines` Determines whether only a few lines of log should be returned.
+/// `offset` Return `lines` lines starting at this (zero-based) line, instead of the last lines.
 #[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
 pub struct LogRequestMessage {
     pub task_ids: Vec<usize>,
     pub send_logs: bool,
     pub lines: Option<usize>,
+    #[serde(default)]
+    pub offset: Option<usize>,
+    /// Send the separate stderr log instead of the default log.
+    #[serde(default)]
+    pub stderr: bool,
+    /// Send the logs of tasks that have been removed via `pueue remove --keep-logs`.
+    #[serde(default)]
+    pub archived: bool,
 }
 
 impl_into_message!(LogRequestMessage, Message::Log);
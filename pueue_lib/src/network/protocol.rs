@@ -24,11 +24,21 @@ where
     let message: Message = message.into();
     debug!("Sending message: {message:#?}",);
     // Prepare command for transfer and determine message byte size
-    let payload = to_vec(&message).map_err(|err| Error::MessageDeserialization(err.to_string()))?;
+    let payload = serialize_message(message)?;
 
     send_bytes(&payload, stream).await
 }
 
+/// Serialize a message into the bytes that are sent by [send_message].
+/// This allows to send the same message multiple times via [send_bytes],
+/// without serializing it again.
+pub fn serialize_message<T>(message: T) -> Result<Vec<u8>, Error>
+where
+    T: Into<Message>,
+{
+    to_vec(&message.into()).map_err(|err| Error::MessageDeserialization(err.to_string()))
+}
+
 /// Send a Vec of bytes.
 /// This is part of the basic protocol beneath all communication. \
 ///
@@ -1,3 +1,4 @@
+use std::ffi::OsString;
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
@@ -6,8 +7,32 @@ use rand::{distributions::Alphanumeric, Rng};
 
 use crate::error::Error;
 
-/// Read the shared secret from a file.
+/// The environment variable that may contain the shared secret.
+/// If it's set, it takes precedence over the secret file.
+pub const SECRET_ENV_VAR: &str = "PUEUE_SECRET";
+
+/// Read the shared secret.
+///
+/// The secret is taken from the [SECRET_ENV_VAR] environment variable, if it's set.
+/// Otherwise, it's read from the given file.
 pub fn read_shared_secret(path: &Path) -> Result<Vec<u8>, Error> {
+    select_secret(std::env::var_os(SECRET_ENV_VAR), path)
+}
+
+/// Use the secret from the environment, if it's set and not empty.
+/// Otherwise, read it from the given file.
+fn select_secret(env_secret: Option<OsString>, path: &Path) -> Result<Vec<u8>, Error> {
+    if let Some(secret) = env_secret {
+        if !secret.is_empty() {
+            return Ok(secret.to_string_lossy().into_owned().into_bytes());
+        }
+    }
+
+    read_secret_file(path)
+}
+
+/// Read a secret from the given file, without considering the [SECRET_ENV_VAR].
+pub fn read_secret_file(path: &Path) -> Result<Vec<u8>, Error> {
     let mut file = File::open(path).map_err(|err| {
         Error::IoPathError(
             path.to_path_buf(),
@@ -60,3 +85,26 @@ pub fn init_shared_secret(path: &Path) -> Result<(), Error> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    /// The secret from the environment takes precedence over the secret file.
+    fn test_secret_from_env() -> Result<(), Error> {
+        let tempdir = tempdir::TempDir::new("pueue_lib").unwrap();
+        let path = tempdir.path().join("secret");
+        std::fs::write(&path, "file_secret").unwrap();
+
+        let secret = select_secret(Some("env_secret".into()), &path)?;
+        assert_eq!(secret, b"env_secret");
+        // An empty variable is ignored.
+        assert_eq!(select_secret(Some("".into()), &path)?, b"file_secret");
+        assert_eq!(select_secret(None, &path)?, b"file_secret");
+
+        Ok(())
+    }
+}
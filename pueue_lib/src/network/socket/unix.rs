@@ -1,4 +1,6 @@
 use std::convert::TryFrom;
+use std::fs::Permissions;
+use std::os::unix::fs::PermissionsExt;
 
 use async_trait::async_trait;
 use log::info;
@@ -143,7 +145,15 @@ pub async fn get_listener(settings: &Shared) -> Result<GenericListener, Error> {
         }
 
         let unix_listener = UnixListener::bind(&socket_path)
-            .map_err(|err| Error::IoPathError(socket_path, "creating unix socket", err))?;
+            .map_err(|err| Error::IoPathError(socket_path.clone(), "creating unix socket", err))?;
+
+        // Restrict or widen the access to the socket, if the user configured it.
+        if let Some(mode) = settings.unix_socket_permissions {
+            std::fs::set_permissions(&socket_path, Permissions::from_mode(mode)).map_err(
+                |err| Error::IoPathError(socket_path, "setting unix socket permissions", err),
+            )?;
+        }
+
         return Ok(Box::new(unix_listener));
     }
 
@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use tokio_rustls::{TlsAcceptor, TlsConnector};
 
+use rustls::server::AllowAnyAuthenticatedClient;
 use rustls::{Certificate, ClientConfig, PrivateKey, RootCertStore, ServerConfig};
 use rustls_pemfile::{pkcs8_private_keys, rsa_private_keys};
 
@@ -13,7 +14,7 @@ use crate::settings::Shared;
 
 /// Initialize our client [TlsConnector]. \
 /// 1. Trust our own CA. ONLY our own CA.
-/// 2. Set the client certificate and key
+/// 2. Set the client certificate and key, if they're configured.
 pub async fn get_tls_connector(settings: &Shared) -> Result<TlsConnector, Error> {
     // Only trust server-certificates signed with our own CA.
     let ca = load_ca(&settings.daemon_cert())?;
@@ -22,30 +23,61 @@ pub async fn get_tls_connector(settings: &Shared) -> Result<TlsConnector, Error>
         Error::CertificateFailure(format!("Failed to build RootCertStore: {err}"))
     })?;
 
-    let config: ClientConfig = ClientConfig::builder()
+    let builder = ClientConfig::builder()
         .with_safe_default_cipher_suites()
         .with_safe_default_kx_groups()
         .with_safe_default_protocol_versions()
         .expect("Couldn't enforce TLS1.2 and TLS 1.3. This is a bug.")
-        .with_root_certificates(cert_store)
-        .with_no_client_auth();
+        .with_root_certificates(cert_store);
+
+    // Present a client certificate to the daemon, if one is configured.
+    let config: ClientConfig = match (settings.client_cert(), settings.client_key()) {
+        (Some(cert_path), Some(key_path)) => {
+            let certs = load_certs(&cert_path)?;
+            let key = load_key(&key_path)?;
+            builder.with_single_cert(certs, key).map_err(|err| {
+                Error::CertificateFailure(format!("Failed to set client certificate: {err}"))
+            })?
+        }
+        _ => builder.with_no_client_auth(),
+    };
 
     Ok(TlsConnector::from(Arc::new(config)))
 }
 
 /// Configure the server using rusttls. \
 /// A TLS server needs a certificate and a fitting private key.
+///
+/// If `require_client_cert` is set, clients have to present a certificate that's signed by the
+/// configured client CA. Otherwise the TLS handshake fails and the connection is rejected.
 pub fn get_tls_listener(settings: &Shared) -> Result<TlsAcceptor, Error> {
     // Set the server-side key and certificate that should be used for all communication.
     let certs = load_certs(&settings.daemon_cert())?;
     let key = load_key(&settings.daemon_key())?;
 
-    let config = ServerConfig::builder()
+    let builder = ServerConfig::builder()
         .with_safe_default_cipher_suites()
         .with_safe_default_kx_groups()
         .with_safe_default_protocol_versions()
-        .expect("Couldn't enforce TLS1.2 and TLS 1.3. This is a bug.")
-        .with_no_client_auth()
+        .expect("Couldn't enforce TLS1.2 and TLS 1.3. This is a bug.");
+
+    let builder = if settings.require_client_cert {
+        let ca_path = settings.client_ca_cert().ok_or_else(|| {
+            Error::CertificateFailure(
+                "`require_client_cert` is set, but no `client_ca_cert` is configured.".into(),
+            )
+        })?;
+        let mut client_cert_store = RootCertStore::empty();
+        client_cert_store.add(&load_ca(&ca_path)?).map_err(|err| {
+            Error::CertificateFailure(format!("Failed to build client RootCertStore: {err}"))
+        })?;
+
+        builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(client_cert_store))
+    } else {
+        builder.with_no_client_auth()
+    };
+
+    let config = builder
         .with_single_cert(certs, key)
         .map_err(|err| Error::CertificateFailure(format!("Failed to build TLS Acceptor: {err}")))?;
 
@@ -1,6 +1,25 @@
 use libproc::libproc::{proc_pid, task_info};
+use libproc::processes::{pids_by_type, ProcFilter};
+use log::warn;
 
 /// Check, whether a specific process exists or not
 pub fn process_exists(pid: u32) -> bool {
     proc_pid::pidinfo::<task_info::TaskInfo>(pid.try_into().unwrap(), 0).is_ok()
 }
+
+/// Get the ids of all processes of a process group, including the group leader.
+pub fn get_process_group_pids(pgid: u32) -> Vec<u32> {
+    match pids_by_type(ProcFilter::ByProgramGroup { pgrpid: pgid }) {
+        Err(error) => {
+            warn!("Failed to get list of processes in process group {pgid}: {error}");
+            Vec::new()
+        }
+        Ok(mut processes) => {
+            // MacOS doesn't list the main process in this group
+            if !processes.contains(&pgid) && process_exists(pgid) {
+                processes.push(pgid)
+            }
+            processes
+        }
+    }
+}
@@ -1,5 +1,15 @@
+use std::io;
+use std::os::unix::process::CommandExt;
+use std::process::Command;
+
+use log::warn;
+use nix::sched::{sched_setaffinity, CpuSet};
+use nix::unistd::Pid;
 use procfs::process;
 
+/// The range of valid scheduling priorities (niceness) of a process.
+const NICE_RANGE: std::ops::RangeInclusive<i32> = -20..=19;
+
 /// Check, whether a specific process is exists or not
 pub fn process_exists(pid: u32) -> bool {
     match pid.try_into() {
@@ -10,3 +20,89 @@ pub fn process_exists(pid: u32) -> bool {
         },
     }
 }
+
+/// Get the ids of all processes of a process group, including the group leader.
+pub fn get_process_group_pids(pgid: u32) -> Vec<u32> {
+    let processes = match process::all_processes() {
+        Ok(processes) => processes,
+        Err(error) => {
+            warn!("Failed to get list of processes in process group {pgid}: {error}");
+            return Vec::new();
+        }
+    };
+
+    processes
+        .filter_map(|process| process.ok())
+        .filter(|process| {
+            process
+                .stat()
+                .map(|stat| stat.pgrp as u32 == pgid)
+                .unwrap_or(false)
+        })
+        .map(|process| process.pid as u32)
+        .collect()
+}
+
+/// Pin the given command to the given CPU cores before it's executed.
+///
+/// The CPU set is built in advance, so invalid core ids result in an error
+/// instead of running the command without an affinity.
+pub fn set_process_cpu_affinity(command: &mut Command, cpus: &[usize]) -> io::Result<()> {
+    let mut cpu_set = CpuSet::new();
+    for cpu in cpus {
+        cpu_set.set(*cpu).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("The CPU core {cpu} doesn't exist"),
+            )
+        })?;
+    }
+
+    // This is safe, as `sched_setaffinity` is async-signal-safe and doesn't allocate.
+    unsafe {
+        command.pre_exec(move || {
+            sched_setaffinity(Pid::from_raw(0), &cpu_set)?;
+            Ok(())
+        });
+    }
+
+    Ok(())
+}
+
+/// Run the given command with the given scheduling priority (niceness).
+///
+/// Values outside of the valid range of `-20` to `19` are clamped to the nearest valid value.
+/// Lowering the niceness below zero usually requires elevated privileges.
+pub fn set_process_nice(command: &mut Command, nice: i32) -> io::Result<()> {
+    let clamped = nice.clamp(*NICE_RANGE.start(), *NICE_RANGE.end());
+    if clamped != nice {
+        warn!("The nice value {nice} is out of range. Using {clamped} instead.");
+    }
+
+    // This is safe, as `setpriority` is async-signal-safe and doesn't allocate.
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setpriority(libc::PRIO_PROCESS, 0, clamped) == -1 {
+                return Err(io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+
+    Ok(())
+}
+
+/// Change the scheduling priority (niceness) of all processes of an already running process group.
+///
+/// Values outside of the valid range of `-20` to `19` are clamped to the nearest valid value.
+/// Lowering the niceness usually requires elevated privileges.
+pub fn set_process_group_nice(pgid: u32, nice: i32) -> io::Result<()> {
+    let clamped = nice.clamp(*NICE_RANGE.start(), *NICE_RANGE.end());
+
+    // This is safe, as `setpriority` doesn't access any memory of this process.
+    if unsafe { libc::setpriority(libc::PRIO_PGRP, pgid, clamped) } == -1 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
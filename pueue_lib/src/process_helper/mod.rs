@@ -4,6 +4,8 @@
 //! each supported platform.
 //! Depending on the target, the respective platform is read and loaded into this scope.
 
+use std::process::Command;
+
 use crate::network::message::Signal as InternalSignal;
 
 // Unix specific process handling
@@ -19,20 +21,62 @@ use command_group::Signal;
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "linux")]
-pub use self::linux::process_exists;
+pub use self::linux::{
+    get_process_group_pids, process_exists, set_process_cpu_affinity, set_process_group_nice,
+    set_process_nice,
+};
 
 // Apple specific process support
 #[cfg(target_vendor = "apple")]
 mod apple;
 #[cfg(target_vendor = "apple")]
-pub use self::apple::process_exists;
+pub use self::apple::{get_process_group_pids, process_exists};
 
 // Windows specific process handling
-#[cfg(any(target_os = "windows"))]
+#[cfg(target_os = "windows")]
 mod windows;
 #[cfg(target_os = "windows")]
 pub use self::windows::*;
 
+/// Build a command that executes the given command string with a custom shell.
+///
+/// The first element of `shell` is the program, all other elements are passed as arguments,
+/// followed by the command string, e.g. `["bash", "-c"]` results in `bash -c <command>`.
+pub fn compile_custom_shell_command(shell: &[String], command_string: &str) -> Command {
+    let mut command = Command::new(&shell[0]);
+    command.args(&shell[1..]).arg(command_string);
+
+    command
+}
+
+/// Pinning processes to specific CPU cores is only supported on Linux.
+/// On all other platforms, the CPU affinity is ignored.
+#[cfg(not(target_os = "linux"))]
+pub fn set_process_cpu_affinity(
+    _command: &mut std::process::Command,
+    _cpus: &[usize],
+) -> std::io::Result<()> {
+    log::warn!("Pinning tasks to CPU cores is only supported on Linux. Ignoring CPU affinity.");
+    Ok(())
+}
+
+/// Setting the scheduling priority of tasks is only supported on Linux.
+/// On all other platforms, the nice value is ignored.
+#[cfg(not(target_os = "linux"))]
+pub fn set_process_nice(_command: &mut std::process::Command, _nice: i32) -> std::io::Result<()> {
+    log::warn!("Setting the nice value of tasks is only supported on Linux. Ignoring nice value.");
+    Ok(())
+}
+
+/// Changing the scheduling priority of running tasks is only supported on Linux.
+#[cfg(not(target_os = "linux"))]
+pub fn set_process_group_nice(_pgid: u32, _nice: i32) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "Changing the nice value of running tasks is only supported on Linux",
+    ))
+}
+
 /// Pueue directly interacts with processes.
 /// Since these interactions can vary depending on the current platform, this enum is introduced.
 /// The intend is to keep any platform specific code out of the top level code.
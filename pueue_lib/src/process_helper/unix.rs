@@ -1,4 +1,8 @@
-use std::process::Command;
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+use std::ffi::CString;
+use std::io;
+use std::os::unix::process::{CommandExt, ExitStatusExt};
+use std::process::{Command, ExitStatus};
 
 // We allow anyhow in here, as this is a module that'll be strictly used internally.
 // As soon as it's obvious that this is code is intended to be exposed to library users, we have to
@@ -6,6 +10,9 @@ use std::process::Command;
 use anyhow::Result;
 use command_group::{GroupChild, Signal, UnixChildExt};
 use log::info;
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+use nix::unistd::getgrouplist;
+use nix::unistd::{setgid, setuid, Gid, User};
 
 pub fn compile_shell_command(command_string: &str) -> Command {
     let mut command = Command::new("sh");
@@ -14,6 +21,81 @@ pub fn compile_shell_command(command_string: &str) -> Command {
     command
 }
 
+/// Make the given command drop its privileges to the given user before it's executed.
+///
+/// The uid, gid and supplementary groups of the user are resolved in advance, so a non-existing
+/// user results in an error instead of running the command with the privileges of the daemon.
+/// `HOME`, `USER` and `LOGNAME` are set to the values of the user.
+/// This has to be called after the environment of the command has been set up.
+pub fn set_process_user(command: &mut Command, user_name: &str) -> io::Result<()> {
+    let user = User::from_name(user_name)?.ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("The user \"{user_name}\" doesn't exist"),
+        )
+    })?;
+    let (uid, gid) = (user.uid, user.gid);
+    let groups = supplementary_groups(&user)?;
+
+    command
+        .env("HOME", &user.dir)
+        .env("USER", &user.name)
+        .env("LOGNAME", &user.name);
+
+    // The groups have to be changed first, as we're no longer allowed to do so after `setuid`.
+    // This is safe, as `setgroups`, `setgid` and `setuid` are async-signal-safe and don't allocate.
+    unsafe {
+        command.pre_exec(move || {
+            set_groups(&groups)?;
+            setgid(gid)?;
+            setuid(uid)?;
+            Ok(())
+        });
+    }
+
+    Ok(())
+}
+
+/// Get all groups of the given user, including its primary group.
+#[cfg(not(any(target_os = "macos", target_os = "ios")))]
+fn supplementary_groups(user: &User) -> io::Result<Vec<Gid>> {
+    let name = CString::new(user.name.as_str())
+        .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))?;
+    Ok(getgrouplist(&name, user.gid)?)
+}
+
+/// The group list cannot be resolved on Apple platforms.
+/// The task is only put into the user's primary group, so it doesn't keep the daemon's groups.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+fn supplementary_groups(user: &User) -> io::Result<Vec<Gid>> {
+    Ok(vec![user.gid])
+}
+
+/// Replace the supplementary groups of the current process.
+fn set_groups(groups: &[Gid]) -> io::Result<()> {
+    let result =
+        unsafe { libc::setgroups(groups.len() as _, groups.as_ptr() as *const libc::gid_t) };
+    if result != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok(())
+}
+
+/// Get the number of the signal that terminated a process, if it has been terminated by one.
+pub fn get_exit_signal(status: &ExitStatus) -> Option<i32> {
+    status.signal()
+}
+
+/// Get the name of a signal, e.g. `SIGTERM`.
+/// Unknown signals are represented by their number.
+pub fn signal_name(signal: i32) -> String {
+    match Signal::try_from(signal) {
+        Ok(signal) => signal.as_str().to_string(),
+        Err(_) => signal.to_string(),
+    }
+}
+
 /// Send a signal to one of Pueue's child process group handle.
 pub fn send_signal_to_child<T>(child: &mut GroupChild, signal: T) -> Result<()>
 where
@@ -73,6 +155,32 @@ mod tests {
         !process_exists(pid)
     }
 
+    #[test]
+    /// Tasks that run as a different user don't keep the daemon's groups and environment.
+    fn test_set_process_user() -> Result<()> {
+        // Changing the user requires root privileges.
+        if !nix::unistd::Uid::effective().is_root() {
+            return Ok(());
+        }
+        let user = User::from_name("nobody")?.expect("The nobody user should exist");
+
+        let mut command = compile_shell_command("id -G; echo $HOME; echo $USER");
+        command.env("HOME", "/root");
+        set_process_user(&mut command, "nobody")?;
+        let output = command.output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let lines: Vec<&str> = stdout.lines().collect();
+
+        assert!(
+            !lines[0].split_whitespace().any(|group| group == "0"),
+            "The root group shouldn't be kept: {stdout}"
+        );
+        assert_eq!(lines[1], user.dir.to_string_lossy());
+        assert_eq!(lines[2], "nobody");
+
+        Ok(())
+    }
+
     #[test]
     fn test_spawn_command() {
         let mut child = compile_shell_command("sleep 0.1")
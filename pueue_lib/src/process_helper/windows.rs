@@ -1,4 +1,4 @@
-use std::process::Command;
+use std::process::{Command, ExitStatus};
 
 // We allow anyhow in here, as this is a module that'll be strictly used internally.
 // As soon as it's obvious that this is code is intended to be exposed to library users, we have to
@@ -26,6 +26,16 @@ pub enum Signal {
     SIGSTOP,
 }
 
+/// Processes aren't terminated by signals on Windows.
+pub fn get_exit_signal(_status: &ExitStatus) -> Option<i32> {
+    None
+}
+
+/// There are no signal names on Windows, so signals are represented by their number.
+pub fn signal_name(signal: i32) -> String {
+    signal.to_string()
+}
+
 pub fn compile_shell_command(command_string: &str) -> Command {
     // Chain two `powershell` commands, one that sets the output encoding to utf8 and then the user provided one.
     let mut command = Command::new("powershell");
@@ -36,6 +46,14 @@ pub fn compile_shell_command(command_string: &str) -> Command {
     command
 }
 
+/// Running tasks as a different user isn't supported on Windows.
+pub fn set_process_user(_command: &mut Command, user_name: &str) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        format!("Can't run the task as user \"{user_name}\", as this isn't supported on Windows"),
+    ))
+}
+
 /// Send a signal to a windows process.
 pub fn send_signal_to_child<T>(child: &mut GroupChild, signal: T) -> Result<()>
 where
@@ -84,6 +102,12 @@ pub fn kill_child(task_id: usize, child: &mut GroupChild) -> std::io::Result<()>
     }
 }
 
+/// Windows doesn't have process groups.
+/// Return the given process and all of its descendants instead.
+pub fn get_process_group_pids(pgid: u32) -> Vec<u32> {
+    get_cur_task_processes(pgid)
+}
+
 /// Get current task pid, all child pid and all children's children
 /// TODO: see if this can be simplified using QueryInformationJobObject
 /// on the job object created by command_group.
@@ -20,6 +20,14 @@ pub(crate) fn default_status_datetime_format() -> String {
     "%Y-%m-%d\n%H:%M:%S".to_string()
 }
 
+pub(crate) fn default_table_style() -> String {
+    "horizontal".to_string()
+}
+
 pub(crate) fn default_callback_log_lines() -> usize {
     10
 }
+
+pub(crate) fn default_max_kill_retries() -> usize {
+    3
+}
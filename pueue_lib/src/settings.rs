@@ -4,6 +4,7 @@ use std::io::{prelude::*, BufReader};
 use std::path::{Path, PathBuf};
 
 use log::info;
+use schemars::{schema_for, JsonSchema};
 use serde_derive::{Deserialize, Serialize};
 use shellexpand::tilde;
 
@@ -11,7 +12,7 @@ use crate::error::Error;
 use crate::setting_defaults::*;
 
 /// All settings which are used by both, the client and the daemon
-#[derive(PartialEq, Eq, Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(PartialEq, Eq, Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
 pub struct Shared {
     /// Don't access this property directly, but rather use the getter with the same name.
     /// It's only public to allow proper integration testing.
@@ -43,6 +44,11 @@ pub struct Shared {
     /// The path to the unix socket.
     #[cfg(not(target_os = "windows"))]
     pub unix_socket_path: Option<PathBuf>,
+    /// The permissions of the unix socket, e.g. `0o660`.
+    /// If this isn't set, the socket is created with the default permissions.
+    #[cfg(not(target_os = "windows"))]
+    #[serde(default)]
+    pub unix_socket_permissions: Option<u32>,
 
     /// The TCP hostname/ip address.
     #[serde(default = "default_host")]
@@ -70,30 +76,65 @@ pub struct Shared {
     /// It's only public to allow proper integration testing.
     ///
     /// The path to the file containing the shared secret used to authenticate the client.
+    /// The `PUEUE_SECRET` environment variable takes precedence over this file, if it's set.
     pub shared_secret_path: Option<PathBuf>,
+    /// Don't access this property directly, but rather use the getter with the same name.
+    /// It's only public to allow proper integration testing.
+    ///
+    /// The path to a second secret, which only grants read-only access to the daemon.
+    /// Clients that authenticate with this secret can only request the status and logs.
+    /// Read-only access is disabled, if this isn't set.
+    #[serde(default)]
+    pub read_only_secret_path: Option<PathBuf>,
+
+    /// If this is set to true, the daemon only accepts TLS connections from clients that
+    /// present a certificate, which is signed by the `client_ca_cert`.
+    #[serde(default)]
+    pub require_client_cert: bool,
+    /// Don't access this property directly, but rather use the getter with the same name.
+    /// It's only public to allow proper integration testing.
+    ///
+    /// The path to the CA certificate, which is used by the daemon to validate client certificates.
+    pub client_ca_cert: Option<PathBuf>,
+    /// Don't access this property directly, but rather use the getter with the same name.
+    /// It's only public to allow proper integration testing.
+    ///
+    /// The path to the TLS certificate that's presented by the client.
+    pub client_cert: Option<PathBuf>,
+    /// Don't access this property directly, but rather use the getter with the same name.
+    /// It's only public to allow proper integration testing.
+    ///
+    /// The path to the TLS key of the client's certificate.
+    pub client_key: Option<PathBuf>,
+
+    /// A prefix for task ids in the client's output, e.g. `work` renders task 42 as `work-42`.
+    /// This helps to tell apart tasks of multiple daemons. Internally, ids stay numeric.
+    /// The prefix is configured for the daemon, clients always use the one of the daemon they're connected to.
+    #[serde(default)]
+    pub id_prefix: Option<String>,
 }
 
 /// All settings which are used by the client
-#[derive(PartialEq, Eq, Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize, JsonSchema)]
 pub struct Client {
     /// If set to true, all tasks will be restart in place, instead of creating a new task.
     /// False is the default, as you'll lose the logs of the previously failed tasks when
     /// restarting tasks in place.
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub restart_in_place: bool,
     /// Whether the client should read the logs directly from disk or whether it should
     /// request the data from the daemon via socket.
     #[serde(default = "default_true")]
     pub read_local_logs: bool,
     /// Whether the client should show a confirmation question on potential dangerous actions.
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub show_confirmation_questions: bool,
     /// Whether aliases specified in `pueue_aliases.yml` should be expanded in the `pueue status`
     /// or shown in their short form.
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub show_expanded_aliases: bool,
     /// Whether the client should use dark shades instead of regular colors.
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub dark_mode: bool,
     /// The max amount of lines each task get's in the `pueue status` view.
     pub max_status_lines: Option<usize>,
@@ -103,22 +144,106 @@ pub struct Client {
     /// The format that will be used to display datetime formats in `pueue status`.
     #[serde(default = "default_status_datetime_format")]
     pub status_datetime_format: String,
+    /// Show recent start and end times in `pueue status` relative to now, e.g. `5m ago`.
+    /// Times that are older than a day are still shown with the formats above.
+    #[serde(default)]
+    pub status_relative_time: bool,
+    /// The border style of the tables in `pueue status`.
+    /// Either `horizontal`, `full`, `rounded`, `ascii` or `none`.
+    #[serde(default = "default_table_style")]
+    pub table_style: String,
+    /// Named lists of columns that can be used via `pueue status --preset $name`.
+    #[serde(default)]
+    pub column_presets: HashMap<String, Vec<String>>,
+    /// Named task templates that can be used via `pueue add --template $name`.
+    #[serde(default)]
+    pub templates: HashMap<String, TaskTemplate>,
+}
+
+/// A template for new tasks, which is expanded by the client.
+/// Values that're explicitly passed to `pueue add` take precedence over the template's values.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub struct TaskTemplate {
+    /// The command of the task. It's used, if no command is passed to `pueue add`.
+    pub command: String,
+    /// The group the task is added to.
+    pub group: Option<String>,
+    /// The label of the task.
+    pub label: Option<String>,
+    /// Additional environment variables of the task.
+    #[serde(default)]
+    pub envs: HashMap<String, String>,
 }
 
 /// All settings which are used by the daemon
-#[derive(PartialEq, Eq, Clone, Debug, Default, Deserialize, Serialize)]
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize, JsonSchema)]
 pub struct Daemon {
     /// Whether a group should be paused as soon as a single task fails
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub pause_group_on_failure: bool,
     /// Whether the daemon (and all groups) should be paused as soon as a single task fails
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub pause_all_on_failure: bool,
     /// The callback that's called whenever a task finishes.
     pub callback: Option<String>,
+    /// An URL to which the details of a task are posted as JSON whenever a task finishes.
+    /// This can be used in addition to the `callback` command.
+    #[serde(default)]
+    pub callback_webhook_url: Option<String>,
+    /// The callback that's called whenever a task starts running.
+    #[serde(default)]
+    pub start_callback: Option<String>,
     /// The amount of log lines from stdout/stderr that are passed to the callback command.
     #[serde(default = "default_callback_log_lines")]
     pub callback_log_lines: usize,
+    /// The maximum size of a task's log file in bytes.
+    /// Once a log file grows larger, it's truncated and only the most recent output is kept.
+    #[serde(default)]
+    pub max_log_size_bytes: Option<u64>,
+    /// The maximum amount of seconds a task may be queued.
+    /// Tasks that have been queued for longer are automatically stashed.
+    #[serde(default)]
+    pub max_queue_age_seconds: Option<u64>,
+    /// Write the stderr output of tasks to a separate log file.
+    /// By default, stdout and stderr are written to the same log file.
+    #[serde(default)]
+    pub separate_stderr: bool,
+    /// Additionally forward the output of tasks to journald.
+    /// Each task is logged with the `SYSLOG_IDENTIFIER=pueue-task-<id>` field.
+    /// The output is then piped through the daemon. This is only supported on Linux.
+    #[serde(default)]
+    pub log_to_journald: bool,
+    /// Prepend each line of a task's log with the time it has been written, e.g. `[12:34:56]`.
+    /// The output is then piped through the daemon.
+    #[serde(default)]
+    pub log_timestamps: bool,
+    /// The shell that's used to execute commands, e.g. `["bash", "-c"]`.
+    /// The command of the task is passed as the last argument.
+    /// If this isn't set, `sh -c` is used on Unix and PowerShell on Windows.
+    #[serde(default)]
+    pub shell_command: Option<Vec<String>>,
+    /// The names of environment variables that are passed from the client to tasks.
+    /// All other variables are removed from new tasks.
+    /// If this is empty, all environment variables are passed.
+    #[serde(default)]
+    pub env_allowlist: Vec<String>,
+    /// How often a single task may be re-queued via `pueue kill --retry`.
+    /// Once this limit is reached, the task is killed as usual.
+    #[serde(default = "default_max_kill_retries")]
+    pub max_kill_retries: usize,
+    /// Lower the niceness of running tasks that other queued tasks depend on.
+    /// This speeds up dependency chains on busy machines.
+    /// Lowering the niceness usually requires elevated privileges and is only supported on Linux.
+    #[serde(default)]
+    pub boost_blockers: bool,
+    /// Serve the same state to all `status` requests for up to this many milliseconds,
+    /// instead of serializing the state for every single request.
+    /// This reduces the load on the daemon, if many clients poll the status.
+    #[serde(default)]
+    pub status_cache_ms: Option<u64>,
+    /// Additional configuration for specific groups, indexed by the group's name.
+    #[serde(default)]
+    pub group_settings: HashMap<String, GroupSettings>,
     /// The legacy configuration for groups
     #[serde(skip_serializing)]
     #[deprecated(
@@ -128,19 +253,109 @@ pub struct Daemon {
     pub groups: Option<HashMap<String, i64>>,
 }
 
+/// The configuration of a single group.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Deserialize, Serialize, JsonSchema)]
+pub struct GroupSettings {
+    /// The working directory of tasks that are added to this group without an explicit
+    /// working directory.
+    pub working_directory: Option<PathBuf>,
+    /// What happens, once a task of this group fails.
+    /// If this isn't set, the global `pause_group_on_failure` and `pause_all_on_failure`
+    /// settings are used.
+    #[serde(default)]
+    pub failure_policy: Option<FailurePolicy>,
+    /// Only react to failures, once this many tasks of this group failed in a row,
+    /// e.g. pause the group after the third consecutive failure.
+    /// Any successful task of the group resets the counter.
+    #[serde(default)]
+    pub pause_after_failures: Option<usize>,
+    /// A shell command that's executed before each task of this group is started,
+    /// e.g. to acquire a lock. The daemon waits for the hook to finish.
+    /// If the hook fails, the task fails without being started.
+    #[serde(default)]
+    pub pre_task_hook: Option<String>,
+    /// A shell command that's executed after each task of this group finished.
+    #[serde(default)]
+    pub post_task_hook: Option<String>,
+    /// Environment variables that are injected into all tasks of this group.
+    /// Variables that are explicitly set on a task take precedence.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+/// What should happen to a group, once one of its tasks fails.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum FailurePolicy {
+    /// Nothing happens, the group continues to start tasks.
+    Continue,
+    /// The group is paused.
+    Pause,
+    /// All queued tasks of the group are marked as skipped.
+    AbortRemaining,
+}
+
+impl GroupSettings {
+    /// The group's working directory with an expanded `~`.
+    pub fn working_directory(&self) -> Option<PathBuf> {
+        self.working_directory.as_deref().map(expand_home)
+    }
+}
+
+/// This is used, if the `client` section is missing in the config file.
+/// It has to be implemented by hand, so the same defaults as for missing fields are used.
+impl Default for Client {
+    fn default() -> Self {
+        Client {
+            restart_in_place: false,
+            read_local_logs: default_true(),
+            show_confirmation_questions: false,
+            show_expanded_aliases: false,
+            dark_mode: false,
+            max_status_lines: None,
+            status_time_format: default_status_time_format(),
+            status_datetime_format: default_status_datetime_format(),
+            status_relative_time: false,
+            table_style: default_table_style(),
+            column_presets: HashMap::new(),
+            templates: HashMap::new(),
+        }
+    }
+}
+
+/// This is used, if the `daemon` section is missing in the config file.
+/// It has to be implemented by hand, so the same defaults as for missing fields are used.
+impl Default for Daemon {
+    fn default() -> Self {
+        #[allow(deprecated)]
+        Daemon {
+            pause_group_on_failure: false,
+            pause_all_on_failure: false,
+            callback: None,
+            callback_webhook_url: None,
+            start_callback: None,
+            callback_log_lines: default_callback_log_lines(),
+            max_log_size_bytes: None,
+            max_queue_age_seconds: None,
+            separate_stderr: false,
+            log_to_journald: false,
+            log_timestamps: false,
+            shell_command: None,
+            env_allowlist: Vec::new(),
+            max_kill_retries: default_max_kill_retries(),
+            boost_blockers: false,
+            status_cache_ms: None,
+            group_settings: HashMap::new(),
+            groups: None,
+        }
+    }
+}
+
 impl Default for Settings {
     fn default() -> Self {
         Settings {
-            client: Client {
-                read_local_logs: true,
-                status_time_format: default_status_time_format(),
-                status_datetime_format: default_status_datetime_format(),
-                ..Default::default()
-            },
-            daemon: Daemon {
-                callback_log_lines: default_callback_log_lines(),
-                ..Default::default()
-            },
+            client: Client::default(),
+            daemon: Daemon::default(),
             shared: Shared {
                 #[cfg(not(target_os = "windows"))]
                 use_unix_socket: true,
@@ -149,33 +364,38 @@ impl Default for Settings {
                 ..Default::default()
             },
             profiles: HashMap::new(),
+            default_profile: None,
         }
     }
 }
 
 /// The parent settings struct. \
 /// This contains all other setting structs.
-#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize, JsonSchema)]
 pub struct Settings {
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub client: Client,
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub daemon: Daemon,
     pub shared: Shared,
-    #[serde(default = "HashMap::new")]
+    #[serde(default)]
     pub profiles: HashMap<String, NestedSettings>,
+    /// The profile that's used, if no profile is explicitly requested via `--profile`.
+    /// If this isn't set, the top-level settings are used.
+    #[serde(default)]
+    pub default_profile: Option<String>,
 }
 
 /// The nested settings struct for profiles. \
 /// In contrast to the normal `Settings` struct, this struct doesn't allow profiles.
 /// That way we prevent nested profiles and problems with self-referencing structs.
-#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize)]
+#[derive(PartialEq, Eq, Clone, Debug, Deserialize, Serialize, JsonSchema)]
 pub struct NestedSettings {
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub client: Client,
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub daemon: Daemon,
-    #[serde(default = "Default::default")]
+    #[serde(default)]
     pub shared: Shared,
 }
 
@@ -276,6 +496,22 @@ impl Shared {
             self.pueue_directory().join("shared_secret")
         }
     }
+
+    pub fn read_only_secret_path(&self) -> Option<PathBuf> {
+        self.read_only_secret_path.as_deref().map(expand_home)
+    }
+
+    pub fn client_ca_cert(&self) -> Option<PathBuf> {
+        self.client_ca_cert.as_deref().map(expand_home)
+    }
+
+    pub fn client_cert(&self) -> Option<PathBuf> {
+        self.client_cert.as_deref().map(expand_home)
+    }
+
+    pub fn client_key(&self) -> Option<PathBuf> {
+        self.client_key.as_deref().map(expand_home)
+    }
 }
 
 impl Settings {
@@ -368,6 +604,22 @@ impl Settings {
         Ok(())
     }
 
+    /// Generate a JSON Schema of the configuration file.
+    /// Editors can use it to validate `pueue.yml` and to offer completion.
+    pub fn json_schema() -> String {
+        let schema = schema_for!(Settings);
+        serde_json::to_string_pretty(&schema).expect("The schema can always be serialized")
+    }
+
+    /// Load the requested profile or, if none has been requested, the `default_profile`.
+    /// Nothing happens, if neither of them is set.
+    pub fn load_requested_profile(&mut self, profile: &Option<String>) -> Result<(), Error> {
+        match profile.clone().or_else(|| self.default_profile.clone()) {
+            Some(profile) => self.load_profile(&profile),
+            None => Ok(()),
+        }
+    }
+
     /// Try to load a profile. Error if it doesn't exist.
     pub fn load_profile(&mut self, profile: &str) -> Result<(), Error> {
         let profile = self.profiles.remove(profile).ok_or_else(|| {
@@ -424,6 +676,34 @@ mod test {
         assert_eq!(settings.shared.host, "quatschhost");
     }
 
+    /// The `default_profile` is loaded, unless another profile is explicitly requested.
+    #[test]
+    fn test_load_default_profile() {
+        let mut settings = Settings::default();
+        for name in ["personal", "work"] {
+            let mut profile = Settings::default();
+            profile.shared.host = name.to_string();
+            let profile = NestedSettings {
+                client: profile.client,
+                daemon: profile.daemon,
+                shared: profile.shared,
+            };
+            settings.profiles.insert(name.to_string(), profile);
+        }
+        settings.default_profile = Some("personal".to_string());
+
+        let mut default = settings.clone();
+        default
+            .load_requested_profile(&None)
+            .expect("The default profile exists");
+        assert_eq!(default.shared.host, "personal");
+
+        settings
+            .load_requested_profile(&Some("work".to_string()))
+            .expect("The requested profile exists");
+        assert_eq!(settings.shared.host, "work");
+    }
+
     /// A proper pueue [Error] should be thrown if the profile cannot be found.
     #[test]
     fn test_error_on_missing_profile() {
@@ -438,4 +718,29 @@ mod test {
 
         panic!("Got unexpected result when expecting missing profile error: {result:?}");
     }
+
+    /// Missing sections in the config file get the same defaults as missing fields.
+    #[test]
+    fn test_missing_client_section() {
+        let settings: Settings =
+            serde_yaml::from_str("shared: {}").expect("Settings without client section are valid");
+
+        assert_eq!(settings.client, Settings::default().client);
+        assert_eq!(settings.client.table_style, default_table_style());
+        assert!(settings.client.read_local_logs);
+    }
+
+    /// Missing sections in the config file get the same defaults as missing fields.
+    #[test]
+    fn test_missing_daemon_section() {
+        let settings: Settings =
+            serde_yaml::from_str("shared: {}").expect("Settings without daemon section are valid");
+
+        assert_eq!(settings.daemon, Settings::default().daemon);
+        assert_eq!(settings.daemon.max_kill_retries, default_max_kill_retries());
+        assert_eq!(
+            settings.daemon.callback_log_lines,
+            default_callback_log_lines()
+        );
+    }
 }
@@ -4,7 +4,7 @@ use std::sync::{Arc, Mutex};
 use serde_derive::{Deserialize, Serialize};
 
 use crate::error::Error;
-use crate::task::{Task, TaskStatus};
+use crate::task::{Task, TaskResult, TaskStatus};
 
 pub const PUEUE_DEFAULT_GROUP: &str = "default";
 
@@ -80,6 +80,11 @@ impl State {
             Some(id) => id + 1,
         };
         task.id = next_id;
+        // New tasks are always put at the end of the queue.
+        task.order = match self.tasks.values().map(|task| task.order).max() {
+            None => 0,
+            Some(order) => order + 1,
+        };
         self.tasks.insert(next_id, task);
 
         next_id
@@ -124,6 +129,33 @@ impl State {
         Ok(())
     }
 
+    /// Rename a group.
+    /// All tasks of the group are moved to the new name, while the group keeps its settings.
+    pub fn rename_group(&mut self, name: &str, new_name: &str) -> Result<(), Error> {
+        if name.eq(PUEUE_DEFAULT_GROUP) {
+            return Err(Error::Generic(
+                "You cannot rename the default group.".into(),
+            ));
+        }
+        if self.groups.contains_key(new_name) {
+            return Err(Error::Generic(format!(
+                "Group \"{new_name}\" already exists"
+            )));
+        }
+        let Some(group) = self.groups.remove(name) else {
+            return Err(Error::Generic(format!("Group \"{name}\" doesn't exist")));
+        };
+        self.groups.insert(new_name.into(), group);
+
+        for (_, task) in self.tasks.iter_mut() {
+            if task.group.eq(name) {
+                task.group = new_name.into();
+            }
+        }
+
+        Ok(())
+    }
+
     /// Set the group status (running/paused) for all groups including the default queue.
     pub fn set_status_for_all_groups(&mut self, status: GroupStatus) {
         for (_, group) in self.groups.iter_mut() {
@@ -140,6 +172,38 @@ impl State {
             .collect()
     }
 
+    /// Get the ids of all tasks that're removed when cleaning up finished tasks. \
+    /// These are all finished tasks, which aren't a dependency of an unfinished task.
+    ///
+    /// This is used by the daemon to clean the state and by the client for `pueue clean --dry-run`.
+    /// - `successful_only` Only select tasks that finished successfully.
+    /// - `group` Only select tasks of a specific group.
+    pub fn cleanable_task_ids(&self, successful_only: bool, group: Option<&str>) -> Vec<usize> {
+        let (matching, _) = self.filter_tasks(
+            |task| {
+                let finished = if successful_only {
+                    matches!(task.status, TaskStatus::Done(TaskResult::Success))
+                } else {
+                    matches!(task.status, TaskStatus::Done(_))
+                };
+
+                finished && group.map_or(true, |group| task.group == group)
+            },
+            None,
+        );
+
+        // Tasks cannot be removed, as long as unfinished tasks depend on them.
+        matching
+            .into_iter()
+            .filter(|task_id| {
+                !self.tasks.values().any(|task| {
+                    task.dependencies.contains(task_id)
+                        && !matches!(task.status, TaskStatus::Done(_))
+                })
+            })
+            .collect()
+    }
+
     /// This checks, whether some tasks match the expected filter criteria. \
     /// The first result is the list of task_ids that match these statuses. \
     /// The second result is the list of task_ids that don't match these statuses. \
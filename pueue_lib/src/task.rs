@@ -4,6 +4,7 @@ use chrono::prelude::*;
 use serde_derive::{Deserialize, Serialize};
 use strum_macros::Display;
 
+use crate::setting_defaults::default_true;
 use crate::state::PUEUE_DEFAULT_GROUP;
 
 /// This enum represents the status of the internal task handling of Pueue.
@@ -36,10 +37,27 @@ pub enum TaskResult {
     FailedToSpawn(String),
     /// Task has been actively killed by either the user or the daemon on shutdown
     Killed,
+    /// The task's process has been terminated by the given signal.
+    KilledBySignal(i32),
     /// Some kind of IO error. This should barely ever happen. Please check the daemon logs.
     Errored,
     /// A dependency of the task failed.
     DependencyFailed,
+    /// The task has been killed by the daemon, as it exceeded its timeout.
+    TimedOut,
+    /// The task hasn't been started, as the output of its dependencies didn't contain
+    /// the text it has been waiting for or as one of its dependencies has been killed.
+    Skipped,
+}
+
+/// A snapshot of the resources used by all processes of a running task.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// The resident set size of the processes in bytes.
+    pub memory: u64,
+    /// The CPU usage of the processes in tenths of a percent, i.e. `1000` equals one core.
+    /// This may exceed 1000 for processes that use multiple cores.
+    pub cpu_usage_permille: u32,
 }
 
 /// Representation of a task.
@@ -68,6 +86,102 @@ pub struct Task {
     pub prev_status: TaskStatus,
     pub start: Option<DateTime<Local>>,
     pub end: Option<DateTime<Local>>,
+    /// The amount of seconds this task may run, before it's killed by the daemon.
+    #[serde(default)]
+    pub timeout: Option<u64>,
+    /// The current resource usage of the task's process.
+    /// This is only set by the daemon while the task is running.
+    #[serde(default)]
+    pub resources: Option<ResourceUsage>,
+    /// How often this task has been re-queued after it has been killed via `pueue kill --retry`.
+    #[serde(default)]
+    pub retries: usize,
+    /// How often this task will be automatically re-enqueued, if it fails.
+    #[serde(default)]
+    pub retries_remaining: usize,
+    /// The delay in seconds before a failed task is re-enqueued.
+    /// The delay doubles with each failed attempt.
+    #[serde(default)]
+    pub retry_delay: u64,
+    /// How often this task has been automatically re-enqueued after it failed.
+    #[serde(default)]
+    pub failed_attempts: usize,
+    /// The user this task is run as.
+    /// If this isn't set, the task is run as the same user as the daemon.
+    #[serde(default)]
+    pub user: Option<String>,
+    /// Queued tasks with a higher priority are started before other tasks of the same group.
+    /// Tasks with the same priority are started by their position in the queue.
+    #[serde(default)]
+    pub priority: i32,
+    /// The CPU cores this task is pinned to. This is only supported on Linux.
+    #[serde(default)]
+    pub cpu_affinity: Option<Vec<usize>>,
+    /// The scheduling priority (niceness) the task is run with. This is only supported on Linux.
+    #[serde(default)]
+    pub nice: Option<i32>,
+    /// Only start the task, if the output of all its dependencies contains this text.
+    /// Otherwise, the task is skipped.
+    #[serde(default)]
+    pub when_output_contains: Option<String>,
+    /// The shell that's used to execute the command, e.g. `["bash", "-c"]`.
+    /// If this isn't set, the daemon's `shell_command` setting is used.
+    #[serde(default)]
+    pub shell: Option<Vec<String>>,
+    /// Queued tasks with this flag aren't started by the scheduler.
+    /// They have to be started manually via `pueue start`.
+    /// The flag is cleared once the task has been started.
+    #[serde(default)]
+    pub manual_start: bool,
+    /// Queued tasks that have been held via `pueue hold` aren't started by the scheduler,
+    /// until they're released via `pueue release` or started manually.
+    #[serde(default)]
+    pub held: bool,
+    /// Whether this task has been started immediately after it has been added.
+    /// Such tasks don't occupy a slot of their group while running.
+    #[serde(default)]
+    pub immediate: bool,
+    /// Only start this task, if all of its dependencies succeeded.
+    /// If this isn't set, the task is started as soon as all dependencies finished.
+    #[serde(default = "default_true")]
+    pub only_if_success: bool,
+    /// Whether this task has been stashed by the daemon, as it has been queued for longer
+    /// than the `max_queue_age_seconds` setting allows.
+    #[serde(default)]
+    pub stashed_by_age: bool,
+    /// The color of the label in the status table.
+    #[serde(default)]
+    pub label_color: Option<String>,
+    /// The position of this task in the queue.
+    /// Queued tasks with the same priority are started in ascending order of this field.
+    #[serde(default)]
+    pub order: usize,
+    /// Create an empty marker file once the task finished.
+    /// The result is encoded as a suffix, i.e. `<path>.success` or `<path>.failed`.
+    #[serde(default)]
+    pub touch_on_done: Option<PathBuf>,
+    /// The amount of parallel slots of its group this task occupies while running.
+    #[serde(default = "default_weight")]
+    pub weight: usize,
+    /// The name of the job this task belongs to.
+    /// Jobs are ad-hoc batches of tasks, which can be inspected and killed together.
+    #[serde(default)]
+    pub job_id: Option<String>,
+    /// A free-form note, which can be changed at any time via `pueue note`.
+    #[serde(default)]
+    pub note: Option<String>,
+    /// A key that's unique among all tasks of the state.
+    /// Adding a task with an already existing key doesn't create a new task.
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// The task runs with a clean environment, which only contains its explicitly set variables.
+    #[serde(default)]
+    pub no_inherit_env: bool,
+}
+
+/// Tasks that have been created before weights existed occupy a single slot.
+pub(crate) fn default_weight() -> usize {
+    1
 }
 
 impl Task {
@@ -96,6 +210,31 @@ impl Task {
             prev_status: starting_status,
             start: None,
             end: None,
+            timeout: None,
+            resources: None,
+            retries: 0,
+            retries_remaining: 0,
+            retry_delay: 0,
+            failed_attempts: 0,
+            user: None,
+            priority: 0,
+            cpu_affinity: None,
+            nice: None,
+            when_output_contains: None,
+            shell: None,
+            manual_start: false,
+            held: false,
+            immediate: false,
+            only_if_success: true,
+            stashed_by_age: false,
+            label_color: None,
+            order: 0,
+            touch_on_done: None,
+            weight: 1,
+            job_id: None,
+            note: None,
+            idempotency_key: None,
+            no_inherit_env: false,
         }
     }
 
@@ -116,9 +255,40 @@ impl Task {
             prev_status: TaskStatus::Queued,
             start: None,
             end: None,
+            timeout: task.timeout,
+            resources: None,
+            retries: 0,
+            retries_remaining: task.retries_remaining,
+            retry_delay: task.retry_delay,
+            failed_attempts: 0,
+            user: task.user.clone(),
+            priority: task.priority,
+            cpu_affinity: task.cpu_affinity.clone(),
+            nice: task.nice,
+            when_output_contains: task.when_output_contains.clone(),
+            shell: task.shell.clone(),
+            manual_start: false,
+            held: false,
+            immediate: false,
+            only_if_success: task.only_if_success,
+            stashed_by_age: false,
+            label_color: task.label_color.clone(),
+            order: 0,
+            touch_on_done: task.touch_on_done.clone(),
+            weight: task.weight,
+            job_id: task.job_id.clone(),
+            note: task.note.clone(),
+            idempotency_key: None,
+            no_inherit_env: task.no_inherit_env,
         }
     }
 
+    /// The number of the current attempt to run this task, starting at 1.
+    /// This increases each time the task is re-queued, either automatically or via `kill --retry`.
+    pub fn attempt(&self) -> usize {
+        1 + self.retries + self.failed_attempts
+    }
+
     /// Whether the task is having a running process managed by the TaskHandler
     pub fn is_running(&self) -> bool {
         matches!(self.status, TaskStatus::Running | TaskStatus::Paused)
@@ -174,6 +344,31 @@ impl std::fmt::Debug for Task {
             .field("prev_status", &self.prev_status)
             .field("start", &self.start)
             .field("end", &self.end)
+            .field("timeout", &self.timeout)
+            .field("resources", &self.resources)
+            .field("retries", &self.retries)
+            .field("retries_remaining", &self.retries_remaining)
+            .field("retry_delay", &self.retry_delay)
+            .field("failed_attempts", &self.failed_attempts)
+            .field("user", &self.user)
+            .field("priority", &self.priority)
+            .field("cpu_affinity", &self.cpu_affinity)
+            .field("nice", &self.nice)
+            .field("when_output_contains", &self.when_output_contains)
+            .field("shell", &self.shell)
+            .field("manual_start", &self.manual_start)
+            .field("held", &self.held)
+            .field("immediate", &self.immediate)
+            .field("only_if_success", &self.only_if_success)
+            .field("stashed_by_age", &self.stashed_by_age)
+            .field("label_color", &self.label_color)
+            .field("order", &self.order)
+            .field("touch_on_done", &self.touch_on_done)
+            .field("weight", &self.weight)
+            .field("job_id", &self.job_id)
+            .field("note", &self.note)
+            .field("idempotency_key", &self.idempotency_key)
+            .field("no_inherit_env", &self.no_inherit_env)
             .finish()
     }
 }
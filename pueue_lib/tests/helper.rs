@@ -20,6 +20,8 @@ pub fn get_shared_settings(
         use_unix_socket,
         #[cfg(not(target_os = "windows"))]
         unix_socket_path: None,
+        #[cfg(not(target_os = "windows"))]
+        unix_socket_permissions: None,
         pid_path: None,
         host: "localhost".to_string(),
         port: pick_unused_port()
@@ -28,6 +30,12 @@ pub fn get_shared_settings(
         daemon_cert: Some(tempdir_path.join("certs").join("daemon.cert")),
         daemon_key: Some(tempdir_path.join("certs").join("daemon.key")),
         shared_secret_path: Some(tempdir_path.join("secret")),
+        read_only_secret_path: None,
+        require_client_cert: false,
+        client_ca_cert: None,
+        client_cert: None,
+        client_key: None,
+        id_prefix: None,
     };
 
     (shared_settings, tempdir)
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use pretty_assertions::assert_eq;
+use rcgen::{BasicConstraints, Certificate, CertificateParams, IsCa};
 use serde_cbor::de::from_slice;
 use serde_cbor::ser::to_vec;
 use tokio::task;
@@ -47,3 +48,83 @@ async fn test_tls_socket() -> Result<()> {
 
     Ok(())
 }
+
+/// Clients without a certificate are rejected, if the daemon requires client certificates.
+#[tokio::test]
+async fn test_tls_socket_requires_client_cert() -> Result<()> {
+    better_panic::install();
+    let (mut shared_settings, _tempdir) = helper::get_shared_settings(false);
+
+    // Create new stub tls certificates/keys in our temp directory
+    create_certificates(&shared_settings).unwrap();
+    shared_settings.require_client_cert = true;
+    shared_settings.client_ca_cert = Some(shared_settings.daemon_cert());
+
+    let listener = get_listener(&shared_settings).await.unwrap();
+    let server = task::spawn(async move { listener.accept().await.is_err() });
+
+    // The client doesn't present a certificate, so the handshake has to fail.
+    // Depending on the TLS version, this is only noticed by the client on its first read.
+    let message = create_success_message("This is a test");
+    let client_result = match get_client_stream(&shared_settings).await {
+        Ok(mut client) => {
+            let _ = send_message(message, &mut client).await;
+            receive_bytes(&mut client).await.map(|_| ())
+        }
+        Err(err) => Err(err),
+    };
+
+    assert!(client_result.is_err());
+    assert!(server.await?, "The daemon shouldn't accept the connection");
+
+    Ok(())
+}
+
+/// Clients that present a certificate signed by the client CA are accepted by the daemon.
+#[tokio::test]
+async fn test_tls_socket_with_client_cert() -> Result<()> {
+    better_panic::install();
+    let (mut shared_settings, tempdir) = helper::get_shared_settings(false);
+
+    // Create new stub tls certificates/keys in our temp directory
+    create_certificates(&shared_settings).unwrap();
+
+    // Create a client CA and a client certificate that's signed by it.
+    let mut ca_params = CertificateParams::new(vec!["pueue client ca".to_string()]);
+    ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+    let ca = Certificate::from_params(ca_params)?;
+    let client = Certificate::from_params(CertificateParams::new(vec!["localhost".to_string()]))?;
+
+    let certs = tempdir.path().join("certs");
+    std::fs::write(certs.join("client_ca.cert"), ca.serialize_pem()?)?;
+    std::fs::write(
+        certs.join("client.cert"),
+        client.serialize_pem_with_signer(&ca)?,
+    )?;
+    std::fs::write(certs.join("client.key"), client.serialize_private_key_pem())?;
+
+    shared_settings.require_client_cert = true;
+    shared_settings.client_ca_cert = Some(certs.join("client_ca.cert"));
+    shared_settings.client_cert = Some(certs.join("client.cert"));
+    shared_settings.client_key = Some(certs.join("client.key"));
+
+    let listener = get_listener(&shared_settings).await.unwrap();
+    let message = create_success_message("This is a test");
+    let original_bytes = to_vec(&message).expect("Failed to serialize message.");
+
+    // Echo the received message back to the client.
+    task::spawn(async move {
+        let mut stream = listener.accept().await.unwrap();
+        let message_bytes = receive_bytes(&mut stream).await.unwrap();
+        let message: Message = from_slice(&message_bytes).unwrap();
+        send_message(message, &mut stream).await.unwrap();
+    });
+
+    let mut client = get_client_stream(&shared_settings).await.unwrap();
+    send_message(message, &mut client).await.unwrap();
+    let response_bytes = receive_bytes(&mut client).await.unwrap();
+
+    assert_eq!(response_bytes, original_bytes);
+
+    Ok(())
+}
@@ -48,4 +48,20 @@ mod tests {
 
         Ok(())
     }
+
+    /// The configured permissions are applied to the socket.
+    #[tokio::test]
+    async fn test_unix_socket_permissions() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (mut shared_settings, _tempdir) = helper::get_shared_settings(true);
+        shared_settings.unix_socket_permissions = Some(0o660);
+
+        let _listener = get_listener(&shared_settings).await?;
+
+        let metadata = std::fs::metadata(shared_settings.unix_socket_path())?;
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o660);
+
+        Ok(())
+    }
 }